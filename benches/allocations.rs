@@ -0,0 +1,137 @@
+//! How many allocations the subset searches make, counted by a wrapping global allocator.
+//!
+//! The party is a generated party of 16 guests with a planted clique of 4. The "cloned" variants
+//! are the searches as they used to be, copying every subset into a party of its own before
+//! checking it; the searches now check borrowed subsets and only copy the ones they return.
+
+use celebrities::{
+    Clique, Party,
+    clique::{Combinations, PowerSet},
+    clique2digraph,
+    generator::planted_party,
+};
+use criterion::{
+    Criterion, Throughput, criterion_group, criterion_main,
+    measurement::{Measurement, ValueFormatter},
+};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+const GUESTS: usize = 16;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+struct Counting;
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+/// Measures allocations instead of wall-clock time.
+struct Allocations;
+
+impl Measurement for Allocations {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> usize {
+        ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    fn end(&self, start: usize) -> usize {
+        ALLOCATIONS.load(Ordering::Relaxed) - start
+    }
+
+    fn add(&self, v1: &usize, v2: &usize) -> usize {
+        v1 + v2
+    }
+
+    fn zero(&self) -> usize {
+        0
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn to_f64(&self, value: &usize) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        &AllocationFormatter
+    }
+}
+
+struct AllocationFormatter;
+
+impl ValueFormatter for AllocationFormatter {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        _throughput: &Throughput,
+        _values: &mut [f64],
+    ) -> &'static str {
+        "allocs"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+}
+
+fn searches(c: &mut Criterion<Allocations>) {
+    let party = Party::from(planted_party(GUESTS, 4, 0.5, 60));
+    let keys = party.ids();
+    let mut group = c.benchmark_group("allocations");
+    group.sample_size(10);
+    group.bench_function("cliques_of_size/cloned", |b| {
+        b.iter(|| {
+            Combinations::<_, Vec<_>>::new(black_box(&party).iter(), 3)
+                .map(Party::from_members)
+                .filter(Party::is_clique)
+                .collect::<Vec<_>>()
+        });
+    });
+    group.bench_function("cliques_of_size/borrowed", |b| {
+        b.iter(|| black_box(&party).cliques_of_size(3));
+    });
+    group.bench_function("cclique_in/cloned", |b| {
+        b.iter(|| {
+            let pool = black_box(&party).iter().collect::<Vec<_>>();
+            PowerSet::<_, Vec<_>>::new(&pool)
+                .skip(1)
+                .map(|people| Party::from_members(people.into_iter().cloned()))
+                .find(|people| people.is_cclique(&party))
+        });
+    });
+    group.bench_function("cclique_in/borrowed", |b| {
+        b.iter(|| black_box(&party).cclique_in(&keys));
+    });
+    group.bench_function("clique2digraph", |b| {
+        b.iter(|| clique2digraph(black_box(&party).as_set()));
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // Every sample is the same count, which the density plots cannot draw.
+    config = Criterion::default().with_measurement(Allocations).without_plots();
+    targets = searches
+}
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+//! Checking subsets against a `KnownBy` index instead of calling `knows` for every pair.
+//!
+//! Everybody knows everybody, so every guest is a candidate and the celebrity clique, the whole
+//! party, is the last subset searched.
+
+use celebrities::{Clique, Party, Person};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const GUESTS: usize = 15;
+
+fn cclique(c: &mut Criterion) {
+    let party = (0..GUESTS)
+        .map(|id| Person::new(id, 0..GUESTS))
+        .collect::<Party>();
+    let mut group = c.benchmark_group("known_by");
+    group.sample_size(10);
+    group.bench_function("naive", |b| b.iter(|| black_box(&party).cclique()));
+    group.bench_function("indexed", |b| {
+        b.iter(|| black_box(&party).cclique_indexed());
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cclique);
+criterion_main!(benches);
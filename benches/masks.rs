@@ -0,0 +1,30 @@
+//! Searching subsets as bit masks rather than as sets of people, on 20 candidates.
+//!
+//! Guests 0 to 19 are known by everybody, but 0 also knows guest 20, whom nobody else knows,
+//! so no subset of the candidates is a celebrity clique and every one of them is checked.
+
+use celebrities::{Clique, Party, Person};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const CANDIDATES: usize = 20;
+
+fn cclique(c: &mut Criterion) {
+    let party = (0..=CANDIDATES)
+        .map(|id| {
+            let outsider = (id == 0).then_some(CANDIDATES);
+            Person::new(id, (0..CANDIDATES).chain(outsider))
+        })
+        .collect::<Party>();
+    let candidates = (0..CANDIDATES).collect();
+    let mut group = c.benchmark_group("masks");
+    group.sample_size(10);
+    group.bench_function("masks", |b| b.iter(|| black_box(&party).cclique()));
+    group.bench_function("sets", |b| {
+        b.iter(|| black_box(&party).cclique_in(&candidates));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cclique);
+criterion_main!(benches);
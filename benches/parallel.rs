@@ -0,0 +1,80 @@
+//! How `cclique_par` and `is_cclique_par` scale with the number of threads.
+//!
+//! For `cclique_par`, everybody but the last guest is known by everyone, and the first one knows
+//! the last one, so no candidate subset is a celebrity clique and the whole search space is
+//! verified. For `is_cclique_par`, guests 0, 1 and 2 of a huge party know each other and are
+//! known by everybody, who also know the next guest, so every guest has to be checked.
+
+use celebrities::{Clique, Party, Person};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use rayon::ThreadPoolBuilder;
+use std::{collections::HashSet, hint::black_box};
+
+const GUESTS: usize = 18;
+const HUGE: usize = 500_000;
+
+fn party() -> Party {
+    (0..GUESTS)
+        .map(|id| {
+            let known = (0..GUESTS - 1).chain((id == 0).then_some(GUESTS - 1));
+            Person::new(id, known)
+        })
+        .collect()
+}
+
+fn cclique_par(c: &mut Criterion) {
+    let party = party();
+    let mut group = c.benchmark_group("cclique_par");
+    group.sample_size(10);
+    group.bench_function("serial", |b| b.iter(|| black_box(&party).cclique()));
+    for threads in [1, 2, 4, 8] {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("thread pool");
+        group.bench_with_input(BenchmarkId::new("threads", threads), &party, |b, party| {
+            b.iter(|| pool.install(|| black_box(party).cclique_par()));
+        });
+    }
+    group.finish();
+}
+
+fn huge_party() -> HashSet<Person> {
+    (0..HUGE)
+        .map(|id| {
+            let known = if id < 3 {
+                vec![0, 1, 2]
+            } else {
+                vec![0, 1, 2, (id + 1) % HUGE]
+            };
+            Person::new(id, known)
+        })
+        .collect()
+}
+
+fn is_cclique_par(c: &mut Criterion) {
+    let party = huge_party();
+    let css = party
+        .iter()
+        .filter(|person| person.id < 3)
+        .cloned()
+        .collect::<HashSet<_>>();
+    let mut group = c.benchmark_group("is_cclique_par");
+    group.sample_size(10);
+    group.bench_function("serial", |b| {
+        b.iter(|| black_box(&css).is_cclique(black_box(&party)));
+    });
+    for threads in [1, 2, 4, 8] {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("thread pool");
+        group.bench_with_input(BenchmarkId::new("threads", threads), &party, |b, party| {
+            b.iter(|| pool.install(|| black_box(&css).is_cclique_par(black_box(party))));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, cclique_par, is_cclique_par);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+//! The exhaustive search over every subset of an 18-person party where everybody knows only the next guest.
+//!
+//! `cclique_in` does not prune the candidates, so all 2^18 subsets are built.
+
+use celebrities::{Clique, Party, Person};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const GUESTS: usize = 18;
+
+fn cclique_in(c: &mut Criterion) {
+    let party = (0..GUESTS)
+        .map(|id| Person::new(id, [(id + 1) % GUESTS]))
+        .collect::<Party>();
+    let everyone = party.ids();
+    let mut group = c.benchmark_group("power_set");
+    group.sample_size(10);
+    group.bench_function("cclique_in", |b| {
+        b.iter(|| black_box(&party).cclique_in(&everyone));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cclique_in);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+//! Keeping short acquaintance lists inline rather than in a `HashSet` of their own.
+//!
+//! Both parties are the same generated party of 60 guests where everybody knows 6 others on
+//! average, so almost every list fits in the 12 inline slots of a `SmallPerson`. `cclique_in` over
+//! the first 12 guests queries `knows` for every guest and every subset it checks.
+
+use celebrities::{Clique, Person, clique::person::SmallPerson, generator::random_party};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::{collections::HashSet, hint::black_box};
+
+const GUESTS: usize = 60;
+
+#[allow(clippy::cast_precision_loss)]
+fn cclique(c: &mut Criterion) {
+    let hashed = random_party(GUESTS, 6.0 / (GUESTS - 1) as f64, 61);
+    let small = hashed
+        .iter()
+        .cloned()
+        .map(SmallPerson::<usize>::from)
+        .collect::<HashSet<_>>();
+    let lists = hashed
+        .iter()
+        .map(|person| (person.id, person.known_iter().collect::<Vec<_>>()))
+        .collect::<Vec<_>>();
+    let candidates = (0..12).collect();
+    let mut group = c.benchmark_group("small");
+    group.sample_size(10);
+    group.bench_function("hash_set/build", |b| {
+        b.iter(|| {
+            (black_box(&lists).iter())
+                .map(|(id, known)| Person::new(*id, known.iter().copied()))
+                .collect::<HashSet<_>>()
+        });
+    });
+    group.bench_function("inline/build", |b| {
+        b.iter(|| {
+            (black_box(&lists).iter())
+                .map(|(id, known)| SmallPerson::<usize>::new(*id, known.iter().copied()))
+                .collect::<HashSet<_>>()
+        });
+    });
+    group.bench_function("hash_set/cclique", |b| {
+        b.iter(|| black_box(&hashed).cclique());
+    });
+    group.bench_function("inline/cclique", |b| {
+        b.iter(|| black_box(&small).cclique());
+    });
+    group.bench_function("hash_set/cclique_in", |b| {
+        b.iter(|| black_box(&hashed).cclique_in(&candidates));
+    });
+    group.bench_function("inline/cclique_in", |b| {
+        b.iter(|| black_box(&small).cclique_in(&candidates));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cclique);
+criterion_main!(benches);
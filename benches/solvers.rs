@@ -0,0 +1,53 @@
+//! Every solver on the same generated parties, with and without a planted celebrity clique.
+//!
+//! Parties come from `celebrities::generator` with fixed seeds, so runs are comparable.
+
+use celebrities::{
+    Clique, Party,
+    generator::{planted_party, random_party},
+};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const SIZES: [usize; 4] = [8, 12, 16, 20];
+const SEED: u64 = 46;
+
+fn solvers(c: &mut Criterion, name: &str, party: impl Fn(usize) -> Party) {
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+    for n in SIZES {
+        let party = party(n);
+        group.bench_with_input(BenchmarkId::new("exhaustive", n), &party, |b, party| {
+            b.iter(|| black_box(party).cclique());
+        });
+        group.bench_with_input(BenchmarkId::new("scc", n), &party, |b, party| {
+            b.iter(|| black_box(party).cclique_scc());
+        });
+        group.bench_with_input(BenchmarkId::new("indexed", n), &party, |b, party| {
+            b.iter(|| black_box(party).cclique_indexed());
+        });
+        group.bench_with_input(BenchmarkId::new("gray", n), &party, |b, party| {
+            b.iter(|| black_box(party).cclique_gray());
+        });
+        group.bench_with_input(BenchmarkId::new("elimination", n), &party, |b, party| {
+            b.iter(|| black_box(party).cclique_elimination());
+        });
+        group.bench_with_input(BenchmarkId::new("celebrity", n), &party, |b, party| {
+            b.iter(|| black_box(party).celebrity());
+        });
+    }
+    group.finish();
+}
+
+fn random(c: &mut Criterion) {
+    solvers(c, "random", |n| Party::from(random_party(n, 0.5, SEED)));
+}
+
+fn planted(c: &mut Criterion) {
+    solvers(c, "planted", |n| {
+        Party::from(planted_party(n, n / 4, 0.5, SEED))
+    });
+}
+
+criterion_group!(benches, random, planted);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+//! Looking acquaintances up in a sorted `Vec` by binary search rather than in a `HashSet`.
+//!
+//! Both parties are the same generated party of 60 guests with a planted clique of 3, where
+//! everybody knows a handful of others. `cclique_in` over the first 12 guests queries `knows` for
+//! every guest and every subset it checks.
+
+use celebrities::{Clique, clique::person::SortedPerson, generator::planted_party};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::{collections::HashSet, hint::black_box};
+
+const GUESTS: usize = 60;
+
+fn cclique(c: &mut Criterion) {
+    let hashed = planted_party(GUESTS, 3, 0.08, 53);
+    let sorted = hashed
+        .iter()
+        .cloned()
+        .map(SortedPerson::from)
+        .collect::<HashSet<_>>();
+    let candidates = (0..12).collect();
+    let mut group = c.benchmark_group("sorted");
+    group.sample_size(10);
+    group.bench_function("hash_set/cclique", |b| {
+        b.iter(|| black_box(&hashed).cclique());
+    });
+    group.bench_function("sorted_vec/cclique", |b| {
+        b.iter(|| black_box(&sorted).cclique());
+    });
+    group.bench_function("hash_set/cclique_in", |b| {
+        b.iter(|| black_box(&hashed).cclique_in(&candidates));
+    });
+    group.bench_function("sorted_vec/cclique_in", |b| {
+        b.iter(|| black_box(&sorted).cclique_in(&candidates));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cclique);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+//! Checking subsets of a party too large for `u128` masks against rows and columns of `u64` words,
+//! rather than against sets of people.
+//!
+//! There are 200 guests. Guests 0 to 13 are known by everybody, but 0 also knows guest 199, whom
+//! nobody else knows, so no subset of the candidates is a celebrity clique and every one of them
+//! is checked.
+
+use celebrities::{Clique, Party, Person};
+use criterion::{Criterion, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const GUESTS: usize = 200;
+const CANDIDATES: usize = 14;
+
+fn cclique(c: &mut Criterion) {
+    let party = (0..GUESTS)
+        .map(|id| {
+            let outsider = (id == 0).then_some(GUESTS - 1);
+            Person::new(id, (0..CANDIDATES).chain(outsider))
+        })
+        .collect::<Party>();
+    let candidates = (0..CANDIDATES).collect();
+    let mut group = c.benchmark_group("words");
+    group.sample_size(10);
+    group.bench_function("words", |b| b.iter(|| black_box(&party).cclique()));
+    group.bench_function("sets", |b| {
+        b.iter(|| black_box(&party).cclique_in(&candidates));
+    });
+    group.finish();
+}
+
+criterion_group!(benches, cclique);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+//! Builds a party from a compact description and checks that the solvers agree.
+//!
+//! Ids are bytes, so that guests collide and know each other often enough for celebrity cliques
+//! to show up. Ids known by somebody but never described are people who did not come.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use celebrities::{Clique, Party, Person, clique::DEFAULT_SEARCH_LIMIT, clique2digraph};
+use libfuzzer_sys::fuzz_target;
+use std::collections::HashSet;
+
+#[derive(Debug, Arbitrary)]
+struct Description {
+    people: Vec<(u8, Vec<u8>)>,
+}
+
+impl Description {
+    fn party(&self) -> Party {
+        (self.people.iter())
+            .map(|(id, known)| {
+                Person::new(usize::from(*id), known.iter().copied().map(usize::from))
+            })
+            .collect()
+    }
+}
+
+fuzz_target!(|description: Description| {
+    let party = description.party();
+    let ids = |css: Option<Party>| css.map(|css| css.ids());
+
+    let expected = party.cclique_elimination();
+    if let Some(css) = &expected {
+        assert!(css.is_clique(), "{css:?} in {party:?}");
+        assert!(css.is_cclique(&party), "{css:?} in {party:?}");
+    }
+    let expected = ids(expected);
+    if let Ok(css) = party.cclique_with_limit(DEFAULT_SEARCH_LIMIT) {
+        assert_eq!(ids(css), expected, "exhaustive {party:?}");
+        assert_eq!(ids(party.cclique_gray()), expected, "gray {party:?}");
+    }
+    assert_eq!(ids(party.cclique_scc()), expected, "scc {party:?}");
+    assert_eq!(ids(party.cclique_fast().0), expected, "fast {party:?}");
+    assert_eq!(
+        party.celebrity().map(|person| HashSet::from([person.id])),
+        expected.clone().filter(|ids| ids.len() == 1),
+        "celebrity {party:?}"
+    );
+
+    let graph = clique2digraph(party.as_set());
+    assert_eq!(graph.node_count(), party.len());
+    let edges = (party.iter())
+        .flat_map(|person| person.known_iter())
+        .filter(|id| party.get(*id).is_some())
+        .count();
+    assert_eq!(graph.edge_count(), edges);
+});
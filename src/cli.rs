@@ -0,0 +1,974 @@
+use celebrities::{
+    Clique, Party, Person,
+    clique::{
+        CcliqueViolation, CliqueError, DEFAULT_SEARCH_LIMIT, ViolationKind,
+        party::{Identifiers, Problem, Stats},
+    },
+    generator::{planted_party, random_party},
+    session::{Engine, Format, RenderOptions, Session, SessionError},
+};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use config::Config;
+use input::{InputFormat, read_input, read_inputs, read_text};
+use log::{Level, LevelFilter, info, warn};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::{
+    fmt::Write as _,
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+mod annotate;
+mod batch;
+mod config;
+mod input;
+mod repl;
+mod watch;
+
+/// Finds the celebrity clique of a party: the guests everybody knows, who know only each other.
+///
+/// Parties are read from files with one `id: known, ...` line per person, JSON, TOML, YAML or
+/// DOT, or from stdin when the file is `-`. Guests are identified by number or by name. Without a
+/// subcommand, the party is solved and rendered, and the book's example is used when no file is
+/// given; the subcommands read stdin instead.
+///
+/// Exits with 0 on success, 1 when the party has no celebrity clique, and 2 on errors, such as a
+/// party that cannot be read, problems `validate` found or a misused flag.
+#[derive(Debug, Parser)]
+#[command(version, args_conflicts_with_subcommands = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The party to solve and render, `-` for stdin.
+    input: Option<PathBuf>,
+
+    #[command(flatten)]
+    global: Global,
+}
+
+/// The flags every subcommand understands.
+///
+/// Those that are not given on the command line default to the values of the config file,
+/// `celebrities.toml` in the working directory unless `--config` names another.
+#[derive(Debug, Args)]
+#[allow(clippy::struct_excessive_bools)]
+struct Global {
+    /// Read the defaults of these flags from this file, rather than `celebrities.toml`.
+    #[arg(long, global = true, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Where the DOT file and images are written.
+    #[arg(long, global = true, default_value = "output")]
+    output_dir: PathBuf,
+
+    /// What Graphviz renders the graph into; repeat for several formats. PNG by default.
+    #[arg(long = "format", global = true, value_enum)]
+    formats: Vec<ImageFormat>,
+
+    /// Print the DOT graph on stdout instead of writing any file or running Graphviz, as
+    /// `--output-dir -` does too.
+    #[arg(long, global = true)]
+    dry_run: bool,
+
+    /// Only write the DOT file, without running Graphviz.
+    #[arg(long, visible_alias = "dot-only", global = true)]
+    no_render: bool,
+
+    /// Fail when Graphviz is missing, rather than only writing the DOT file.
+    #[arg(long, global = true, conflicts_with = "no_render")]
+    require_render: bool,
+
+    /// Do not draw the celebrity clique in a box of its own in the graph.
+    #[arg(long, global = true)]
+    no_cluster: bool,
+
+    /// Join two guests who know each other by one double-headed edge in the graph, not two.
+    #[arg(long, global = true)]
+    merge_mutual: bool,
+
+    /// Put a key to the colors and arrows of the graph in its corner.
+    #[arg(long, global = true)]
+    legend: bool,
+
+    /// The Graphviz layout engine; `neato`, `fdp` or `circo` suit large parties better.
+    #[arg(long, global = true, value_enum, default_value_t = LayoutEngine::Dot)]
+    engine: LayoutEngine,
+
+    /// How the celebrity clique is searched for; `auto` searches small parties exhaustively,
+    /// and larger ones with `linear`.
+    #[arg(long, global = true, value_enum, default_value_t = Algorithm::Auto)]
+    algorithm: Algorithm,
+
+    /// The Graphviz program to render with, rather than the engine's from the `PATH`.
+    #[arg(long, global = true)]
+    dot_binary: Option<PathBuf>,
+
+    /// How the input describes the party; by default, going by the extension of the file, or
+    /// else edges if every line is two ids, and text otherwise.
+    #[arg(long, global = true, value_enum)]
+    input_format: Option<InputFormat>,
+
+    /// Only accept numbers as ids, rather than names when the first id of the input is not one.
+    #[arg(long, global = true)]
+    ids: bool,
+
+    /// Explain what is being done on stderr; repeat for more detail.
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Print the answer as one JSON object on stdout, with the files written.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Only print the answer and errors.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the celebrity clique of a party.
+    Solve {
+        /// The party, read from stdin when missing or `-`. Several are merged into one.
+        #[arg(value_name = "INPUT")]
+        inputs: Vec<PathBuf>,
+        /// Fail when inputs describe the same person differently, rather than merging whom
+        /// they know.
+        #[arg(long)]
+        strict: bool,
+        /// Only consider these comma-separated guests as celebrities.
+        #[arg(long, value_delimiter = ',', value_name = "IDS", conflicts_with_all = ["dir", "watch"])]
+        candidates: Option<Vec<String>>,
+        /// Print statistics about the party on stderr before solving it.
+        #[arg(long, conflicts_with_all = ["dir", "watch"])]
+        stats: bool,
+        /// Solve every party file in this directory instead, writing each result into the
+        /// output directory and printing a summary.
+        #[arg(long, conflicts_with = "inputs")]
+        dir: Option<PathBuf>,
+        /// Also solve the files in the subdirectories of `--dir`.
+        #[arg(long, requires = "dir")]
+        recursive: bool,
+        /// Keep solving the input file again whenever it changes, until interrupted.
+        #[arg(long, requires = "inputs", conflicts_with = "dir")]
+        watch: bool,
+        /// Also render the graph each time, when watching.
+        #[arg(long, requires = "watch")]
+        render: bool,
+        /// How many milliseconds the file must stay unchanged before it is solved again.
+        #[arg(long, default_value_t = 200, requires = "watch")]
+        debounce: u64,
+    },
+    /// Write the "knows" graph of a party as DOT and render it with Graphviz.
+    Render {
+        /// The party, read from stdin when missing or `-`.
+        input: Option<PathBuf>,
+    },
+    /// Print a DOT digraph with the nodes of its celebrity clique highlighted, to pipe into
+    /// Graphviz.
+    Annotate {
+        /// The graph, read from stdin when missing or `-`.
+        input: Option<PathBuf>,
+    },
+    /// Write a random party, in one of the input formats, to a file or stdout.
+    Generate(Generation),
+    /// Edit a party at a prompt, solving and rendering it as it changes.
+    Repl {
+        /// The party to start from, instead of an empty one.
+        input: Option<PathBuf>,
+    },
+    /// Print the completion script of a shell.
+    Completions { shell: Shell },
+    /// Check that some guests are the celebrity clique of a party, without searching for it.
+    Check {
+        /// The party, read from stdin when missing or `-`.
+        input: Option<PathBuf>,
+        /// The comma-separated guests proposed as the celebrity clique.
+        #[arg(long, value_name = "GUESTS", value_delimiter = ',', required = true)]
+        clique: Vec<String>,
+    },
+    /// Print statistics about a party on stderr, without solving it.
+    Stats {
+        /// The party, read from stdin when missing or `-`.
+        input: Option<PathBuf>,
+    },
+    /// Check a party for problems without solving it, printing one `kind: ...` line for each.
+    Validate {
+        /// The party, read from stdin when missing or `-`.
+        input: Option<PathBuf>,
+        /// Succeed even when there are problems.
+        #[arg(long)]
+        warn_only: bool,
+    },
+}
+
+/// What `generate` makes, and where it puts it.
+#[derive(Debug, Args)]
+struct Generation {
+    /// The number of guests.
+    #[arg(long)]
+    n: usize,
+    /// The chance that a guest knows another one.
+    #[arg(long, default_value_t = 0.5, value_parser = probability)]
+    p: f64,
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    /// Plant a celebrity clique of this many guests, so that there is one to find.
+    #[arg(long, value_name = "SIZE")]
+    plant_clique: Option<usize>,
+    /// Write the party to this file instead of stdout, which `-` also means.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+    /// The format to write the party in [default: by the output's extension, or text].
+    #[arg(long, value_enum)]
+    output_format: Option<InputFormat>,
+}
+
+/// How a run that did not fail ended, which tells its exit code apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    /// Exits with 0: a clique was found, or the command does not look for one.
+    Done,
+    /// Exits with 1: the party has no celebrity clique, or not the one proposed to `check`.
+    NoClique,
+}
+
+impl Status {
+    const fn of(css: Option<&Party>) -> Self {
+        match css {
+            Some(_) => Self::Done,
+            None => Self::NoClique,
+        }
+    }
+}
+
+fn probability(s: &str) -> Result<f64, String> {
+    let p = s.parse::<f64>().map_err(|err| err.to_string())?;
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(format!("{p} is not between 0 and 1"))
+    }
+}
+
+/// An image format Graphviz renders the graph into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ImageFormat {
+    Png,
+    Svg,
+    Pdf,
+}
+
+impl From<ImageFormat> for Format {
+    fn from(format: ImageFormat) -> Self {
+        match format {
+            ImageFormat::Png => Self::Png,
+            ImageFormat::Svg => Self::Svg,
+            ImageFormat::Pdf => Self::Pdf,
+        }
+    }
+}
+
+/// A Graphviz layout engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum LayoutEngine {
+    Dot,
+    Neato,
+    Fdp,
+    Circo,
+    Twopi,
+    Sfdp,
+}
+
+/// A way of searching for the celebrity clique.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Algorithm {
+    /// Try every group of the people known by everybody; refuses large parties.
+    Exhaustive,
+    /// Check the people known by everybody, or else the survivors of `elimination`.
+    Linear,
+    /// Find the one group of guests who know only each other in the graph.
+    Scc,
+    /// Drop the candidates who know somebody else until none is left to drop.
+    Elimination,
+    /// `exhaustive` for parties of up to 16 guests, `linear` for larger ones.
+    #[default]
+    Auto,
+}
+
+/// The most guests `--algorithm auto` searches exhaustively.
+const AUTO_EXHAUSTIVE: usize = 16;
+
+impl Algorithm {
+    /// The algorithm this one stands for with `people` guests.
+    const fn choose(self, people: usize) -> Self {
+        match self {
+            Self::Auto if people <= AUTO_EXHAUSTIVE => Self::Exhaustive,
+            Self::Auto => Self::Linear,
+            algorithm => algorithm,
+        }
+    }
+
+    const fn name(self) -> &'static str {
+        match self {
+            Self::Exhaustive => "exhaustive",
+            Self::Linear => "linear",
+            Self::Scc => "scc",
+            Self::Elimination => "elimination",
+            Self::Auto => "auto",
+        }
+    }
+}
+
+impl From<LayoutEngine> for Engine {
+    fn from(engine: LayoutEngine) -> Self {
+        match engine {
+            LayoutEngine::Dot => Self::Dot,
+            LayoutEngine::Neato => Self::Neato,
+            LayoutEngine::Fdp => Self::Fdp,
+            LayoutEngine::Circo => Self::Circo,
+            LayoutEngine::Twopi => Self::Twopi,
+            LayoutEngine::Sfdp => Self::Sfdp,
+        }
+    }
+}
+
+impl Global {
+    /// Logs to stderr, at the level `--verbose` and `--quiet` ask for.
+    fn init_logging(&self) {
+        let level = match (self.quiet, self.verbose) {
+            (true, _) => LevelFilter::Error,
+            (false, 0) => LevelFilter::Warn,
+            (false, 1) => LevelFilter::Info,
+            (false, 2) => LevelFilter::Debug,
+            (false, _) => LevelFilter::Trace,
+        };
+        env_logger::Builder::new()
+            .filter_level(level)
+            .format(|f, record| {
+                let level = match record.level() {
+                    Level::Warn => "warning".to_owned(),
+                    level => level.as_str().to_ascii_lowercase(),
+                };
+                writeln!(f, "{level}: {}", record.args())
+            })
+            .init();
+    }
+
+    const fn identifiers(&self) -> Identifiers {
+        if self.ids {
+            Identifiers::Numbers
+        } else {
+            Identifiers::Auto
+        }
+    }
+
+    /// Whether the DOT graph goes to stdout rather than to the output directory.
+    fn dry_run(&self) -> bool {
+        self.dry_run || self.output_dir == Path::new("-")
+    }
+
+    /// Fails on flags that cannot be used together, once the config file is applied.
+    fn check_conflicts(&self) -> Result<(), String> {
+        if self.json && self.dry_run() {
+            return Err(
+                "`--json` cannot be used with `--dry-run` or `--output-dir -`, which print the DOT \
+                 graph on stdout"
+                    .to_owned(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Reads the party of `input`, or stdin, for `subcommand`, in the format and with the ids of
+    /// the flags.
+    fn read(&self, input: Option<&Path>, subcommand: &str) -> Result<Session, String> {
+        read_input(
+            input,
+            self.input_format,
+            self.identifiers(),
+            Some(subcommand),
+        )
+    }
+}
+
+impl Cli {
+    /// Parses the command line over the defaults of the config file, and runs it.
+    pub fn main() -> ExitCode {
+        let matches = Self::command().get_matches();
+        let mut cli = Self::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+        cli.global.init_logging();
+        let config = Config::read(cli.global.config.as_deref());
+        let applied = config.and_then(|config| config.apply(&mut cli.global, &matches));
+        if let Err(message) = applied.and_then(|()| cli.global.check_conflicts()) {
+            eprintln!("error: {message}");
+            return ExitCode::from(2);
+        }
+        cli.run()
+    }
+
+    /// Runs the command, reporting failures on stderr.
+    fn run(self) -> ExitCode {
+        match self.execute() {
+            Ok(Status::Done) => ExitCode::SUCCESS,
+            Ok(Status::NoClique) => ExitCode::from(1),
+            Err(message) => {
+                eprintln!("error: {message}");
+                ExitCode::from(2)
+            }
+        }
+    }
+
+    fn execute(self) -> Result<Status, String> {
+        let ids = self.global.identifiers();
+        match self.command {
+            None => self
+                .input
+                .as_deref()
+                .map_or_else(
+                    || Ok(Session::new(Party::demo())),
+                    |input| read_input(Some(input), self.global.input_format, ids, None),
+                )
+                .and_then(|session| solve_and_render(&session, &self.global)),
+            Some(Command::Solve {
+                dir: Some(dir),
+                recursive,
+                ..
+            }) => batch::solve_dir(&dir, recursive, &self.global),
+            Some(Command::Solve {
+                inputs,
+                watch: true,
+                render,
+                debounce,
+                ..
+            }) => match &inputs[..] {
+                [input] => {
+                    watch::watch(input, render, Duration::from_millis(debounce), &self.global)
+                }
+                _ => Err("`--watch` takes a single input".to_owned()),
+            },
+            Some(Command::Solve {
+                inputs,
+                strict,
+                candidates,
+                stats,
+                ..
+            }) => {
+                let session = read_inputs(
+                    &inputs,
+                    self.global.input_format,
+                    ids,
+                    strict,
+                    self.global.quiet,
+                )?;
+                if stats {
+                    eprint!("{}", stats_table(&session.party().stats()));
+                }
+                solve_among(&session, candidates.as_deref(), &self.global)
+            }
+            Some(Command::Render { input }) => (self.global.read(input.as_deref(), "render"))
+                .and_then(|session| {
+                    // the answer is only drawn, so there is nothing to warn about
+                    let solved = clique(&session, self.global.algorithm, |_| {});
+                    let solved = solved.as_ref().map(Option::as_ref);
+                    let written = render(&session, solved, &self.global)?;
+                    if self.global.json {
+                        println!("{}", Value::from(json(&session, &written)));
+                    }
+                    Ok(Status::Done)
+                }),
+            Some(Command::Repl { input }) => input
+                .as_deref()
+                .map_or_else(
+                    || Ok(Party::new()),
+                    |input| {
+                        read_input(
+                            Some(input),
+                            self.global.input_format,
+                            Identifiers::Numbers,
+                            None,
+                        )
+                        .map(|session| session.party().clone())
+                    },
+                )
+                .and_then(|party| repl::repl(party, &self.global))
+                .map(|()| Status::Done),
+            Some(Command::Annotate { input }) => annotate::annotate(input.as_deref(), &self.global),
+            Some(Command::Generate(generation)) => {
+                generate(&generation, &self.global).map(|()| Status::Done)
+            }
+            Some(Command::Completions { shell }) => {
+                let mut command = Self::command();
+                clap_complete::generate(shell, &mut command, "celebrities", &mut io::stdout());
+                Ok(Status::Done)
+            }
+            Some(Command::Check { input, clique }) => (self.global.read(input.as_deref(), "check"))
+                .and_then(|session| check(&session, &clique, &self.global)),
+            Some(Command::Stats { input }) => {
+                let session = self.global.read(input.as_deref(), "stats")?;
+                eprint!("{}", stats_table(&session.party().stats()));
+                Ok(Status::Done)
+            }
+            Some(Command::Validate { input, warn_only }) => {
+                read_text(input.as_deref(), self.global.input_format, Some("validate"))
+                    .and_then(|input| input.check(ids))
+                    .and_then(|(session, problems)| validate(&session, &problems, warn_only))
+                    .map(|()| Status::Done)
+            }
+        }
+    }
+}
+
+/// Solves the party, and prints the clique unless `--json` asks for it later.
+fn solve(session: &Session, global: &Global) -> Result<Option<Party>, String> {
+    let css = clique(session, global.algorithm, |err| warn!("{err}"))?;
+    if !global.json {
+        print!("{}", report(session, css.as_ref()));
+    }
+    Ok(css)
+}
+
+/// What is done without a subcommand: solves the party, then renders it.
+fn solve_and_render(session: &Session, global: &Global) -> Result<Status, String> {
+    let css = solve(session, global)?;
+    let written = render(session, Ok(css.as_ref()), global)?;
+    if global.json {
+        println!(
+            "{}",
+            Value::from(json_solved(session, css.as_ref(), &written))
+        );
+    }
+    Ok(Status::of(css.as_ref()))
+}
+
+/// Like [`solve`], only considering the guests of `candidates` as celebrities when there are any,
+/// which the answer says.
+fn solve_among(
+    session: &Session,
+    candidates: Option<&[String]>,
+    global: &Global,
+) -> Result<Status, String> {
+    let Some(candidates) = candidates else {
+        let css = solve(session, global)?;
+        if global.json {
+            println!("{}", Value::from(json_solved(session, css.as_ref(), &[])));
+        }
+        return Ok(Status::of(css.as_ref()));
+    };
+    let mut ids = (candidates.iter())
+        .map(|candidate| guest_id(session, candidate.trim(), "candidate"))
+        .collect::<Result<Vec<_>, _>>()?;
+    ids.sort_unstable();
+    ids.dedup();
+
+    let start = Instant::now();
+    let css = (session.party())
+        .cclique_in_with_limit(&ids.iter().copied().collect(), DEFAULT_SEARCH_LIMIT)
+        .map_err(|err| err.to_string())?;
+    info!(
+        "solved among {} candidate(s) in {:?}",
+        ids.len(),
+        start.elapsed()
+    );
+    if global.json {
+        let mut object = json_solved(session, css.as_ref(), &[]);
+        object.insert("candidates".to_owned(), ids.into());
+        println!("{}", Value::from(object));
+    } else {
+        let names = ids
+            .iter()
+            .map(|&id| session.display_id(id))
+            .collect::<Vec<_>>();
+        println!("only {} may be celebrities:", names.join(", "));
+        print!("{}", report(session, css.as_ref()));
+    }
+    Ok(Status::of(css.as_ref()))
+}
+
+/// The id of the guest `guest`, by name if the party has names, or else an error calling them by
+/// their `role`.
+fn guest_id(session: &Session, guest: &str, role: &str) -> Result<usize, String> {
+    let (id, shown) = if let Some(names) = session.names() {
+        let id = names.get(guest).map(|symbol| symbol as usize);
+        (id, format!("`{guest}`"))
+    } else {
+        let id = (guest.parse()).map_err(|_| format!("`{guest}` is not an id"))?;
+        (Some(id), guest.to_owned())
+    };
+    id.filter(|&id| session.party().contains_id(id))
+        .ok_or_else(|| format!("{role} {shown} is not at the party"))
+}
+
+/// Checks whether the guests of `proposed` are the celebrity clique of the party, printing why
+/// not when they are not.
+fn check(session: &Session, proposed: &[String], global: &Global) -> Result<Status, String> {
+    let mut ids = (proposed.iter())
+        .map(|guest| guest_id(session, guest.trim(), "proposed celebrity"))
+        .collect::<Result<Vec<_>, _>>()?;
+    ids.sort_unstable();
+    ids.dedup();
+
+    let clique = (session.party().iter())
+        .filter(|person| ids.contains(&person.id))
+        .cloned()
+        .collect::<Party>();
+    let name = |id| session.display_id(id);
+    // The violation of the smallest ids, rather than the first one found, so that it is always
+    // the same one.
+    let violation = (clique
+        .cclique_violations(session.party(), None)
+        .into_iter()
+        .min())
+    .map(|(guest, celebrity, kind)| match kind {
+        ViolationKind::NotKnownByEveryone => CcliqueViolation::NotKnownByEveryone {
+            guest: name(guest),
+            celebrity: name(celebrity),
+        },
+        ViolationKind::KnowsOutsider => CcliqueViolation::KnowsOutsider {
+            celebrity: name(celebrity),
+            outsider: name(guest),
+        },
+    });
+    if global.json {
+        let mut object = json(session, &[]);
+        if session.names().is_some() {
+            let names = ids.iter().map(|&id| name(id));
+            object.insert("names".to_owned(), names.collect::<Vec<_>>().into());
+        }
+        object.insert("clique".to_owned(), ids.into());
+        object.insert("valid".to_owned(), violation.is_none().into());
+        let why = violation.as_ref().map(ToString::to_string);
+        object.insert("violation".to_owned(), why.into());
+        println!("{}", Value::from(object));
+    } else {
+        let names = ids
+            .iter()
+            .map(|&id| name(id))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &violation {
+            None => println!("{names}: the celebrity clique"),
+            Some(violation) => println!("{names}: not the celebrity clique, as {violation}"),
+        }
+    }
+    Ok(if violation.is_none() {
+        Status::Done
+    } else {
+        Status::NoClique
+    })
+}
+
+/// What `--json` prints about a party that was not solved: its size and the files written.
+fn json(session: &Session, written: &[PathBuf]) -> Map<String, Value> {
+    let mut object = Map::new();
+    object.insert("party_size".to_owned(), session.party().len().into());
+    let files = written.iter().map(|path| path.display().to_string());
+    object.insert("files".to_owned(), files.collect::<Vec<_>>().into());
+    object
+}
+
+/// What `--json` prints about a solved party: also whether it has a clique, and who is in it as
+/// a sorted list of ids, with their names alongside when the party has names.
+fn json_solved(session: &Session, css: Option<&Party>, written: &[PathBuf]) -> Map<String, Value> {
+    let mut object = json(session, written);
+    object.insert("found".to_owned(), css.is_some().into());
+    let mut ids = css.map_or_else(Vec::new, |css| css.ids().into_iter().collect());
+    ids.sort_unstable();
+    if session.names().is_some() {
+        let names = ids.iter().map(|&id| session.display_id(id));
+        object.insert("names".to_owned(), names.collect::<Vec<_>>().into());
+    }
+    object.insert("clique".to_owned(), ids.into());
+    object
+}
+
+/// The celebrity clique of the session's party, passing inconsistent data to `warn` rather than
+/// failing on it.
+fn clique(
+    session: &Session,
+    algorithm: Algorithm,
+    warn: impl FnOnce(&CliqueError),
+) -> Result<Option<Party>, String> {
+    let party = session.party();
+    let acquaintances = party.iter().map(|person| person.known_iter().count());
+    info!(
+        "{} guest(s), with {} acquaintance(s) between them",
+        party.len(),
+        acquaintances.sum::<usize>()
+    );
+    let chosen = algorithm.choose(party.len());
+    let start = Instant::now();
+    let result = if chosen == Algorithm::Exhaustive {
+        match session.try_solve() {
+            // references to people outside the party cannot affect the answer, but the limit can
+            Err(SessionError::Clique(err @ CliqueError::InconsistentData { .. })) => {
+                warn(&err);
+                (party.cclique_with_limit(DEFAULT_SEARCH_LIMIT)).map_err(|err| err.to_string())
+            }
+            result => result.map_err(|err| err.to_string()),
+        }
+    } else {
+        solve_with(party, chosen, warn)
+    };
+    info!(
+        "solved with {}{} in {:?}",
+        chosen.name(),
+        if algorithm == Algorithm::Auto {
+            " (auto)"
+        } else {
+            ""
+        },
+        start.elapsed()
+    );
+    result
+}
+
+/// Solves `party` with an algorithm other than the exhaustive search, which fails and warns on
+/// the same input as it does.
+fn solve_with(
+    party: &Party,
+    algorithm: Algorithm,
+    warn: impl FnOnce(&CliqueError),
+) -> Result<Option<Party>, String> {
+    if party.is_empty() {
+        return Err(CliqueError::<usize>::EmptyParty.to_string());
+    }
+    let mut missing_ids = (party.iter())
+        .flat_map(Person::known_iter)
+        .filter(|&id| !party.contains_id(id))
+        .collect::<Vec<_>>();
+    if !missing_ids.is_empty() {
+        missing_ids.sort_unstable();
+        missing_ids.dedup();
+        warn(&CliqueError::InconsistentData { missing_ids });
+    }
+    Ok(match algorithm {
+        Algorithm::Linear => party.cclique_fast().0,
+        Algorithm::Scc => party.cclique_scc(),
+        Algorithm::Elimination => party.cclique_elimination(),
+        Algorithm::Exhaustive | Algorithm::Auto => {
+            (party.cclique_with_limit(DEFAULT_SEARCH_LIMIT)).map_err(|err| err.to_string())?
+        }
+    })
+}
+
+/// What `solve` prints about the clique `css`.
+fn report(session: &Session, css: Option<&Party>) -> String {
+    css.map_or_else(
+        || "no celebrity clique\n".to_owned(),
+        |css| {
+            let people = css.sorted();
+            let people = people.iter().map(|person| describe(session, person));
+            format!("{:#?}\n", people.collect::<Vec<_>>())
+        },
+    )
+}
+
+/// The figures of `stats`, one per line, with their values aligned.
+fn stats_table(stats: &Stats) -> String {
+    let rows = [
+        ("people", stats.people.to_string()),
+        ("acquaintances", stats.acquaintances.to_string()),
+        ("out-degree min", stats.min_out_degree.to_string()),
+        ("out-degree mean", format!("{:.2}", stats.mean_out_degree())),
+        ("out-degree max", stats.max_out_degree.to_string()),
+        ("strangers", stats.strangers.to_string()),
+    ];
+    let width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or(0);
+    let mut table = String::new();
+    for (figure, value) in rows {
+        let _ = writeln!(table, "{figure:<16}{value:>width$}");
+    }
+    table
+}
+
+/// A guest and whom they know, by name if the party has names.
+fn describe(session: &Session, person: &Person) -> String {
+    if session.names().is_none() {
+        return person.to_string();
+    }
+    let mut known = person
+        .known_iter()
+        .map(|id| session.display_id(id))
+        .collect::<Vec<_>>();
+    known.sort_unstable();
+    format!(
+        "{} knows {{{}}}",
+        session.display_id(person.id),
+        known.join(", ")
+    )
+}
+
+/// Writes the graph, showing the answer of solving the party unless it could not be solved,
+/// returning the paths written, or prints it for `--dry-run`.
+fn render(
+    session: &Session,
+    solved: Result<Option<&Party>, &String>,
+    global: &Global,
+) -> Result<Vec<PathBuf>, String> {
+    let mut formats = Vec::new();
+    if !global.no_render {
+        for format in global.formats.iter().copied().map(Format::from) {
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        if formats.is_empty() {
+            formats.push(Format::Png);
+        }
+    }
+    let engine = Engine::from(global.engine);
+    let mut options = RenderOptions::new()
+        .output_dir(&global.output_dir)
+        .cluster(!global.no_cluster)
+        .merge_mutual(global.merge_mutual)
+        .legend(global.legend)
+        .engine(engine)
+        .formats(formats.iter().copied());
+    if let Some(binary) = &global.dot_binary {
+        options = options.dot_binary(binary);
+    }
+    if let Ok(css) = solved {
+        options = options.clique(css);
+    }
+    if global.dry_run() {
+        print!("{}", session.to_dot(&options));
+        return Ok(Vec::new());
+    }
+    let written = match session.render(&options) {
+        // the DOT file, which is the graph, is there anyway
+        Err(SessionError::GraphvizNotFound { binary }) if !global.require_render => {
+            let formats = formats
+                .iter()
+                .map(|format| format.extension().to_ascii_uppercase());
+            warn!(
+                "Graphviz '{}' not found; wrote {}, skipping {}",
+                binary.display(),
+                options.dot_path().display(),
+                formats.collect::<Vec<_>>().join(", ")
+            );
+            return Ok(vec![options.dot_path()]);
+        }
+        result => result.map_err(|err| err.to_string())?,
+    };
+    let (dot, images) = written
+        .split_first()
+        .expect("the DOT file is always written");
+    if !global.quiet {
+        eprintln!("wrote {}", dot.display());
+        for path in images {
+            eprintln!("wrote {} with {}", path.display(), engine.name());
+        }
+    }
+    Ok(written)
+}
+
+/// Writes a random party to `output` or stdout, and says what it is on stderr.
+fn generate(generation: &Generation, global: &Global) -> Result<(), String> {
+    let &Generation {
+        n,
+        p,
+        seed,
+        plant_clique,
+        ..
+    } = generation;
+    let output = (generation.output.as_deref()).filter(|path| *path != Path::new("-"));
+    let party = match plant_clique {
+        Some(k) if !(1..=n).contains(&k) => {
+            return Err(format!("cannot plant a clique of {k} among {n} guest(s)"));
+        }
+        Some(k) => planted_party(n, k, p, seed),
+        None => random_party(n, p, seed),
+    };
+    let party = party.into_iter().collect::<Party>();
+    let format = (generation.output_format)
+        .or_else(|| output.and_then(InputFormat::of))
+        .unwrap_or(InputFormat::Text);
+    let text = format.write(&party)?;
+    match output {
+        Some(path) => fs::write(path, text).map_err(|err| format!("{}: {err}", path.display()))?,
+        None => io::stdout()
+            .write_all(text.as_bytes())
+            .map_err(|err| err.to_string())?,
+    }
+    if !global.quiet {
+        let acquaintances = party
+            .iter()
+            .map(|person| person.known_iter().count())
+            .sum::<usize>();
+        let planted =
+            plant_clique.map_or_else(String::new, |k| format!(", a planted clique of {k}"));
+        let destination =
+            output.map_or_else(|| "stdout".to_owned(), |path| path.display().to_string());
+        eprintln!(
+            "generated {n} guest(s) with {acquaintances} acquaintance(s){planted} from seed {seed}, \
+             as {format} to {destination}"
+        );
+    }
+    Ok(())
+}
+
+/// Prints one line per problem, and fails if there is any, unless `warn_only`.
+fn validate(session: &Session, problems: &[Problem], warn_only: bool) -> Result<(), String> {
+    for problem in problems {
+        println!("{}", describe_problem(session, problem));
+    }
+    match problems.len() {
+        0 => info!("{} people, no problems", session.party().len()),
+        n if warn_only => warn!("found {n} problem(s)"),
+        n => return Err(format!("found {n} problem(s)")),
+    }
+    Ok(())
+}
+
+/// A line for `problem` that starts with its kind, for `grep`.
+fn describe_problem(session: &Session, problem: &Problem) -> String {
+    let id = |id: usize| session.display_id(id);
+    match problem {
+        Problem::Empty => "empty: nobody is at the party".to_owned(),
+        Problem::Stranger {
+            id: stranger,
+            known_by,
+        } => {
+            let known_by = known_by
+                .iter()
+                .map(|&knower| id(knower))
+                .collect::<Vec<_>>();
+            let verb = if known_by.len() == 1 { "knows" } else { "know" };
+            format!(
+                "stranger: {} is not at the party, but {} {verb} them",
+                id(*stranger),
+                known_by.join(", ")
+            )
+        }
+        Problem::Isolated { id: guest } => {
+            format!(
+                "isolated: {} knows nobody at the party, and nobody knows them",
+                id(*guest)
+            )
+        }
+        Problem::SelfReference { line, id: guest } => {
+            format!(
+                "self-reference: line {line}: {} knows themselves",
+                id(*guest)
+            )
+        }
+        Problem::Duplicate {
+            line,
+            first,
+            id: guest,
+        } => format!(
+            "duplicate: line {line}: {} was already described on line {first}",
+            id(*guest)
+        ),
+    }
+}
@@ -0,0 +1,41 @@
+use super::{
+    Global, Status, clique,
+    input::{InputFormat, read_text},
+};
+use celebrities::{Party, session::Session};
+use log::warn;
+use std::{collections::HashMap, path::Path};
+
+/// The attributes added to the nodes of the celebrity clique.
+const HIGHLIGHT: &str = "style = filled, fillcolor = gold, penwidth = 3";
+
+/// Reads a DOT digraph from `input`, or stdin, and prints it with the nodes of its celebrity
+/// clique highlighted.
+pub fn annotate(input: Option<&Path>, global: &Global) -> Result<Status, String> {
+    let input = read_text(input, Some(InputFormat::Dot), Some("annotate"))?;
+    let (party, nodes) = Party::parse_dot_nodes(input.text(), &mut global.identifiers())
+        .map_err(|err| format!("{}: {err}", input.name()))?;
+    let css = clique(&Session::new(party), global.algorithm, |err| warn!("{err}"))?;
+    print!("{}", highlight(input.text(), css.as_ref(), &nodes));
+    Ok(Status::of(css.as_ref()))
+}
+
+/// `dot` as it is, with a statement for each member of `css` at the end of the graph, which adds
+/// to the attributes of their node, or else a comment at the top saying there is no clique.
+fn highlight(dot: &str, css: Option<&Party>, nodes: &HashMap<usize, String>) -> String {
+    let (Some(css), Some(end)) = (css, dot.rfind('}')) else {
+        return format!("// no celebrity clique\n{dot}");
+    };
+    let mut ids = css.ids().into_iter().collect::<Vec<_>>();
+    ids.sort_unstable();
+    let statements = ids.iter().filter_map(|id| nodes.get(id)).map(|node| {
+        let node = node.replace('"', "\\\"");
+        format!("    \"{node}\" [ {HIGHLIGHT} ]\n")
+    });
+    let (graph, rest) = dot.split_at(end);
+    format!(
+        "{}\n    // the celebrity clique\n{}{rest}",
+        graph.trim_end(),
+        statements.collect::<String>()
+    )
+}
@@ -0,0 +1,146 @@
+use super::{
+    Global, Status, clique,
+    input::{InputFormat, read_party},
+    json_solved, report,
+};
+use log::{info, warn};
+use serde_json::{Map, Value};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// How solving one file of a batch went.
+enum Outcome {
+    /// The clique, by name or id, if there is one, and what `--json` says about the file.
+    Solved(Option<Vec<String>>, Map<String, Value>),
+    Error(String),
+}
+
+/// Solves every party file in `dir`, and in its subdirectories if `recursive`, writing what
+/// `solve` would print for each into the output directory, and prints a summary.
+///
+/// A file that cannot be read or solved is reported in the summary without stopping the others,
+/// and makes the batch fail at the end; otherwise, a file without a clique is the batch's status.
+pub fn solve_dir(dir: &Path, recursive: bool, global: &Global) -> Result<Status, String> {
+    let mut files = Vec::new();
+    collect(dir, recursive, &mut files).map_err(|err| format!("{}: {err}", dir.display()))?;
+    files.sort_unstable();
+
+    let mut outcomes = Vec::with_capacity(files.len());
+    for file in &files {
+        info!("solving {}", file.display());
+        let relative = file.strip_prefix(dir).unwrap_or(file);
+        let outcome = solve_file(file, relative, global).unwrap_or_else(Outcome::Error);
+        outcomes.push((relative.display().to_string(), outcome));
+    }
+    if global.json {
+        println!("{}", summary_json(&outcomes));
+    } else {
+        print!("{}", summary(&outcomes));
+    }
+
+    let errors = (outcomes.iter())
+        .filter(|(_, outcome)| matches!(outcome, Outcome::Error(_)))
+        .count();
+    if errors > 0 {
+        return Err(format!("{errors} of {} file(s) failed", outcomes.len()));
+    }
+    let none = (outcomes.iter()).any(|(_, outcome)| matches!(outcome, Outcome::Solved(None, _)));
+    Ok(if none { Status::NoClique } else { Status::Done })
+}
+
+/// The party files in `dir`, in no particular order.
+fn collect(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if recursive {
+                collect(&path, recursive, files)?;
+            }
+        } else if InputFormat::recognizes(&path) {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Solves `file`, writing the result to `<output dir>/<relative>.txt`.
+fn solve_file(file: &Path, relative: &Path, global: &Global) -> Result<Outcome, String> {
+    let ids = global.identifiers();
+    let session = read_party(file, global.input_format, ids)?;
+    let css = clique(&session, global.algorithm, |err| {
+        warn!("{}: {err}", file.display());
+    })
+    .map_err(|err| format!("{}: {err}", file.display()))?;
+
+    let mut result = global.output_dir.join(relative).into_os_string();
+    result.push(".txt");
+    let result = PathBuf::from(result);
+    if let Some(parent) = result.parent() {
+        fs::create_dir_all(parent).map_err(|err| format!("{}: {err}", parent.display()))?;
+    }
+    fs::write(&result, report(&session, css.as_ref()))
+        .map_err(|err| format!("{}: {err}", result.display()))?;
+    info!("wrote {}", result.display());
+
+    let answer = json_solved(&session, css.as_ref(), &[result]);
+    let people = css.map(|css| {
+        let mut ids = css.ids().into_iter().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.into_iter().map(|id| session.display_id(id)).collect()
+    });
+    Ok(Outcome::Solved(people, answer))
+}
+
+/// The summary as a JSON object, with a `files` list of what `--json` says about each file, or
+/// its `error`, along with its name.
+fn summary_json(outcomes: &[(String, Outcome)]) -> Value {
+    let files = outcomes.iter().map(|(file, outcome)| {
+        let mut object = Map::new();
+        object.insert("file".to_owned(), file.as_str().into());
+        match outcome {
+            Outcome::Solved(_, answer) => object.extend(answer.clone()),
+            Outcome::Error(err) => {
+                object.insert("error".to_owned(), err.as_str().into());
+            }
+        }
+        Value::from(object)
+    });
+    let mut object = Map::new();
+    object.insert("files".to_owned(), files.collect::<Vec<_>>().into());
+    object.into()
+}
+
+/// A table of each file and its clique, `none` or error, followed by the totals.
+fn summary(outcomes: &[(String, Outcome)]) -> String {
+    let width = (outcomes.iter())
+        .map(|(file, _)| file.chars().count())
+        .chain(["file".len()])
+        .max()
+        .unwrap_or_default();
+    let mut table = vec![format!("{:width$}  result", "file")];
+    let (mut cliques, mut nones, mut errors) = (0, 0, 0);
+    for (file, outcome) in outcomes {
+        let result = match outcome {
+            Outcome::Solved(Some(people), _) => {
+                cliques += 1;
+                format!("{{{}}}", people.join(", "))
+            }
+            Outcome::Solved(None, _) => {
+                nones += 1;
+                "none".to_owned()
+            }
+            Outcome::Error(err) => {
+                errors += 1;
+                format!("error: {err}")
+            }
+        };
+        table.push(format!("{file:width$}  {result}"));
+    }
+    table.push(format!(
+        "{} file(s): {cliques} clique(s), {nones} without, {errors} error(s)\n",
+        outcomes.len()
+    ));
+    table.join("\n")
+}
@@ -0,0 +1,95 @@
+use super::{Algorithm, Global, ImageFormat, LayoutEngine, input::InputFormat};
+use clap::{ArgMatches, parser::ValueSource};
+use log::{info, warn};
+use serde::Deserialize;
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// The file in the working directory read for defaults when `--config` does not name another.
+pub const CONFIG_FILE: &str = "celebrities.toml";
+
+/// Defaults for the global flags, under their names: `output-dir = "out"`, `format = ["svg"]`,
+/// `engine = "neato"`, and so on.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Config {
+    output_dir: Option<PathBuf>,
+    format: Option<Vec<ImageFormat>>,
+    no_render: Option<bool>,
+    require_render: Option<bool>,
+    engine: Option<LayoutEngine>,
+    algorithm: Option<Algorithm>,
+    dot_binary: Option<PathBuf>,
+    input_format: Option<InputFormat>,
+    ids: Option<bool>,
+    /// Keys of no flag, which are warned about.
+    #[serde(flatten)]
+    unknown: BTreeMap<String, toml::Value>,
+}
+
+impl Config {
+    /// Reads `path`, or else [`CONFIG_FILE`] when there is one.
+    pub fn read(path: Option<&Path>) -> Result<Self, String> {
+        let path = path.unwrap_or_else(|| Path::new(CONFIG_FILE));
+        let text = match fs::read_to_string(path) {
+            Err(err) if err.kind() == io::ErrorKind::NotFound && path == Path::new(CONFIG_FILE) => {
+                return Ok(Self::default());
+            }
+            text => text.map_err(|err| format!("{}: {err}", path.display()))?,
+        };
+        info!("reading defaults from {}", path.display());
+        let config =
+            toml::from_str::<Self>(&text).map_err(|err| format!("{}: {err}", path.display()))?;
+        for key in config.unknown.keys() {
+            warn!("{}: unknown key `{key}`, ignored", path.display());
+        }
+        Ok(config)
+    }
+
+    /// Sets the flags of `global` that were not given on the command line `matches` to the values
+    /// of this file.
+    pub fn apply(self, global: &mut Global, matches: &ArgMatches) -> Result<(), String> {
+        let default = |id: &str| !from_command_line(matches, id);
+        let rendering = default("no_render") && default("require_render");
+        if let Some(output_dir) = self.output_dir.filter(|_| default("output_dir")) {
+            global.output_dir = output_dir;
+        }
+        if let Some(formats) = self.format.filter(|_| default("formats")) {
+            global.formats = formats;
+        }
+        if let Some(no_render) = self.no_render.filter(|_| rendering) {
+            global.no_render = no_render;
+        }
+        if let Some(require_render) = self.require_render.filter(|_| rendering) {
+            global.require_render = require_render;
+        }
+        if let Some(engine) = self.engine.filter(|_| default("engine")) {
+            global.engine = engine;
+        }
+        if let Some(algorithm) = self.algorithm.filter(|_| default("algorithm")) {
+            global.algorithm = algorithm;
+        }
+        if let Some(dot_binary) = self.dot_binary.filter(|_| default("dot_binary")) {
+            global.dot_binary = Some(dot_binary);
+        }
+        if let Some(format) = self.input_format.filter(|_| default("input_format")) {
+            global.input_format = Some(format);
+        }
+        if let Some(ids) = self.ids.filter(|_| default("ids")) {
+            global.ids = ids;
+        }
+        if global.no_render && global.require_render {
+            return Err("`no-render` and `require-render` cannot both be set".to_owned());
+        }
+        Ok(())
+    }
+}
+
+/// Whether the flag `id` was given on the command line, before or after the subcommand.
+fn from_command_line(matches: &ArgMatches, id: &str) -> bool {
+    matches.value_source(id) == Some(ValueSource::CommandLine)
+        || (matches.subcommand()).is_some_and(|(_, matches)| from_command_line(matches, id))
+}
@@ -0,0 +1,583 @@
+use super::Cli;
+use celebrities::{
+    Party, Person,
+    clique::{
+        Label,
+        party::{Identifiers, Problem, looks_like_edges},
+    },
+    session::Session,
+};
+use clap::{CommandFactory, ValueEnum, error::ErrorKind};
+use flate2::read::MultiGzDecoder;
+use log::info;
+use petgraph::{
+    dot::{Config, Dot},
+    graph::DiGraph,
+};
+use serde::{
+    Deserialize, Deserializer,
+    de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor},
+};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    fs,
+    io::{self, IsTerminal, Read},
+    path::{Path, PathBuf},
+};
+
+/// How an input file describes the party.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputFormat {
+    /// One `id: known, ...` line per person.
+    Text,
+    /// `{"people": [{"id": 1, "knows": [2, 3]}, ...]}`.
+    Json,
+    /// `[people]` with a `1 = [2, 3]` line per person, or `[[people]]` tables like JSON's people.
+    Toml,
+    /// JSON's `people`, as a YAML list.
+    Yaml,
+    /// A digraph whose nodes are labelled with ids, as in `graph.dot`.
+    Dot,
+    /// Rows of `0`s and `1`s, a `1` in row `i` and column `j` when `i` knows `j`.
+    Matrix,
+    /// One `a b` line per edge, when `a` knows `b`.
+    Edges,
+}
+
+impl Display for InputFormat {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let value = self.to_possible_value().expect("no format is skipped");
+        f.write_str(value.get_name())
+    }
+}
+
+impl InputFormat {
+    /// The format of a file named `path`, going by its extension, or by the one before `.gz`.
+    pub fn of(path: &Path) -> Option<Self> {
+        let path = uncompressed(path);
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => Some(Self::Json),
+            Some("toml") => Some(Self::Toml),
+            Some("yaml" | "yml") => Some(Self::Yaml),
+            Some("dot" | "gv") => Some(Self::Dot),
+            _ => None,
+        }
+    }
+
+    /// Whether a file named `path` looks like a party: one with an extension of a format, or
+    /// `.txt`, gzipped or not.
+    pub fn recognizes(path: &Path) -> bool {
+        Self::of(path).is_some()
+            || uncompressed(path)
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("txt"))
+    }
+
+    /// The format of `text` that says nothing else about it: edges if every line is two ids, and
+    /// text otherwise.
+    fn sniff(text: &str) -> Self {
+        if looks_like_edges(text) {
+            Self::Edges
+        } else {
+            Self::Text
+        }
+    }
+
+    fn parse(self, text: &str, ids: &mut Identifiers) -> Result<Party, String> {
+        match self {
+            Self::Text => Party::parse_text(text, ids).map_err(|err| format!("{err}")),
+            Self::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(text);
+                ids.deserialize(&mut deserializer)
+                    .and_then(|party| deserializer.end().map(|()| party))
+                    .map_err(|err| format!("{err}"))
+            }
+            Self::Toml => TomlParty(ids)
+                .deserialize(toml::Deserializer::parse(text).map_err(|err| format!("{err}"))?)
+                .map_err(|err| format!("{err}")),
+            Self::Yaml => ids
+                .deserialize(serde_yaml_ng::Deserializer::from_str(text))
+                .map_err(|err| format!("{err}")),
+            Self::Dot => Party::parse_dot(text, ids).map_err(|err| format!("{err}")),
+            Self::Matrix => Party::from_matrix(text).map_err(|err| format!("{err}")),
+            Self::Edges => Party::parse_edges(text, ids).map_err(|err| format!("{err}")),
+        }
+    }
+
+    /// `party` in this format, to be read back as it.
+    ///
+    /// A matrix numbers the people from 1 in increasing id order, and edges leave out the people
+    /// who know nobody and whom nobody knows.
+    pub fn write(self, party: &Party) -> Result<String, String> {
+        let people = party.sorted();
+        let known = |person: &Person| {
+            let mut known = person.known_iter().collect::<Vec<_>>();
+            known.sort_unstable();
+            known
+        };
+        let lines = |line: &dyn Fn(&Person) -> Vec<String>| {
+            people
+                .iter()
+                .flat_map(line)
+                .map(|line| line + "\n")
+                .collect()
+        };
+        match self {
+            Self::Text => Ok(lines(&|person| {
+                let known = known(person)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>();
+                vec![format!("{}: {}", person.id, known.join(", "))]
+            })),
+            Self::Json => serde_json::to_string_pretty(party)
+                .map(|json| json + "\n")
+                .map_err(|err| format!("{err}")),
+            Self::Toml => toml::to_string(party).map_err(|err| format!("{err}")),
+            Self::Yaml => serde_yaml_ng::to_string(party).map_err(|err| format!("{err}")),
+            Self::Dot => {
+                // Unlike `clique2digraph`, in id order, for the same seed to write the same file.
+                let mut graph = DiGraph::new();
+                let nodes = (people.iter())
+                    .map(|person| (person.id, graph.add_node(Label(person.label()))))
+                    .collect::<HashMap<_, _>>();
+                for person in &people {
+                    for known in known(person) {
+                        if let Some(&node) = nodes.get(&known) {
+                            graph.add_edge(nodes[&person.id], node, ());
+                        }
+                    }
+                }
+                Ok(format!(
+                    "{:?}",
+                    Dot::with_config(&graph, &[Config::EdgeNoLabel])
+                ))
+            }
+            Self::Matrix => Ok(lines(&|person| {
+                let known = known(person);
+                let row = people.iter().map(|other| {
+                    if other.id != person.id && known.binary_search(&other.id).is_ok() {
+                        "1"
+                    } else {
+                        "0"
+                    }
+                });
+                vec![row.collect::<Vec<_>>().join(" ")]
+            })),
+            Self::Edges => Ok(lines(&|person| {
+                (known(person).iter())
+                    .map(|known| format!("{} {known}", person.id))
+                    .collect()
+            })),
+        }
+    }
+}
+
+/// Reads a TOML party, with errors that point at the key or table they are about.
+struct TomlParty<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for TomlParty<'_> {
+    type Value = Party;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Party, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TomlParty<'_> {
+    type Value = Party;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a `people` table or array of tables")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Party, A::Error> {
+        let mut party = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "people" {
+                party = Some(map.next_value_seed(TomlPeople(&mut *self.0))?);
+            } else {
+                map.next_value::<de::IgnoredAny>()?;
+            }
+        }
+        party.ok_or_else(|| de::Error::missing_field("people"))
+    }
+}
+
+struct TomlPeople<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for TomlPeople<'_> {
+    type Value = Party;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Party, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TomlPeople<'_> {
+    type Value = Party;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a table of ids to acquaintances, or an array of tables with an `id` each")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Party, A::Error> {
+        let mut party = Party::new();
+        while let Some(id) = map.next_key_seed(Id::new(self.0, &party))? {
+            let known = map.next_value_seed(Known(&mut *self.0))?;
+            party.merge(self.0.person(id, known));
+        }
+        Ok(party)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Party, A::Error> {
+        let mut party = Party::new();
+        while let Some(person) = seq.next_element_seed(NewPerson(&mut *self.0, &party))? {
+            party.merge(person);
+        }
+        Ok(party)
+    }
+}
+
+/// Reads an id from a key, an `id` or an acquaintance, checking that it is somebody not in
+/// `newcomer_to` yet if there is one.
+struct Id<'a> {
+    ids: &'a mut Identifiers,
+    newcomer_to: Option<&'a Party>,
+}
+
+impl<'a> Id<'a> {
+    const fn new(ids: &'a mut Identifiers, party: &'a Party) -> Self {
+        Self {
+            ids,
+            newcomer_to: Some(party),
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Id<'_> {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl Visitor<'_> for Id<'_> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a non-negative integer id, or a name")
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<usize, E> {
+        self.visit_str(&v.to_string())
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+        let id = (self.ids.resolve(v)).ok_or_else(|| E::custom(format!("`{v}` is not an id")))?;
+        match self.newcomer_to {
+            Some(party) if party.contains_id(id) => Err(E::custom(format!(
+                "person {} is described more than once",
+                self.ids.describe(id)
+            ))),
+            _ => Ok(id),
+        }
+    }
+}
+
+/// Reads a list of acquaintances.
+struct Known<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for Known<'_> {
+    type Value = Vec<usize>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<usize>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Known<'_> {
+    type Value = Vec<usize>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an array of ids")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<usize>, A::Error> {
+        let mut known = Vec::new();
+        while let Some(id) = seq.next_element_seed(Id {
+            ids: &mut *self.0,
+            newcomer_to: None,
+        })? {
+            known.push(id);
+        }
+        Ok(known)
+    }
+}
+
+/// Reads somebody not in the party yet from a `[[people]]` table, so that a second description
+/// is reported at its `id`.
+struct NewPerson<'a>(&'a mut Identifiers, &'a Party);
+
+impl<'de> DeserializeSeed<'de> for NewPerson<'_> {
+    type Value = Person;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Person, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NewPerson<'_> {
+    type Value = Person;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a table with an `id`, and optionally a `name` and whom they `knows`")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Person, A::Error> {
+        let (mut id, mut name, mut knows) = (None, None, Vec::new());
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "id" => id = Some(map.next_value_seed(Id::new(&mut *self.0, self.1))?),
+                "name" => name = Some(map.next_value::<String>()?),
+                "knows" => knows = map.next_value_seed(Known(&mut *self.0))?,
+                _ => drop(map.next_value::<de::IgnoredAny>()?),
+            }
+        }
+        let id = id.ok_or_else(|| de::Error::missing_field("id"))?;
+        let person = self.0.person(id, knows);
+        Ok(Person {
+            name: name.or(person.name),
+            ..person
+        })
+    }
+}
+
+/// Reads a party from `path`, in `format` or the one its extension suggests, describing any
+/// problem with the file name and where in the file it is.
+///
+/// The session knows the guests' names when `ids` lets them be identified by name.
+pub fn read_party(
+    path: &Path,
+    format: Option<InputFormat>,
+    ids: Identifiers,
+) -> Result<Session, String> {
+    read_file(path, format)?.session(ids)
+}
+
+/// The text of a party, before it is parsed.
+pub struct Input {
+    /// The file it was read from, or `<stdin>`, for error messages.
+    name: String,
+    text: String,
+    format: InputFormat,
+}
+
+impl Input {
+    pub fn session(self, mut ids: Identifiers) -> Result<Session, String> {
+        let party = self.parse(&mut ids)?;
+        Ok(with_names(Session::new(party), ids))
+    }
+
+    /// The file it was read from, or `<stdin>`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn parse(&self, ids: &mut Identifiers) -> Result<Party, String> {
+        (self.format.parse(&self.text, ids)).map_err(|err| format!("{}: {err}", self.name))
+    }
+
+    /// The session, and the problems with its data. The text format is checked as it is read,
+    /// for people who know themselves or are described twice, which the other formats drop or
+    /// fail on.
+    pub fn check(self, mut ids: Identifiers) -> Result<(Session, Vec<Problem>), String> {
+        if self.format != InputFormat::Text {
+            let session = self.session(ids)?;
+            let problems = session.party().problems();
+            return Ok((session, problems));
+        }
+        let (party, problems) = Party::check_text(&self.text, &mut ids)
+            .map_err(|err| format!("{}: {err}", self.name))?;
+        Ok((with_names(Session::new(party), ids), problems))
+    }
+}
+
+fn with_names(session: Session, ids: Identifiers) -> Session {
+    match ids.into_names() {
+        Some(names) => session.with_names(names),
+        None => session,
+    }
+}
+
+/// The first bytes of a gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+fn is_gzip(path: &Path) -> bool {
+    (path.extension()).is_some_and(|extension| extension.eq_ignore_ascii_case("gz"))
+}
+
+/// `path` without its `.gz`, if it has one.
+fn uncompressed(path: &Path) -> Cow<'_, Path> {
+    if is_gzip(path) {
+        Cow::Owned(path.with_extension(""))
+    } else {
+        Cow::Borrowed(path)
+    }
+}
+
+/// `bytes` as text, decompressed first if `gzipped` or if they start like gzip.
+fn decode(bytes: Vec<u8>, gzipped: bool) -> io::Result<String> {
+    let bytes = if gzipped || bytes.starts_with(&GZIP_MAGIC) {
+        let mut text = Vec::new();
+        MultiGzDecoder::new(&bytes[..]).read_to_end(&mut text)?;
+        text
+    } else {
+        bytes
+    };
+    String::from_utf8(bytes).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            "stream did not contain valid UTF-8",
+        )
+    })
+}
+
+/// Reads the party in `path`, decompressing it if it is gzipped, in `format` or else the one of
+/// its extension or contents.
+fn read_file(path: &Path, format: Option<InputFormat>) -> Result<Input, String> {
+    let text = fs::read(path)
+        .and_then(|bytes| decode(bytes, is_gzip(path)))
+        .map_err(|err| format!("{}: {err}", path.display()))?;
+    let (format, why) = format.map_or_else(
+        || {
+            InputFormat::of(path).map_or_else(
+                || (InputFormat::sniff(&text), "going by its contents"),
+                |format| (format, "going by its extension"),
+            )
+        },
+        |format| (format, "as asked"),
+    );
+    info!("reading {} as {format}, {why}", path.display());
+    Ok(Input {
+        name: path.display().to_string(),
+        text,
+        format,
+    })
+}
+
+/// Reads the party from `input`, or from stdin, in `format` or else as text or edges, when it is
+/// missing or `-`.
+///
+/// Rather than wait for somebody to type a party in, a terminal on stdin is a usage error of
+/// `subcommand`, which exits.
+pub fn read_input(
+    input: Option<&Path>,
+    format: Option<InputFormat>,
+    ids: Identifiers,
+    subcommand: Option<&str>,
+) -> Result<Session, String> {
+    read_text(input, format, subcommand)?.session(ids)
+}
+
+/// Like [`read_input`] for several inputs, each in `format` or else its own, merged into one
+/// party. The acquaintances of people in several inputs are unioned, unless `strict` makes it an
+/// error for them to differ. Each input's people and acquaintances are counted on stderr, unless
+/// `quiet`.
+pub fn read_inputs(
+    inputs: &[PathBuf],
+    format: Option<InputFormat>,
+    mut ids: Identifiers,
+    strict: bool,
+    quiet: bool,
+) -> Result<Session, String> {
+    let [_, _, ..] = inputs else {
+        return read_input(
+            inputs.first().map(PathBuf::as_path),
+            format,
+            ids,
+            Some("solve"),
+        );
+    };
+    let mut party = Party::new();
+    let mut described_in = HashMap::new();
+    for path in inputs {
+        let input = read_text(Some(path), format, Some("solve"))?;
+        let people = input.parse(&mut ids)?.sorted();
+        if !quiet {
+            let acquaintances = people.iter().map(|person| person.known_people.len());
+            eprintln!(
+                "read {} people and {} acquaintances from {}",
+                people.len(),
+                acquaintances.sum::<usize>(),
+                input.name
+            );
+        }
+        for person in people {
+            let first = described_in
+                .entry(person.id)
+                .or_insert_with(|| input.name.clone());
+            if strict
+                && let Some(earlier) = party.get(person.id)
+                && earlier.known_people != person.known_people
+            {
+                return Err(format!(
+                    "{}: person {} is described differently in {first}",
+                    input.name,
+                    ids.describe(person.id)
+                ));
+            }
+            party.merge(person);
+        }
+    }
+    Ok(with_names(Session::new(party), ids))
+}
+
+/// Like [`read_input`], without parsing the party yet.
+pub fn read_text(
+    input: Option<&Path>,
+    format: Option<InputFormat>,
+    subcommand: Option<&str>,
+) -> Result<Input, String> {
+    if let Some(path) = input.filter(|path| *path != Path::new("-")) {
+        return read_file(path, format);
+    }
+    let stdin = io::stdin();
+    if stdin.is_terminal() {
+        let mut command = Cli::command();
+        command.build();
+        let mut usage = subcommand
+            .and_then(|name| command.find_subcommand(name))
+            .cloned()
+            .unwrap_or(command);
+        usage
+            .error(
+                ErrorKind::MissingRequiredArgument,
+                "no input: give a file, or pipe a party into stdin",
+            )
+            .exit();
+    }
+    let mut bytes = Vec::new();
+    let text = (stdin.lock().read_to_end(&mut bytes))
+        .and_then(|_| decode(bytes, false))
+        .map_err(|err| format!("<stdin>: {err}"))?;
+    let (format, why) = format.map_or_else(
+        || (InputFormat::sniff(&text), "going by its contents"),
+        |format| (format, "as asked"),
+    );
+    info!("reading <stdin> as {format}, {why}");
+    Ok(Input {
+        name: "<stdin>".to_owned(),
+        text,
+        format,
+    })
+}
@@ -0,0 +1,261 @@
+use super::{Algorithm, Global, clique, render, report};
+use celebrities::{Party, Person, session::Session};
+use log::warn;
+use std::{
+    fmt::Write as _,
+    io::{self, IsTerminal, Write},
+    str::FromStr,
+};
+
+const USAGE: &str = "\
+commands:
+  add <id> [knows <id>...]  add a guest, or acquaintances of one
+  forget <who> <whom>       <who> no longer knows <whom>
+  remove <id>               remove a guest, and everybody's acquaintance with them
+  solve                     print the celebrity clique
+  show                      print the guests and whom they know
+  render                    write the graph and render it
+  help                      print this
+  quit                      leave
+";
+
+/// A line typed at the prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Add {
+        id: usize,
+        known: Vec<usize>,
+    },
+    Forget {
+        who: usize,
+        whom: usize,
+    },
+    Remove(usize),
+    Solve,
+    Show,
+    Render,
+    Help,
+    Quit,
+    /// An empty line.
+    Nothing,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Ok(Self::Nothing);
+        };
+        let id = |word: &str| (word.parse::<usize>()).map_err(|_| format!("`{word}` is not an id"));
+        let parsed = match command {
+            "add" => match (words.next(), words.next()) {
+                (Some(guest), None) => Some(Self::Add {
+                    id: id(guest)?,
+                    known: Vec::new(),
+                }),
+                (Some(guest), Some("knows")) => Some(Self::Add {
+                    id: id(guest)?,
+                    known: words.map(id).collect::<Result<_, _>>()?,
+                }),
+                _ => None,
+            },
+            "forget" => match words.map(id).collect::<Result<Vec<_>, _>>()?[..] {
+                [who, whom] => Some(Self::Forget { who, whom }),
+                _ => None,
+            },
+            "remove" => match words.map(id).collect::<Result<Vec<_>, _>>()?[..] {
+                [id] => Some(Self::Remove(id)),
+                _ => None,
+            },
+            "solve" | "show" | "render" | "help" | "quit" | "exit" if words.next().is_some() => {
+                None
+            }
+            "solve" => Some(Self::Solve),
+            "show" => Some(Self::Show),
+            "render" => Some(Self::Render),
+            "help" => Some(Self::Help),
+            "quit" | "exit" => Some(Self::Quit),
+            _ => return Err(format!("unknown command `{command}`")),
+        };
+        parsed.ok_or_else(|| format!("bad use of `{command}`"))
+    }
+}
+
+/// What the loop does after a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reply {
+    Print(String),
+    Render,
+    Quit,
+}
+
+/// The party being edited at the prompt.
+#[derive(Debug, Default)]
+struct Repl {
+    party: Party,
+    algorithm: Algorithm,
+}
+
+impl Repl {
+    fn execute(&mut self, command: Command) -> Reply {
+        let text = match command {
+            Command::Add { id, known } => {
+                self.party.merge(Person::new(id, known));
+                String::new()
+            }
+            Command::Forget { who, whom } => {
+                if self.party.forget(who, whom) {
+                    String::new()
+                } else {
+                    format!("{who} does not know {whom}\n")
+                }
+            }
+            Command::Remove(id) => match self.party.remove_person_and_references(id) {
+                Some(_) => String::new(),
+                None => format!("{id} is not at the party\n"),
+            },
+            Command::Solve => {
+                let session = Session::new(self.party.clone());
+                match clique(&session, self.algorithm, |err| warn!("{err}")) {
+                    Ok(css) => report(&session, css.as_ref()),
+                    Err(err) => format!("error: {err}\n"),
+                }
+            }
+            Command::Show => self.show(),
+            Command::Render => return Reply::Render,
+            Command::Help => USAGE.to_owned(),
+            Command::Quit => return Reply::Quit,
+            Command::Nothing => String::new(),
+        };
+        Reply::Print(text)
+    }
+
+    /// One `id: known, ...` line per guest, sorted, as in the text format.
+    fn show(&self) -> String {
+        if self.party.is_empty() {
+            return "nobody is at the party\n".to_owned();
+        }
+        let mut text = String::new();
+        for person in self.party.sorted() {
+            let mut known = person.known_iter().collect::<Vec<_>>();
+            known.sort_unstable();
+            let known = known.iter().map(ToString::to_string).collect::<Vec<_>>();
+            let _ = writeln!(text, "{}: {}", person.id, known.join(", "));
+        }
+        text
+    }
+}
+
+/// Reads commands from stdin until `quit` or the end of it, starting from `party`.
+pub fn repl(party: Party, global: &Global) -> Result<(), String> {
+    let mut repl = Repl {
+        party,
+        algorithm: global.algorithm,
+    };
+    let interactive = io::stdin().is_terminal();
+    if interactive {
+        println!("type `help` for the commands");
+    }
+    let mut line = String::new();
+    loop {
+        if interactive {
+            print!("> ");
+            io::stdout().flush().map_err(|err| err.to_string())?;
+        }
+        line.clear();
+        let read = io::stdin().read_line(&mut line);
+        if read.map_err(|err| format!("<stdin>: {err}"))? == 0 {
+            return Ok(());
+        }
+        let reply = match line.parse::<Command>() {
+            Ok(command) => repl.execute(command),
+            Err(err) => Reply::Print(format!("error: {err}\n{USAGE}")),
+        };
+        match reply {
+            Reply::Print(text) => print!("{text}"),
+            Reply::Render => {
+                let session = Session::new(repl.party.clone());
+                let solved = clique(&session, global.algorithm, |_| {});
+                if let Err(err) = render(&session, solved.as_ref().map(Option::as_ref), global) {
+                    println!("error: {err}");
+                }
+            }
+            Reply::Quit => return Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "add 8 knows 1 2 3".parse(),
+            Ok(Command::Add {
+                id: 8,
+                known: vec![1, 2, 3]
+            })
+        );
+        assert_eq!(
+            "  add 8 ".parse(),
+            Ok(Command::Add {
+                id: 8,
+                known: Vec::new()
+            })
+        );
+        assert_eq!(
+            "forget 5 2".parse(),
+            Ok(Command::Forget { who: 5, whom: 2 })
+        );
+        assert_eq!("remove 4".parse(), Ok(Command::Remove(4)));
+        assert_eq!("exit".parse(), Ok(Command::Quit));
+        assert_eq!("".parse(), Ok(Command::Nothing));
+
+        for (line, err) in [
+            ("dance", "unknown command `dance`"),
+            ("add", "bad use of `add`"),
+            ("add 8 likes 1", "bad use of `add`"),
+            ("add eight", "`eight` is not an id"),
+            ("add 8 knows 1 two", "`two` is not an id"),
+            ("forget 5", "bad use of `forget`"),
+            ("remove 4 5", "bad use of `remove`"),
+            ("solve now", "bad use of `solve`"),
+        ] {
+            assert_eq!(line.parse::<Command>(), Err(err.to_owned()), "{line}");
+        }
+    }
+
+    #[test]
+    fn test_execute() {
+        let mut repl = Repl::default();
+        let mut run = |line: &str| repl.execute(line.parse().expect("a valid command"));
+        assert_eq!(run("show"), Reply::Print("nobody is at the party\n".into()));
+        for line in ["add 1 knows 2", "add 2 knows 1", "add 3 knows 1 2", "add 3"] {
+            assert_eq!(run(line), Reply::Print(String::new()));
+        }
+        assert_eq!(run("show"), Reply::Print("1: 2\n2: 1\n3: 1, 2\n".into()));
+        let Reply::Print(solved) = run("solve") else {
+            panic!("solve prints");
+        };
+        assert_eq!(solved.matches("knows").count(), 2, "{solved}");
+
+        assert_eq!(run("forget 3 2"), Reply::Print(String::new()));
+        assert_eq!(
+            run("forget 3 2"),
+            Reply::Print("3 does not know 2\n".into())
+        );
+        assert_eq!(run("solve"), Reply::Print("no celebrity clique\n".into()));
+        assert_eq!(run("remove 3"), Reply::Print(String::new()));
+        assert_eq!(
+            run("remove 3"),
+            Reply::Print("3 is not at the party\n".into())
+        );
+        assert_eq!(run("show"), Reply::Print("1: 2\n2: 1\n".into()));
+        assert_eq!(run("render"), Reply::Render);
+        assert_eq!(run("quit"), Reply::Quit);
+    }
+}
@@ -0,0 +1,76 @@
+use super::{Global, clique, input::read_party, json_solved, render, report};
+use log::warn;
+use serde_json::Value;
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+/// How often the file is looked at.
+const POLL: Duration = Duration::from_millis(100);
+
+/// What is looked at to tell whether a file changed: when it was modified, and its length.
+type Stamp = Option<(SystemTime, u64)>;
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Solves the party in `path`, and renders it if `rendering`, then again every time the file
+/// changes, until interrupted.
+///
+/// A change is only acted on once the file has stayed the same for `debounce`, so that a burst of
+/// saves is solved once. Problems with the file are reported without ending the watch.
+pub fn watch(path: &Path, rendering: bool, debounce: Duration, global: &Global) -> ! {
+    let mut last = stamp(path);
+    solve_once(path, rendering, global);
+    loop {
+        thread::sleep(POLL);
+        let mut current = stamp(path);
+        if current == last {
+            continue;
+        }
+        loop {
+            thread::sleep(debounce);
+            let settled = stamp(path);
+            if settled == current {
+                break;
+            }
+            current = settled;
+        }
+        last = current;
+        if io::stdout().is_terminal() && !global.json {
+            // clear the screen, and go back to its top
+            print!("\x1b[2J\x1b[H");
+        }
+        solve_once(path, rendering, global);
+    }
+}
+
+fn solve_once(path: &Path, rendering: bool, global: &Global) {
+    let result = read_party(path, global.input_format, global.identifiers()).and_then(|session| {
+        let css = clique(&session, global.algorithm, |err| warn!("{err}"))?;
+        let written = if rendering {
+            render(&session, Ok(css.as_ref()), global)?
+        } else {
+            Vec::new()
+        };
+        if global.json {
+            // one line each time
+            println!(
+                "{}",
+                Value::from(json_solved(&session, css.as_ref(), &written))
+            );
+        } else {
+            print!("{}", report(&session, css.as_ref()));
+        }
+        Ok(())
+    });
+    if let Err(message) = result {
+        eprintln!("error: {message}");
+    }
+}
@@ -1,21 +1,190 @@
+mod adjacency;
+mod batch;
+mod cancel;
+mod dense;
+mod error;
+mod gray;
+mod interner;
+mod mask;
+mod matrix;
+mod mutual;
+pub mod party;
 pub mod person;
+mod progress;
+mod slice;
+mod strategy;
+mod streaming;
+mod subsets;
+mod violation;
+mod words;
 
+pub use adjacency::AdjacencyClique;
+pub use batch::solve_batch;
+pub use cancel::{CANCEL_CHECK_INTERVAL, CancelToken, Cancelled};
+pub use dense::DenseParty;
+pub use error::{CliqueError, DEFAULT_SEARCH_LIMIT, SearchLimitExceeded};
+pub use interner::{InternError, Interner};
+pub use matrix::KnowsMatrix;
+pub use progress::SearchProgress;
+pub use slice::{DuplicateId, SliceClique};
+pub use strategy::SolveStrategy;
+pub use streaming::cclique_streaming;
+pub use subsets::{Combinations, PowerSet, k_subsets};
+pub use violation::{CcliqueViolation, ViolationKind};
+
+use gray::GraySearch;
+use mask::MaskTable;
+use mutual::MutualGraph;
 use person::Person;
 use petgraph::graph::DiGraph;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
+    convert::Infallible,
+    fmt::{self, Debug, Display, Formatter},
     hash::Hash,
+    iter,
 };
+use words::WordTable;
+
+/// The "knows" relation the celebrity clique is defined over.
+///
+/// Everybody knows themselves, so `x.knows(x)` must hold for every `x`.
+/// The key identifies a member; `Eq`, `Ord` and `Hash` on the implementor should agree with it.
+pub trait Knows {
+    type Key: Eq + Hash + Clone;
+
+    fn key(&self) -> &Self::Key;
+
+    #[must_use]
+    fn knows(&self, other: &Self) -> bool;
+
+    /// The keys this member claims to know, used to spot references to people who are not at the party.
+    ///
+    /// Implementors that cannot list them keep the default, which reports nobody.
+    fn acquaintances(&self) -> impl Iterator<Item = &Self::Key> {
+        iter::empty()
+    }
+}
+
+/// The key identifying a member of the clique-like collection `C`.
+pub type MemberKey<C> = <<C as Clique>::Member as Knows>::Key;
 
 pub trait Clique: Sized {
+    type Member: Knows + Clone;
+
+    fn members(&self) -> impl Iterator<Item = &Self::Member> + Clone;
+
+    fn contains_member(&self, member: &Self::Member) -> bool;
+
+    /// Collects `members` into a collection of the same kind, used for the subsets a search inspects.
+    fn from_members<I: IntoIterator<Item = Self::Member>>(members: I) -> Self;
+
     /// A clique is a non-empty set in which every members know each other.
-    fn is_clique(&self) -> bool;
+    ///
+    /// ```
+    /// use celebrities::{Clique, Person};
+    /// use std::collections::HashSet;
+    ///
+    /// let people = HashSet::from([(1_usize, vec![2]), (2, vec![1])].map(Person::from));
+    /// assert!(people.is_clique());
+    /// ```
+    #[must_use]
+    fn is_clique(&self) -> bool {
+        know_each_other(self.members())
+    }
+
+    /// Whether `self` is a clique that no other member of `party` can join, i.e. nobody outside
+    /// `self` is mutually acquainted with every member.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Party, party};
+    ///
+    /// let party = party! { 1 <=> 2; 2 <=> 3; 1 <=> 3 };
+    /// let pair = party.iter().filter(|person| person.id != 3).cloned().collect::<Party>();
+    /// assert!(pair.is_clique() && !pair.is_clique_maximal(&party));
+    /// assert!(party.is_clique_maximal(&party));
+    /// ```
+    #[must_use]
+    fn is_clique_maximal(&self, party: &Self) -> bool {
+        self.is_clique()
+            && !party
+                .members()
+                .filter(|guest| !self.contains_member(guest))
+                .any(|guest| {
+                    self.members()
+                        .all(|member| member.knows(guest) && guest.knows(member))
+                })
+    }
 
     /// A celebrity clique `C` is a non-empty set in which everybody at the party knows every member of `C`, but members of `C` know only each other.
     ///
     /// Theorem:
     /// All celebrity clique is a clique.
-    fn is_cclique(&self, party: &Self) -> bool;
+    ///
+    /// ```
+    /// use celebrities::{Clique, Person};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = HashSet::from([(1_usize, vec![2]), (2, vec![1]), (3, vec![1, 2])].map(Person::from));
+    /// let celebrities = HashSet::from([(1_usize, vec![2]), (2, vec![1])].map(Person::from));
+    /// assert!(celebrities.is_cclique(&party));
+    /// assert!(!party.is_cclique(&party));
+    ///
+    /// // 3 is not known by anyone, so it cannot be a celebrity.
+    /// let outsider = HashSet::from([(3_usize, vec![1, 2])].map(Person::from));
+    /// assert!(!outsider.is_cclique(&party));
+    /// ```
+    #[must_use]
+    fn is_cclique(&self, party: &Self) -> bool {
+        self.why_not_cclique(party).is_none()
+    }
+
+    /// Explains why `self` is not a celebrity clique of `party`, or returns `None` if it is one.
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::CcliqueViolation, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1] };
+    /// assert_eq!(
+    ///     party! { 1 <=> 2 }.why_not_cclique(&party),
+    ///     Some(CcliqueViolation::NotKnownByEveryone { guest: 3, celebrity: 2 })
+    /// );
+    /// ```
+    #[must_use]
+    fn why_not_cclique(&self, party: &Self) -> Option<CcliqueViolation<MemberKey<Self>>> {
+        violations_among(self.members(), party.members(), |someone| {
+            self.contains_member(someone)
+        })
+        .next()
+    }
+
+    /// Every `(guest, celebrity, kind)` pair that breaks the celebrity clique definition, at most `limit` of them.
+    ///
+    /// For [`ViolationKind::KnowsOutsider`] the guest is the outsider the celebrity knows.
+    /// Prefer [`is_cclique`](Clique::is_cclique) when a yes or no answer is enough.
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::ViolationKind, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [] };
+    /// let mut violations = party! { 1 <=> 2 }.cclique_violations(&party, None);
+    /// violations.sort_unstable();
+    /// assert_eq!(violations, [(3, 1, ViolationKind::NotKnownByEveryone), (3, 2, ViolationKind::NotKnownByEveryone)]);
+    /// assert_eq!(party! { 1 <=> 2 }.cclique_violations(&party, Some(1)).len(), 1);
+    /// ```
+    #[must_use]
+    fn cclique_violations(
+        &self,
+        party: &Self,
+        limit: Option<usize>,
+    ) -> Vec<(MemberKey<Self>, MemberKey<Self>, ViolationKind)> {
+        violations_among(self.members(), party.members(), |someone| {
+            self.contains_member(someone)
+        })
+        .take(limit.unwrap_or(usize::MAX))
+        .map(CcliqueViolation::into_triple)
+        .collect()
+    }
 
     /// Exhaustive search of cclique.
     ///
@@ -28,60 +197,784 @@ pub trait Clique: Sized {
     /// We have that `c1` knows `c2` from the fact that everybody in the clique `C2` is known by everybody at the party.
     /// But since clique members know only other members of the clique, it follows that `c2` in `C1`.
     /// Since `c2` was arbitrary, we have `C2` is a subset of `C1` and, by symmetry, `C1` is a subset of `C2`.
-    fn cclique(&self) -> Option<Self>;
-}
+    ///
+    /// ```
+    /// use celebrities::{Clique, Person};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = HashSet::from(
+    ///     [(1_usize, vec![2, 3]), (2, vec![1, 3]), (3, vec![1, 2]), (4, vec![1, 2, 3])].map(Person::from),
+    /// );
+    /// let mut ids = party.cclique().unwrap_or_default().iter().map(|p| p.id).collect::<Vec<_>>();
+    /// ids.sort_unstable();
+    /// assert_eq!(ids, [1, 2, 3]);
+    /// ```
+    ///
+    /// Every subset of the candidates is searched, however many there are, so this can run for a
+    /// very long time. Use [`cclique_with_limit`](Clique::cclique_with_limit) or
+    /// [`try_cclique`](Clique::try_cclique) to fail past [`DEFAULT_SEARCH_LIMIT`] subsets instead,
+    /// or a polynomial solver such as [`cclique_elimination`](Clique::cclique_elimination).
+    #[must_use]
+    fn cclique(&self) -> Option<Self> {
+        first_cclique(&known_by_everyone(self.members()), &self.members())
+            .map(|people| Self::from_members(people.into_iter().cloned()))
+    }
 
-impl Clique for HashSet<Person> {
-    fn is_clique(&self) -> bool {
-        let clique = self.iter().map(|member| member.id).collect::<HashSet<_>>();
-        self.iter().all(|member| {
-            clique
-                .difference(&member.known_people)
-                .filter(|rem| **rem != member.id)
-                .count()
-                == 0
-        })
+    /// Like [`cclique`](Clique::cclique), but gives up before inspecting more than `max_subsets` subsets.
+    ///
+    /// Only the members known by everybody are candidates, so large parties with few of them are fine.
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::SearchLimitExceeded, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_with_limit(4).map(|css| css.map(|css| css.len())), Ok(Some(2)));
+    /// assert_eq!(party.cclique_with_limit(3), Err(SearchLimitExceeded { party_size: 3, limit: 3 }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the candidates have more than `max_subsets` subsets.
+    fn cclique_with_limit(&self, max_subsets: usize) -> Result<Option<Self>, SearchLimitExceeded> {
+        let candidates = known_by_everyone(self.members());
+        if subset_count(candidates.len()).is_none_or(|subsets| subsets > max_subsets) {
+            return Err(SearchLimitExceeded {
+                party_size: self.members().count(),
+                limit: max_subsets,
+            });
+        }
+        Ok(first_cclique(&candidates, &self.members())
+            .map(|people| Self::from_members(people.into_iter().cloned())))
     }
 
-    fn is_cclique(&self, party: &Self) -> bool {
-        for someone in party {
-            for celebrity in self {
-                if !someone.knows(celebrity)
-                    || (celebrity.knows(someone) && !self.contains(someone))
-                {
-                    return false;
-                }
+    /// Like [`cclique`](Clique::cclique), but searches over borrowed members
+    /// and returns the celebrities without cloning them.
+    ///
+    /// Every celebrity is known by every guest, so only the subsets of the members known by
+    /// everybody are searched.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 => [2]; 2 => [1]; 3 => [1, 2] };
+    /// let mut ids = party.cclique_ref().unwrap_or_default().iter().map(|p| p.id).collect::<Vec<_>>();
+    /// ids.sort_unstable();
+    /// assert_eq!(ids, [1, 2]);
+    /// ```
+    #[must_use]
+    fn cclique_ref(&self) -> Option<Vec<&Self::Member>> {
+        first_cclique(&known_by_everyone(self.members()), &self.members())
+    }
+
+    /// Like [`cclique`](Clique::cclique), without a limit on the number of subsets, but the search
+    /// gives up once `token` is cancelled.
+    ///
+    /// The token is looked at every [`CANCEL_CHECK_INTERVAL`] subsets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Cancelled`] if `token` was cancelled before the search finished.
+    fn cclique_cancellable(&self, token: &CancelToken) -> Result<Option<Self>, Cancelled> {
+        let candidates = known_by_everyone(self.members());
+        let mut examined = 0_usize;
+        let css = search(&candidates, &self.members(), |_| {
+            examined += 1;
+            if examined % CANCEL_CHECK_INTERVAL == 1 && token.is_cancelled() {
+                Err(Cancelled)
+            } else {
+                Ok(())
+            }
+        })?;
+        Ok(css.map(|people| Self::from_members(people.into_iter().cloned())))
+    }
+
+    /// Like [`cclique`](Clique::cclique), without a limit on the number of subsets, but `report` is
+    /// told how the search is going every `stride` subsets, and once more when it is over.
+    ///
+    /// Like [`cclique`](Clique::cclique), this never gives up past [`DEFAULT_SEARCH_LIMIT`]: with
+    /// many candidates it runs for as long as the `total` of its reports says. Use
+    /// [`cclique_cancellable`](Clique::cclique_cancellable) for a search that can be stopped.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 => [1, 2, 3]; 2 => [1, 3]; 3 => [1, 2]; 4 => [1, 2, 3] };
+    /// let mut reports = Vec::new();
+    /// let css = party.cclique_with_progress(2, |progress| reports.push(progress.examined));
+    /// assert_eq!(css.map(|css| css.len()), Some(3));
+    /// assert_eq!(reports.first(), Some(&0));
+    /// ```
+    #[must_use]
+    fn cclique_with_progress(
+        &self,
+        stride: usize,
+        mut report: impl FnMut(SearchProgress),
+    ) -> Option<Self> {
+        let candidates = known_by_everyone(self.members());
+        let total = non_empty_subsets(candidates.len());
+        let mut progress = SearchProgress {
+            examined: 0,
+            size: 0,
+            total,
+        };
+        let Ok(css) = search(&candidates, &self.members(), |size| {
+            if progress.examined.is_multiple_of(stride.max(1)) {
+                report(SearchProgress { size, ..progress });
             }
+            progress.examined += 1;
+            progress.size = size;
+            Ok::<_, Infallible>(())
+        });
+        report(progress);
+        css.map(|people| Self::from_members(people.into_iter().cloned()))
+    }
+
+    /// Like [`cclique`](Clique::cclique), but the candidate subsets are verified in parallel.
+    ///
+    /// Each subset is generated from its index as a bit mask, so no power set is built up front.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_par(), party.cclique());
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    fn cclique_par(&self) -> Option<Self>
+    where
+        Self: Sync,
+        Self::Member: Sync,
+    {
+        use rayon::prelude::*;
+
+        let candidates = known_by_everyone(self.members());
+        if candidates.len() >= 64 {
+            return self.cclique();
         }
-        true
+        let select = |mask: u64| {
+            candidates
+                .iter()
+                .enumerate()
+                .filter(move |(bit, _)| mask >> bit & 1 == 1)
+                .map(|(_, &candidate)| candidate)
+        };
+        (1..1_u64 << candidates.len())
+            .into_par_iter()
+            .find_any(|&mask| is_cclique_among(&select(mask).collect::<Vec<_>>(), self.members()))
+            .map(|mask| Self::from_members(select(mask).cloned()))
     }
 
-    fn cclique(&self) -> Option<Self> {
-        power_set(self)
-            .iter()
-            .skip(1)
-            .find(|&people| people.is_cclique(self))
+    /// Like [`is_cclique`](Clique::is_cclique), but the guests of `party` are split across rayon's
+    /// thread pool.
+    ///
+    /// Each guest is checked against every celebrity once, for both conditions: that they know the
+    /// celebrity, and, if they are not one themselves, that the celebrity does not know them.
+    /// The search stops on every thread as soon as one of them finds a violation.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert!(party! { 1 <=> 2 }.is_cclique_par(&party));
+    /// assert!(!party! { 1 => [2] }.is_cclique_par(&party));
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[must_use]
+    fn is_cclique_par(&self, party: &Self) -> bool
+    where
+        Self: Sync,
+        Self::Member: Sync,
+    {
+        use rayon::prelude::*;
+
+        let celebrities = self.members().collect::<Vec<_>>();
+        let guests = party.members().collect::<Vec<_>>();
+        !guests.par_iter().any(|guest| {
+            let is_member = self.contains_member(guest);
+            celebrities
+                .iter()
+                .any(|celebrity| !guest.knows(celebrity) || (!is_member && celebrity.knows(guest)))
+        })
+    }
+
+    /// Like [`cclique`](Clique::cclique), but tells bad input apart from a party without a celebrity clique.
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::CliqueError, party};
+    ///
+    /// assert_eq!(party! {}.try_cclique(), Err(CliqueError::EmptyParty));
+    /// assert_eq!(
+    ///     party! { 1 => [2] }.try_cclique(),
+    ///     Err(CliqueError::InconsistentData { missing_ids: vec![2] })
+    /// );
+    /// assert_eq!(party! { 1 => []; 2 => [] }.try_cclique(), Ok(None));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the party is empty, if somebody knows a person who is not at the party,
+    /// or if the members known by everybody have more than [`DEFAULT_SEARCH_LIMIT`] subsets.
+    fn try_cclique(&self) -> Result<Option<Self>, CliqueError<MemberKey<Self>>> {
+        let party_size = self.members().count();
+        if party_size == 0 {
+            return Err(CliqueError::EmptyParty);
+        }
+
+        let keys = self.members().map(Knows::key).collect::<HashSet<_>>();
+        let mut seen = HashSet::new();
+        let missing_ids = self
+            .members()
+            .flat_map(Knows::acquaintances)
+            .filter(|&key| !keys.contains(key) && seen.insert(key))
             .cloned()
+            .collect::<Vec<_>>();
+        if !missing_ids.is_empty() {
+            return Err(CliqueError::InconsistentData { missing_ids });
+        }
+
+        Ok(self.cclique_with_limit(DEFAULT_SEARCH_LIMIT)?)
     }
-}
 
-fn power_set<T: Clone + Eq + Hash>(set: &HashSet<T>) -> Vec<HashSet<T>> {
-    let mut levels = Vec::with_capacity(set.len() + 1);
-    levels.push(vec![HashSet::default()]);
-    for k in 1..=set.len() {
-        levels.push(Vec::with_capacity(binomial_approx(set.len(), k)));
+    /// Every clique with exactly `k` members.
+    ///
+    /// Only the `k`-subsets are checked, so this is much cheaper than walking the power set
+    /// when `k` is small. Since a clique is non-empty, `k = 0` yields nothing.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Person};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = HashSet::from([(1_usize, vec![2]), (2, vec![1]), (3, vec![1])].map(Person::from));
+    /// assert_eq!(party.cliques_of_size(1).len(), 3);
+    /// assert_eq!(party.cliques_of_size(2).len(), 1);
+    /// assert!(party.cliques_of_size(3).is_empty());
+    /// ```
+    #[must_use]
+    fn cliques_of_size(&self, k: usize) -> Vec<Self> {
+        if k == 0 {
+            return Vec::new();
+        }
+        let pool = self.members().collect::<Vec<_>>();
+        Combinations::<_, Vec<_>>::new(&pool, k)
+            .filter(|people| know_each_other(people.iter().copied()))
+            .map(|people| Self::from_members(people.into_iter().cloned()))
+            .collect()
+    }
+
+    /// The celebrity clique together with the rest of the party.
+    ///
+    /// The two collections are disjoint and together make up `self`.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// let (css, rest) = party.cclique_partition().unwrap_or_default();
+    /// assert_eq!(css.ids(), HashSet::from([1, 2]));
+    /// assert_eq!(rest.ids(), HashSet::from([3]));
+    /// ```
+    #[must_use]
+    fn cclique_partition(&self) -> Option<(Self, Self)> {
+        self.cclique().map(|css| {
+            let rest = self
+                .members()
+                .filter(|member| !css.contains_member(member))
+                .cloned();
+            let rest = Self::from_members(rest);
+            (css, rest)
+        })
+    }
+
+    /// A largest clique of mutual acquaintances, or `None` for an empty party.
+    ///
+    /// Unlike the celebrity clique, ordinary cliques are not unique. Members are visited in key
+    /// order, so ties are broken the same way on every run.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 2 <=> 3; 1 <=> 3; 3 <=> 4 };
+    /// assert_eq!(party.max_clique().map(|clique| clique.ids()), Some(HashSet::from([1, 2, 3])));
+    /// ```
+    #[must_use]
+    fn max_clique(&self) -> Option<Self>
+    where
+        MemberKey<Self>: Ord,
+    {
+        let mut members = self.members().collect::<Vec<_>>();
+        members.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+
+        MutualGraph::new(&members)
+            .largest_clique()
+            .map(|clique| Self::from_members(clique.into_iter().map(|i| members[i].clone())))
     }
 
-    for elem in set {
-        for cap in (0..set.len()).rev() {
-            for subset in &levels[cap].clone() {
-                let mut temp = subset.clone();
-                temp.insert(elem.clone());
-                levels[cap + 1].push(temp);
+    /// A largest clique of mutual acquaintances that includes the member with `key`,
+    /// or `None` if there is no such member.
+    ///
+    /// Only the people mutually acquainted with that member are searched.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 2 <=> 3; 1 <=> 3; 3 <=> 4 };
+    /// assert_eq!(party.clique_containing(&4).map(|clique| clique.ids()), Some(HashSet::from([3, 4])));
+    /// assert_eq!(party.clique_containing(&5), None);
+    /// ```
+    #[must_use]
+    fn clique_containing(&self, key: &MemberKey<Self>) -> Option<Self>
+    where
+        MemberKey<Self>: Ord,
+    {
+        let person = self.members().find(|member| member.key() == key)?;
+        let mut neighborhood = self
+            .members()
+            .filter(|&member| member.key() != key && member.knows(person) && person.knows(member))
+            .collect::<Vec<_>>();
+        neighborhood.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+
+        let largest = MutualGraph::new(&neighborhood)
+            .largest_clique()
+            .unwrap_or_default();
+        Some(Self::from_members(
+            iter::once(person.clone()).chain(largest.into_iter().map(|i| neighborhood[i].clone())),
+        ))
+    }
+
+    /// Every maximal clique of mutual acquaintances, each exactly once.
+    ///
+    /// An acquaintance only counts when it is reciprocated, so somebody nobody knows back is a
+    /// maximal clique on their own.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 <=> 2; 2 <=> 3; 3 => [1]; 4 => [1] };
+    /// assert_eq!(party.maximal_cliques().len(), 3); // {1, 2}, {2, 3} and {4}
+    /// ```
+    #[must_use]
+    fn maximal_cliques(&self) -> Vec<Self> {
+        let members = self.members().collect::<Vec<_>>();
+        let mut cliques = Vec::new();
+        MutualGraph::new(&members).maximal_cliques(&mut |clique| {
+            cliques.push(Self::from_members(
+                clique.iter().map(|&i| members[i].clone()),
+            ));
+        });
+        cliques
+    }
+
+    /// Like [`cclique`](Clique::cclique), but only subsets of the members whose key is in
+    /// `candidates` are considered. Each of them is still checked against the whole party.
+    ///
+    /// Candidates that are not members of the party are ignored.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_in(&HashSet::from([1, 2, 42])).map(|css| css.ids()), Some(HashSet::from([1, 2])));
+    /// assert_eq!(party.cclique_in(&HashSet::from([1, 3])), None);
+    /// ```
+    ///
+    /// Every subset of the candidates is searched, even those with a member somebody does not
+    /// know. Use [`cclique_in_with_limit`](Clique::cclique_in_with_limit) for a long list.
+    #[must_use]
+    fn cclique_in(&self, candidates: &HashSet<MemberKey<Self>>) -> Option<Self> {
+        let pool = self
+            .members()
+            .filter(|member| candidates.contains(member.key()))
+            .collect::<Vec<_>>();
+        PowerSet::<_, Vec<_>>::new(&pool)
+            .skip(1)
+            .find(|people| is_cclique_among(people, self.members()))
+            .map(|people| Self::from_members(people.into_iter().cloned()))
+    }
+
+    /// Like [`cclique_in`](Clique::cclique_in), but only the candidates known by everybody are
+    /// searched, giving up before inspecting more than `max_subsets` of their subsets, like
+    /// [`cclique_with_limit`](Clique::cclique_with_limit).
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::SearchLimitExceeded, party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// let css = party.cclique_in_with_limit(&HashSet::from([1, 2, 3]), 4);
+    /// assert_eq!(css.map(|css| css.map(|css| css.ids())), Ok(Some(HashSet::from([1, 2]))));
+    /// let css = party.cclique_in_with_limit(&HashSet::from([1, 2]), 3);
+    /// assert_eq!(css, Err(SearchLimitExceeded { party_size: 3, limit: 3 }));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the candidates known by everybody have more than `max_subsets` subsets.
+    fn cclique_in_with_limit(
+        &self,
+        candidates: &HashSet<MemberKey<Self>>,
+        max_subsets: usize,
+    ) -> Result<Option<Self>, SearchLimitExceeded> {
+        let pool = known_by_everyone(self.members())
+            .into_iter()
+            .filter(|member| candidates.contains(member.key()))
+            .collect::<Vec<_>>();
+        if subset_count(pool.len()).is_none_or(|subsets| subsets > max_subsets) {
+            return Err(SearchLimitExceeded {
+                party_size: self.members().count(),
+                limit: max_subsets,
+            });
+        }
+        Ok(first_cclique(&pool, &self.members())
+            .map(|people| Self::from_members(people.into_iter().cloned())))
+    }
+
+    /// Like [`cclique`](Clique::cclique), but only subsets of at most `max_size` people are searched.
+    ///
+    /// A celebrity clique with more than `max_size` members is missed, and `None` is returned
+    /// as if there were none: no smaller subset can stand in for it, since the celebrity clique
+    /// is unique. In exchange, at most `C(k, 1) + ... + C(k, max_size)` subsets of the `k`
+    /// candidates are checked rather than `2^k`.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_bounded(2), party.cclique());
+    /// assert_eq!(party.cclique_bounded(1), None);
+    /// ```
+    #[must_use]
+    fn cclique_bounded(&self, max_size: usize) -> Option<Self> {
+        let candidates = known_by_everyone(self.members());
+        (1..=max_size.min(candidates.len()))
+            .flat_map(|size| Combinations::<_, Vec<_>>::new(&candidates, size))
+            .find(|celebrities| is_cclique_among(celebrities, self.members()))
+            .map(|celebrities| Self::from_members(celebrities.into_iter().cloned()))
+    }
+
+    /// The celebrity clique, found by repeatedly dropping candidates until none is disqualified.
+    ///
+    /// Everybody starts as a candidate. Anyone not known by some guest, or who knows somebody who is no
+    /// longer a candidate, is dropped, until a fixed point is reached. The survivors are then verified
+    /// with the definition, as a safeguard against [`Knows`] implementations that are not reflexive.
+    /// At most `n` rounds of `O(n²)` [`knows`](Knows::knows) queries are made.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_elimination().map(|css| css.ids()), Some(HashSet::from([1, 2])));
+    /// // 2 knows 3, then 1 knows 2 once 2 is dropped.
+    /// assert_eq!(party! { 1 => [2]; 2 => [1, 3]; 3 => [1, 2]; 4 => [1, 2] }.cclique_elimination(), None);
+    /// ```
+    #[must_use]
+    fn cclique_elimination(&self) -> Option<Self> {
+        let candidates = eliminate(&self.members());
+        (!candidates.is_empty() && is_cclique_among(&candidates, self.members()))
+            .then(|| Self::from_members(candidates.into_iter().cloned()))
+    }
+
+    /// The celebrity clique, found with the cheapest strategy that gives a verified answer, and
+    /// which strategy that was.
+    ///
+    /// The people known by everybody are tried first, then the survivors of
+    /// [`cclique_elimination`](Clique::cclique_elimination), and only if those fail verification
+    /// with [`is_cclique`](Clique::is_cclique) is the exhaustive [`cclique`](Clique::cclique) run.
+    /// For a [`Knows`] relation that answers consistently the elimination is always right, so the
+    /// exhaustive search guards against relations that do not, such as a flaky remote lookup.
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::SolveStrategy, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_fast(), (party.cclique(), SolveStrategy::Candidates));
+    /// let party = party! { 1 => [2]; 2 => [1, 3]; 3 => [1, 2]; 4 => [1, 2] };
+    /// assert_eq!(party.cclique_fast(), (None, SolveStrategy::Elimination));
+    /// ```
+    #[must_use]
+    fn cclique_fast(&self) -> (Option<Self>, SolveStrategy) {
+        let verified = |people: Vec<&Self::Member>| {
+            let people = Self::from_members(people.into_iter().cloned());
+            people.is_cclique(self).then_some(people)
+        };
+        let candidates = known_by_everyone(self.members());
+        if candidates.is_empty() {
+            return (None, SolveStrategy::Candidates);
+        }
+        if let Some(css) = verified(candidates) {
+            return (Some(css), SolveStrategy::Candidates);
+        }
+        let survivors = eliminate(&self.members());
+        if survivors.is_empty() {
+            return (None, SolveStrategy::Elimination);
+        }
+        if let Some(css) = verified(survivors) {
+            return (Some(css), SolveStrategy::Elimination);
+        }
+        let css = self.cclique().filter(|css| css.is_cclique(self));
+        (css, SolveStrategy::Exhaustive)
+    }
+
+    /// Like [`cclique`](Clique::cclique), but visits the subsets of the candidates in Gray-code order.
+    ///
+    /// Each subset differs from the previous one by a single person, so the number of guests who
+    /// do not know everybody in it, and the number of acquaintances its members have outside it,
+    /// are updated with `O(n)` work instead of checking every subset from scratch.
+    /// Parties with 64 or more candidates are handed to [`cclique`](Clique::cclique).
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_gray(), party.cclique());
+    /// ```
+    #[must_use]
+    fn cclique_gray(&self) -> Option<Self> {
+        let guests = self.members().collect::<Vec<_>>();
+        let candidates = (guests.iter().enumerate())
+            .filter(|(_, candidate)| guests.iter().all(|guest| guest.knows(candidate)))
+            .map(|(position, _)| position)
+            .collect::<Vec<_>>();
+        if candidates.len() >= 64 {
+            return self.cclique();
+        }
+        let mut search = GraySearch::new(guests, candidates)?;
+        let css = search.first_cclique()?;
+        Some(Self::from_members(
+            search.members(&css).into_iter().cloned(),
+        ))
+    }
+
+    /// The one person everybody knows and who knows nobody else, i.e. a celebrity clique of size one.
+    ///
+    /// Candidates are eliminated pairwise: if `a` knows `b`, `a` is not the celebrity, otherwise `b` is not.
+    /// The survivor is then verified against everybody, so at most `3n` [`knows`](Knows::knows) queries are made.
+    /// There is no such person when the celebrity clique has several members.
+    ///
+    /// ```
+    /// use celebrities::{Clique, party};
+    ///
+    /// assert_eq!(party! { 1 => []; 2 => [1]; 3 => [1, 2] }.celebrity().map(|p| p.id), Some(1));
+    /// assert_eq!(party! { 1 <=> 2; 3 => [1, 2] }.celebrity(), None);
+    /// ```
+    #[must_use]
+    fn celebrity(&self) -> Option<Self::Member> {
+        let mut people = self.members();
+        let first = people.next()?;
+        let candidate = people.fold(first, |candidate, other| {
+            if candidate.knows(other) {
+                other
+            } else {
+                candidate
             }
+        });
+        is_cclique_among(&[candidate], self.members()).then(|| candidate.clone())
+    }
+}
+
+impl<T: Knows + Eq + Hash + Clone> Clique for HashSet<T> {
+    type Member = T;
+
+    fn members(&self) -> impl Iterator<Item = &T> + Clone {
+        self.iter()
+    }
+
+    fn contains_member(&self, member: &T) -> bool {
+        self.contains(member)
+    }
+
+    fn from_members<I: IntoIterator<Item = T>>(members: I) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+/// Duplicate ids are not detected here; see [`SliceClique`] for the checked variants.
+impl<T: Knows + PartialEq + Clone> Clique for Vec<T> {
+    type Member = T;
+
+    fn members(&self) -> impl Iterator<Item = &T> + Clone {
+        self.iter()
+    }
+
+    fn contains_member(&self, member: &T) -> bool {
+        self.contains(member)
+    }
+
+    fn from_members<I: IntoIterator<Item = T>>(members: I) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+impl<T: Knows + Ord + Clone> Clique for BTreeSet<T> {
+    type Member = T;
+
+    fn members(&self) -> impl Iterator<Item = &T> + Clone {
+        self.iter()
+    }
+
+    fn contains_member(&self, member: &T) -> bool {
+        self.contains(member)
+    }
+
+    fn from_members<I: IntoIterator<Item = T>>(members: I) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+// The collection-independent cores of `is_clique` and `is_cclique`, also used by `SliceClique`.
+
+/// The non-empty subset of `candidates` that is a celebrity clique of `party`.
+///
+/// `candidates` are the members known by everybody, as [`known_by_everyone`] finds them. Parties
+/// that fit in a [`MaskTable`] are searched with `u128` masks, larger ones with a [`WordTable`]
+/// of the rows of the candidates alone.
+fn first_cclique<'a, T, P>(candidates: &[&'a T], party: &P) -> Option<Vec<&'a T>>
+where
+    T: Knows + 'a,
+    P: IntoIterator<Item = &'a T> + Clone,
+{
+    let Ok(css) = search(candidates, party, |_| Ok::<_, Infallible>(()));
+    css
+}
+
+/// Like [`first_cclique`], but `step` is called with the size of each subset before it is
+/// inspected, and the search stops with its error if it fails.
+fn search<'a, T, P, E>(
+    candidates: &[&'a T],
+    party: &P,
+    step: impl FnMut(usize) -> Result<(), E>,
+) -> Result<Option<Vec<&'a T>>, E>
+where
+    T: Knows + 'a,
+    P: IntoIterator<Item = &'a T> + Clone,
+{
+    if candidates.is_empty() {
+        return Ok(None);
+    }
+    let guests = party.clone().into_iter().collect::<Vec<_>>();
+    if let Some(table) = MaskTable::new(guests.clone()) {
+        let css = table.first_cclique(table.mask_of(candidates), step)?;
+        return Ok(css.map(|css| table.members(css)));
+    }
+    WordTable::new(&guests, candidates).first_cclique(step)
+}
+
+/// `2^n`, or `None` if that does not fit in a `usize`.
+fn subset_count(n: usize) -> Option<usize> {
+    u32::try_from(n).ok().and_then(|n| 1_usize.checked_shl(n))
+}
+
+/// `2^n - 1`, saturating at `usize::MAX`.
+fn non_empty_subsets(n: usize) -> usize {
+    subset_count(n).map_or(usize::MAX, |subsets| subsets - 1)
+}
+
+/// The fixed point of [`Clique::cclique_elimination`], before it is verified.
+fn eliminate<'a, T, P>(party: &P) -> Vec<&'a T>
+where
+    T: Knows + 'a,
+    P: IntoIterator<Item = &'a T> + Clone,
+{
+    let mut candidates = known_by_everyone(party.clone());
+    loop {
+        let is_candidate = |someone: &T| {
+            candidates
+                .iter()
+                .any(|candidate| candidate.key() == someone.key())
+        };
+        let survivors = candidates
+            .iter()
+            .copied()
+            .filter(|candidate| {
+                (party.clone().into_iter())
+                    .all(|guest| is_candidate(guest) || !candidate.knows(guest))
+            })
+            .collect::<Vec<_>>();
+        if survivors.len() == candidates.len() {
+            return candidates;
         }
+        candidates = survivors;
     }
-    levels.into_iter().flatten().collect()
+}
+
+/// Whether `celebrities` form a celebrity clique of `party`.
+fn is_cclique_among<'a, T, P>(celebrities: &[&'a T], party: P) -> bool
+where
+    T: Knows + 'a,
+    P: IntoIterator<Item = &'a T>,
+{
+    let is_member = |someone: &T| {
+        celebrities
+            .iter()
+            .any(|celebrity| celebrity.key() == someone.key())
+    };
+    violations_among(celebrities.iter().copied(), party, is_member)
+        .next()
+        .is_none()
+}
+
+/// The people known by all of `party`, in the order of `party`.
+fn known_by_everyone<'a, T, I>(party: I) -> Vec<&'a T>
+where
+    T: Knows + 'a,
+    I: IntoIterator<Item = &'a T> + Clone,
+{
+    let guests = party.clone();
+    party
+        .into_iter()
+        .filter(|candidate| {
+            guests
+                .clone()
+                .into_iter()
+                .all(|guest| guest.knows(candidate))
+        })
+        .collect()
+}
+
+fn know_each_other<'a, T, I>(members: I) -> bool
+where
+    T: Knows + 'a,
+    I: IntoIterator<Item = &'a T> + Clone,
+{
+    let others = members.clone();
+    members
+        .into_iter()
+        .all(|member| others.clone().into_iter().all(|other| member.knows(other)))
+}
+
+/// Every violation of the celebrity clique definition, guest by guest.
+fn violations_among<'a, T, C, P, F>(
+    celebrities: C,
+    party: P,
+    is_member: F,
+) -> impl Iterator<Item = CcliqueViolation<T::Key>>
+where
+    T: Knows + 'a,
+    C: IntoIterator<Item = &'a T> + Clone,
+    P: IntoIterator<Item = &'a T>,
+    F: Fn(&T) -> bool + Copy,
+{
+    party.into_iter().flat_map(move |someone| {
+        celebrities.clone().into_iter().flat_map(move |celebrity| {
+            let unknown =
+                (!someone.knows(celebrity)).then(|| CcliqueViolation::NotKnownByEveryone {
+                    guest: someone.key().clone(),
+                    celebrity: celebrity.key().clone(),
+                });
+            let outsider = (celebrity.knows(someone) && !is_member(someone)).then(|| {
+                CcliqueViolation::KnowsOutsider {
+                    celebrity: celebrity.key().clone(),
+                    outsider: someone.key().clone(),
+                }
+            });
+            unknown.into_iter().chain(outsider)
+        })
+    })
 }
 
 fn binomial_approx(n: usize, k: usize) -> usize {
@@ -95,22 +988,80 @@ fn binomial_approx(n: usize, k: usize) -> usize {
     // equivalane to (0..=n).rev().zip(1..=k).fold(1, |mut acc, (num, denom)| {acc *= num; acc /= denom; acc})
 }
 
-pub fn clique2digraph(clique: &HashSet<Person>) -> DiGraph<usize, ()> {
+/// The node weight of [`clique2digraph`].
+///
+/// Both `Display` and `Debug` print the bare label, so that `Dot` does not wrap it in a second pair of quotes.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Label(pub String);
+
+impl Display for Label {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Debug for Label {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Builds the "knows" digraph of `clique`, keeping only edges between its members.
+///
+/// Nodes are weighted with the [`Person::label`] of each member, so formatting the graph with
+/// [`Dot`](petgraph::dot::Dot) yields properly escaped labels such as `"Alice (1)"`.
+/// They are added in the iteration order of `clique`, so the `i`-th person is node `i`.
+///
+/// ```
+/// use celebrities::{Person, clique2digraph};
+/// use std::collections::HashSet;
+///
+/// let people = HashSet::from([
+///     Person::builder(1).knows(2).build(),
+///     Person::builder(2).knows_many([1, 42]).build(),
+/// ]);
+/// let graph = clique2digraph(&people);
+/// assert_eq!(graph.node_count(), 2);
+/// assert_eq!(graph.edge_count(), 2);
+/// ```
+#[must_use]
+pub fn clique2digraph<Id>(clique: &HashSet<Person<Id>>) -> DiGraph<Label, ()>
+where
+    Id: Eq + Hash + Clone + Display,
+{
+    clique2digraph_with(clique, Person::label)
+}
+
+/// Like [`clique2digraph`], with the nodes labelled by `label` instead of [`Person::label`].
+///
+/// ```
+/// use celebrities::{Person, clique::clique2digraph_with};
+/// use std::collections::HashSet;
+///
+/// let people = HashSet::from([Person::named(1, "Alice", [2]), Person::new(2, [1])]);
+/// let graph = clique2digraph_with(&people, |person| person.name.clone().unwrap_or_default());
+/// assert!(graph.node_weights().any(|label| label.0 == "Alice"));
+/// ```
+pub fn clique2digraph_with<Id, F>(clique: &HashSet<Person<Id>>, label: F) -> DiGraph<Label, ()>
+where
+    Id: Eq + Hash + Clone + Display,
+    F: Fn(&Person<Id>) -> String,
+{
     let mut graph = DiGraph::new();
 
-    let mut nodes: HashMap<usize, _> = HashMap::with_capacity(clique.len());
+    let mut nodes: HashMap<Id, _> = HashMap::with_capacity(clique.len());
     for person in clique {
-        nodes.insert(person.id, graph.add_node(person.id));
+        nodes.insert(person.id.clone(), graph.add_node(Label(label(person))));
     }
 
     for person in clique {
-        for known_person_id in person.known_people.clone() {
+        for known_person_id in &person.known_people {
             graph.add_edge(
                 match nodes.get(&person.id) {
                     None => continue,
                     Some(node) => *node,
                 },
-                match nodes.get(&known_person_id) {
+                match nodes.get(known_person_id) {
                     None => continue,
                     Some(node) => *node,
                 },
@@ -125,19 +1076,963 @@ pub fn clique2digraph(clique: &HashSet<Person>) -> DiGraph<usize, ()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generator::sample_parties;
+    use petgraph::dot::{Config, Dot};
+    use std::cell::Cell;
 
     #[test]
     #[allow(clippy::iter_on_single_items)]
     fn test_power_set() {
-        println!("{:?}", power_set(&[0; 0].into_iter().collect()).len());
-        println!("{:?}", power_set(&[1].into_iter().collect()).len());
-        println!("{:?}", power_set(&[1, 2, 3].into_iter().collect()).len());
-        println!(
-            "{:?}",
-            power_set(&[1, 2, 3, 4, 5].into_iter().collect()).len()
+        for n in [0, 1, 3, 5] {
+            let set = (0..n).collect::<HashSet<_>>();
+            assert_eq!(PowerSet::<_>::new(&set).count(), 1 << n);
+        }
+    }
+
+    #[test]
+    fn test_power_set_is_exact() {
+        for n in 0..=10 {
+            let subsets =
+                PowerSet::<_, Vec<usize>>::new(&(0..n).collect::<Vec<_>>()).collect::<Vec<_>>();
+            assert_eq!(subsets.len(), 1 << n);
+            assert!(
+                subsets
+                    .windows(2)
+                    .all(|pair| pair[0].len() <= pair[1].len())
+            );
+            let distinct = subsets
+                .into_iter()
+                .map(|mut subset| {
+                    subset.sort_unstable();
+                    subset
+                })
+                .collect::<BTreeSet<_>>();
+            assert_eq!(distinct.len(), 1 << n);
+        }
+    }
+
+    #[test]
+    fn test_power_set_allocates_once_per_subset() {
+        let n = 12;
+        let set = (0..n).collect::<Vec<usize>>();
+        let (subsets, allocations) = counting::allocations(|| {
+            let mut subsets = Vec::with_capacity(1 << n);
+            subsets.extend(PowerSet::<_, Vec<_>>::new(&set));
+            subsets
+        });
+        assert_eq!(subsets.len(), 1 << n);
+        // One per non-empty subset, plus the result and the bookkeeping of each subset size.
+        assert!(allocations <= (1 << n) + 2 * (n + 1) + 2, "{allocations}");
+    }
+
+    #[test]
+    fn test_search_stops_early() {
+        // Everybody knows 0, who knows nobody, so the first subset of one person is the answer.
+        let party = (0..16)
+            .map(|id| Person::new(id, [0]))
+            .collect::<crate::Party>();
+        let everyone = party.ids();
+        let (css, allocations) = counting::allocations(|| party.cclique_in(&everyone));
+        assert_eq!(css.map(|css| css.ids()), Some(HashSet::from([0])));
+        assert!(allocations < 1000, "{allocations}");
+    }
+
+    #[test]
+    fn test_btree_and_hash_impls_agree() {
+        let hash = HashSet::from(crate::Party::demo());
+        let btree = hash.iter().cloned().collect::<BTreeSet<_>>();
+
+        assert_eq!(hash.is_clique(), btree.is_clique());
+        let css = btree.cclique().unwrap_or_default();
+        assert_eq!(css.iter().map(|p| p.id).collect::<Vec<_>>(), [1, 2, 3]);
+        assert_eq!(
+            hash.cclique().unwrap_or_default(),
+            css.into_iter().collect::<HashSet<_>>()
         );
     }
 
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Account {
+        handle: String,
+        follows: Vec<String>,
+    }
+
+    impl Knows for Account {
+        type Key = String;
+
+        fn key(&self) -> &String {
+            &self.handle
+        }
+
+        fn knows(&self, other: &Self) -> bool {
+            self.handle == other.handle || self.follows.contains(&other.handle)
+        }
+    }
+
+    #[test]
+    fn test_custom_knows() {
+        let account = |handle: &str, follows: &[&str]| Account {
+            handle: handle.to_owned(),
+            follows: follows.iter().map(|&f| f.to_owned()).collect(),
+        };
+        let party = HashSet::from([
+            account("ada", &["bob"]),
+            account("bob", &["ada"]),
+            account("cat", &["ada", "bob"]),
+        ]);
+        let mut handles = party
+            .cclique()
+            .unwrap_or_default()
+            .iter()
+            .map(|account| account.key().clone())
+            .collect::<Vec<_>>();
+        handles.sort_unstable();
+        assert_eq!(handles, ["ada", "bob"]);
+    }
+
+    #[test]
+    fn test_candidate_pruning() {
+        let party = crate::Party::demo();
+        let mut candidates = known_by_everyone(party.members())
+            .iter()
+            .map(|person| person.id)
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        assert_eq!(candidates, [1, 2, 3]);
+
+        // 1 is known by everyone but knows 3, who is not.
+        let party = crate::party! { 1 => [3]; 2 => [1]; 3 => [1] };
+        assert_eq!(known_by_everyone(party.members()).len(), 1);
+        assert_eq!(party.cclique(), None);
+
+        for party in sample_parties(300, 8, 0.6, 33).map(crate::Party::from) {
+            let unpruned = party.cclique_in(&party.ids());
+            assert_eq!(party.cclique(), unpruned, "{party:?}");
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_cclique_par() {
+        for party in sample_parties(300, 9, 0.6, 35).map(crate::Party::from) {
+            assert_eq!(party.cclique_par(), party.cclique(), "{party:?}");
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_is_cclique_par() {
+        let mut rng = crate::generator::Rng::new(59);
+        for party in sample_parties(300, 9, 0.6, 59).map(crate::Party::from) {
+            let candidates = known_by_everyone(party.members());
+            let candidates = crate::Party::from_members(candidates.into_iter().cloned());
+            let random = (party.iter())
+                .filter(|_| rng.chance(0.5))
+                .cloned()
+                .collect::<crate::Party>();
+            for subset in [candidates, random, crate::Party::new(), party.clone()] {
+                assert_eq!(
+                    subset.is_cclique_par(&party),
+                    subset.is_cclique(&party),
+                    "{subset:?} in {party:?}"
+                );
+            }
+        }
+
+        let n = 20_000;
+        let mut party = (0..n)
+            .map(|id| Person::new(id, [0, 1, (id + 1) % n]))
+            .chain([Person::new(0, [1]), Person::new(1, [0])])
+            .collect::<HashSet<_>>();
+        let css = crate::party! { 0 <=> 1 }.as_set().clone();
+        assert!(css.is_cclique_par(&party));
+        party.replace(Person::new(n / 2, [0]));
+        assert!(!css.is_cclique_par(&party));
+        assert!(!css.is_cclique(&party));
+    }
+
+    #[test]
+    fn test_cclique_elimination() {
+        let demo = crate::Party::demo();
+        assert_eq!(
+            demo.cclique_elimination().map(|css| css.ids()),
+            Some(HashSet::from([1, 2, 3]))
+        );
+        assert_eq!(crate::party! {}.cclique_elimination(), None);
+
+        // 1 and 2 are known by everyone, but 2 knows 3, so 2 and then 1 are dropped.
+        let cascade = crate::party! {
+            1 => [2];
+            2 => [1, 3];
+            3 => [1, 2];
+            4 => [1, 2];
+        };
+        assert_eq!(known_by_everyone(cascade.members()).len(), 2);
+        assert_eq!(cascade.cclique_elimination(), None);
+        assert_eq!(cascade.cclique(), None);
+
+        for party in sample_parties(300, 8, 0.7, 36).map(crate::Party::from) {
+            assert_eq!(party.cclique_elimination(), party.cclique(), "{party:?}");
+        }
+    }
+
+    #[test]
+    fn test_cclique_fast() {
+        let demo = crate::party! { 1 <=> 2; 3 => [1, 2] };
+        assert_eq!(
+            demo.cclique_fast(),
+            (demo.cclique(), SolveStrategy::Candidates)
+        );
+        let nobody = crate::party! { 1 => []; 2 => [] };
+        assert_eq!(nobody.cclique_fast(), (None, SolveStrategy::Candidates));
+        // 1 and 2 are known by everyone but are no celebrity clique, as 2 knows 3.
+        let cascade = crate::party! { 1 => [2]; 2 => [1, 3]; 3 => [1, 2]; 4 => [1, 2] };
+        assert_eq!(cascade.cclique_fast(), (None, SolveStrategy::Elimination));
+
+        for party in sample_parties(300, 9, 0.7, 58).map(crate::Party::from) {
+            let (css, _) = party.cclique_fast();
+            assert_eq!(css, party.cclique(), "{party:?}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::single_range_in_vec_init)]
+    fn test_cclique_fast_falls_back() {
+        /// A lookup that claims everybody knows everybody while the query count is in `lies`.
+        #[derive(Clone)]
+        struct Flaky<'a> {
+            id: usize,
+            known: Vec<usize>,
+            queries: &'a Cell<usize>,
+            lies: &'a [std::ops::Range<usize>],
+        }
+
+        impl PartialEq for Flaky<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
+            }
+        }
+
+        impl Knows for Flaky<'_> {
+            type Key = usize;
+
+            fn key(&self) -> &usize {
+                &self.id
+            }
+
+            fn knows(&self, other: &Self) -> bool {
+                let query = self.queries.get();
+                self.queries.set(query + 1);
+                self.lies.iter().any(|lies| lies.contains(&query))
+                    || self.id == other.id
+                    || self.known.contains(&other.id)
+            }
+        }
+
+        let solve = |lies: &[std::ops::Range<usize>]| {
+            let queries = Cell::new(0);
+            let party = [(1, vec![2]), (2, vec![1]), (3, vec![1, 2])]
+                .map(|(id, known)| Flaky {
+                    id,
+                    known,
+                    queries: &queries,
+                    lies,
+                })
+                .to_vec();
+            let (css, strategy) = party.cclique_fast();
+            let mut ids = css.map(|css| css.iter().map(|p| p.id).collect::<Vec<_>>());
+            if let Some(ids) = &mut ids {
+                ids.sort_unstable();
+            }
+            (ids, strategy)
+        };
+
+        assert_eq!(solve(&[]), (Some(vec![1, 2]), SolveStrategy::Candidates));
+        // Everybody looks like a candidate, but the truth comes out in the verification, and
+        // the elimination starts over from the real candidates.
+        assert_eq!(
+            solve(&[0..9]),
+            (Some(vec![1, 2]), SolveStrategy::Elimination)
+        );
+        // Lying through the elimination as well leaves only the exhaustive search to get it right.
+        let exhaustive = (9..40)
+            .map(|start| solve(&[0..9, start..start + 9]))
+            .filter(|(_, strategy)| *strategy == SolveStrategy::Exhaustive)
+            .collect::<Vec<_>>();
+        assert!(!exhaustive.is_empty());
+        assert!(exhaustive.iter().all(|(ids, _)| *ids == Some(vec![1, 2])));
+    }
+
+    #[test]
+    fn test_cclique_bounded() {
+        let demo = crate::Party::demo();
+        assert_eq!(
+            demo.cclique_bounded(3).map(|css| css.ids()),
+            Some(HashSet::from([1, 2, 3]))
+        );
+        assert_eq!(demo.cclique_bounded(2), None);
+        assert_eq!(demo.cclique_bounded(0), None);
+
+        let planted = crate::Party::from(crate::generator::planted_party(12, 5, 0.5, 54));
+        assert_eq!(planted.cclique_bounded(3), None);
+        assert_eq!(planted.cclique_bounded(5), planted.cclique());
+        assert_eq!(planted.cclique_bounded(12), planted.cclique());
+        assert!(planted.cclique().is_some_and(|css| css.len() == 5));
+    }
+
+    #[test]
+    fn test_cclique_gray() {
+        // Every subset of the six candidates is visited, and none of them is a celebrity clique.
+        let hopeless = (0..=6)
+            .map(|id| Person::new(id, (0..6).chain((id == 0).then_some(6))))
+            .collect::<HashSet<_>>();
+        assert_eq!(known_by_everyone(hopeless.members()).len(), 6);
+        assert_eq!(hopeless.cclique_gray(), None);
+
+        for party in sample_parties(300, 10, 0.7, 52).map(crate::Party::from) {
+            assert_eq!(
+                party.cclique_gray().map(|css| css.ids()),
+                party.cclique().map(|css| css.ids()),
+                "{party:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_celebrity() {
+        let singleton = crate::party! { 1 => []; 2 => [1, 3]; 3 => [1]; 4 => [1, 2] };
+        let celebrity = singleton.celebrity();
+        assert_eq!(celebrity.as_ref().map(|person| person.id), Some(1));
+        assert_eq!(
+            singleton.cclique().map(|css| css.ids()),
+            Some(HashSet::from([1]))
+        );
+
+        let demo = crate::Party::demo();
+        assert!(demo.cclique().is_some_and(|css| css.len() == 3));
+        assert_eq!(demo.celebrity(), None);
+
+        let strangers = crate::party! { 1 => []; 2 => [] };
+        assert_eq!(strangers.cclique(), None);
+        assert_eq!(strangers.celebrity(), None);
+        assert_eq!(crate::party! {}.celebrity(), None);
+    }
+
+    #[test]
+    fn test_celebrity_query_count() {
+        #[derive(Clone)]
+        struct Counted<'a> {
+            id: usize,
+            known: Vec<usize>,
+            queries: &'a Cell<usize>,
+        }
+
+        impl PartialEq for Counted<'_> {
+            fn eq(&self, other: &Self) -> bool {
+                self.id == other.id
+            }
+        }
+
+        impl Knows for Counted<'_> {
+            type Key = usize;
+
+            fn key(&self) -> &usize {
+                &self.id
+            }
+
+            fn knows(&self, other: &Self) -> bool {
+                self.queries.set(self.queries.get() + 1);
+                self.id == other.id || self.known.contains(&other.id)
+            }
+        }
+
+        let queries = Cell::new(0);
+        let n = 50;
+        let people = (0..n)
+            .map(|id| Counted {
+                id,
+                known: if id == 17 { vec![] } else { vec![17] },
+                queries: &queries,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(people.celebrity().map(|person| person.id), Some(17));
+        assert!(queries.get() <= 3 * n, "{} queries", queries.get());
+    }
+
+    #[test]
+    fn test_cliques_of_size() {
+        let party = HashSet::from(crate::Party::demo());
+        let ids = |clique: &HashSet<Person>| {
+            let mut ids = clique.iter().map(|p| p.id).collect::<Vec<_>>();
+            ids.sort_unstable();
+            ids
+        };
+
+        assert!(party.cliques_of_size(0).is_empty());
+        assert_eq!(party.cliques_of_size(1).len(), party.len());
+        assert!(party.cliques_of_size(party.len() + 1).is_empty());
+        let triangles = party.cliques_of_size(3);
+        assert!(triangles.iter().any(|clique| ids(clique) == [1, 2, 3]));
+        assert!(triangles.iter().all(Clique::is_clique));
+    }
+
+    #[test]
+    fn test_cclique_in() {
+        let party = crate::Party::demo();
+        let css = party.cclique();
+        assert_eq!(party.cclique_in(&HashSet::from([1, 2, 3])), css);
+        assert_eq!(party.cclique_in(&party.ids()), css);
+        assert_eq!(party.cclique_in(&HashSet::from([1, 2, 3, 99])), css);
+        assert_eq!(party.cclique_in(&HashSet::from([4, 5, 6, 7])), None);
+        assert_eq!(party.cclique_in(&HashSet::new()), None);
+    }
+
+    #[test]
+    fn test_cclique_in_with_limit() {
+        let party = crate::Party::demo();
+        let css = party.cclique();
+        let limited = |candidates: &[usize], limit| {
+            party.cclique_in_with_limit(&candidates.iter().copied().collect(), limit)
+        };
+        // Only 1, 2 and 3 are known by everybody, whatever else is listed.
+        assert_eq!(limited(&[1, 2, 3, 4, 5, 6, 7, 99], 8), Ok(css));
+        assert_eq!(limited(&[4, 5, 6, 7], 1), Ok(None));
+        assert_eq!(
+            limited(&[1, 2, 3], 7),
+            Err(SearchLimitExceeded {
+                party_size: 7,
+                limit: 7
+            })
+        );
+
+        // Everybody knows everybody, so pruning leaves all 40 candidates.
+        let party = (0..40)
+            .map(|id| Person::new(id, 0..40))
+            .collect::<crate::Party>();
+        let css = party.cclique_in_with_limit(&party.ids(), DEFAULT_SEARCH_LIMIT);
+        assert_eq!(
+            css,
+            Err(SearchLimitExceeded {
+                party_size: 40,
+                limit: DEFAULT_SEARCH_LIMIT
+            })
+        );
+    }
+
+    #[test]
+    fn test_searches_borrow_members() {
+        let party = crate::Party::from(crate::generator::planted_party(16, 4, 0.5, 60));
+        assert_eq!(party.len(), 16);
+        let ids = |cliques: &[crate::Party]| {
+            (cliques.iter())
+                .map(|clique| clique.sorted().iter().map(|p| p.id).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        // The old way: every subset is cloned into a party of its own before it is checked.
+        let (expected, cloned) = counting::allocations(|| {
+            Combinations::<_, Vec<_>>::new(party.iter(), 3)
+                .map(crate::Party::from_members)
+                .filter(crate::Party::is_clique)
+                .collect::<Vec<_>>()
+        });
+        let (cliques, borrowed) = counting::allocations(|| party.cliques_of_size(3));
+        assert_eq!(ids(&cliques), ids(&expected));
+        assert!(borrowed * 2 < cloned, "{borrowed} allocations vs {cloned}");
+
+        let keys = party.ids();
+        let (expected, cloned) = counting::allocations(|| {
+            let pool = party.iter().collect::<Vec<_>>();
+            PowerSet::<_, Vec<_>>::new(&pool)
+                .skip(1)
+                .map(|people| crate::Party::from_members(people.into_iter().cloned()))
+                .find(|people| people.is_cclique(&party))
+        });
+        let (css, borrowed) = counting::allocations(|| party.cclique_in(&keys));
+        assert_eq!(css.map(|css| css.ids()), expected.map(|css| css.ids()));
+        assert!(borrowed * 2 < cloned, "{borrowed} allocations vs {cloned}");
+
+        // Edges are still added in the iteration order of each person's acquaintances.
+        let (graph, allocations) = counting::allocations(|| clique2digraph(party.as_set()));
+        let edges = (graph.raw_edges().iter())
+            .map(|edge| {
+                (
+                    graph[edge.source()].0.clone(),
+                    graph[edge.target()].0.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let expected = (party.iter())
+            .flat_map(|person| {
+                let known = person.known_people.clone().into_iter();
+                known
+                    .filter(|id| party.iter().any(|other| other.id == *id))
+                    .map(|id| (person.label(), id.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(edges, expected);
+        // One label per person, and the growth of the node map and the graph's vectors.
+        assert!(allocations < 2 * party.len(), "{allocations}");
+    }
+
+    #[test]
+    fn test_cclique_partition() {
+        let party = crate::Party::demo();
+        let (css, rest) = party.cclique_partition().unwrap_or_default();
+        assert_eq!(css.ids(), HashSet::from([1, 2, 3]));
+        assert_eq!(rest.ids(), HashSet::from([4, 5, 6, 7]));
+        assert_eq!(
+            rest.get(4).map(|person| person.known_people.clone()),
+            Some(HashSet::from([1, 2, 3, 42]))
+        );
+
+        let cycle = crate::party! { 1 => [2]; 2 => [3]; 3 => [1] };
+        assert_eq!(cycle.cclique_partition(), None);
+    }
+
+    #[test]
+    fn test_try_cclique() {
+        let party = crate::party! { 1 <=> 2; 3 => [1, 2] };
+        assert_eq!(party.try_cclique(), Ok(party.cclique()));
+
+        // Strangers are not known by everybody, so there is nothing to search.
+        let strangers = (0..21)
+            .map(|id| Person::new(id, []))
+            .collect::<crate::Party>();
+        assert_eq!(strangers.try_cclique(), Ok(None));
+
+        let everybody = (0..21)
+            .map(|id| Person::new(id, (0..21).filter(|&other| other != id)))
+            .collect::<crate::Party>();
+        assert_eq!(
+            everybody.try_cclique(),
+            Err(CliqueError::SearchLimitExceeded {
+                party_size: 21,
+                limit: DEFAULT_SEARCH_LIMIT
+            })
+        );
+    }
+
+    #[test]
+    fn test_cclique_without_candidates() {
+        // Nobody is known by everybody, so no table of the whole party is built.
+        let party = (0..20_000)
+            .map(|id| Person::new(id, [id + 1]))
+            .collect::<crate::Party>();
+        assert_eq!(party.cclique(), None);
+        assert_eq!(party.cclique_with_limit(1), Ok(None));
+    }
+
+    #[test]
+    fn test_cclique_beyond_mask_capacity() {
+        let party = (0..200)
+            .map(|id| Person::new(id, [0, 1, 2]))
+            .collect::<crate::Party>();
+        assert_eq!(
+            party.cclique().map(|css| css.ids()),
+            Some(HashSet::from([0, 1, 2]))
+        );
+        assert_eq!(party.cclique_ref().map(|css| css.len()), Some(3));
+    }
+
+    #[test]
+    fn test_cclique_cancellable() {
+        use std::time::{Duration, Instant};
+
+        // 0 to 39 are known by everybody, but 0 also knows 40, so all 2^40 subsets would be searched.
+        let party = (0..=40)
+            .map(|id| Person::new(id, (0..40).chain((id == 0).then_some(40))))
+            .collect::<crate::Party>();
+        let token = CancelToken::new();
+        let (result, elapsed) = std::thread::scope(|scope| {
+            let search = scope.spawn(|| party.cclique_cancellable(&token));
+            std::thread::sleep(Duration::from_millis(50));
+            let cancelled_at = Instant::now();
+            token.cancel();
+            let result = search.join().expect("search thread");
+            (result, cancelled_at.elapsed())
+        });
+        assert_eq!(result, Err(Cancelled));
+        assert!(elapsed < Duration::from_secs(1), "{elapsed:?}");
+    }
+
+    #[test]
+    fn test_cclique_with_progress() {
+        // 0 to 9 are known by everybody, but 0 also knows 10, so every subset is inspected.
+        let party = (0..=10)
+            .map(|id| Person::new(id, (0..10).chain((id == 0).then_some(10))))
+            .collect::<crate::Party>();
+        let mut reports = Vec::new();
+        assert_eq!(
+            party.cclique_with_progress(100, |progress| reports.push(progress)),
+            None
+        );
+        assert_eq!(reports.len(), 1023 / 100 + 2);
+        assert!(
+            reports
+                .windows(2)
+                .all(|pair| pair[0].examined < pair[1].examined)
+        );
+        assert!(reports.iter().all(|progress| progress.total == 1023));
+        assert!(
+            reports
+                .iter()
+                .all(|progress| (1..=10).contains(&progress.size))
+        );
+        assert_eq!(reports.last().map(|progress| progress.examined), Some(1023));
+        assert_eq!(reports.last().map(SearchProgress::fraction), Some(1.0));
+
+        let mut reports = Vec::new();
+        let css = crate::party! {}.cclique_with_progress(0, |progress| reports.push(progress));
+        assert_eq!(css, None);
+        assert_eq!(
+            reports,
+            [SearchProgress {
+                examined: 0,
+                size: 0,
+                total: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_cclique_with_limit() {
+        // Everybody knows everybody, so nobody is pruned.
+        let everyone = |n: usize| {
+            crate::Party::from(
+                (0..n)
+                    .map(|id| Person::new(id, 0..n))
+                    .collect::<HashSet<_>>(),
+            )
+        };
+        assert_eq!(
+            everyone(5)
+                .cclique_with_limit(1 << 5)
+                .map(|css| css.map(|css| css.len())),
+            Ok(Some(5))
+        );
+        assert_eq!(
+            everyone(5).cclique_with_limit((1 << 5) - 1),
+            Err(SearchLimitExceeded {
+                party_size: 5,
+                limit: (1 << 5) - 1
+            })
+        );
+        assert_eq!(
+            everyone(21).cclique_with_limit(DEFAULT_SEARCH_LIMIT),
+            Err(SearchLimitExceeded {
+                party_size: 21,
+                limit: DEFAULT_SEARCH_LIMIT
+            })
+        );
+        assert!(everyone(100).cclique_with_limit(usize::MAX).is_err());
+
+        // Few candidates in a large party are fine.
+        let strangers = (0..100)
+            .map(|id| Person::new(id, []))
+            .collect::<HashSet<_>>();
+        assert_eq!(strangers.cclique_with_limit(DEFAULT_SEARCH_LIMIT), Ok(None));
+    }
+
+    #[test]
+    fn test_cclique_past_default_limit() {
+        let everyone = (0..21)
+            .map(|id| Person::new(id, 0..21))
+            .collect::<HashSet<_>>();
+        assert!(everyone.cclique_with_limit(DEFAULT_SEARCH_LIMIT).is_err());
+        // Without a limit, the search goes on until it finds them.
+        assert_eq!(everyone.cclique().map(|css| css.len()), Some(21));
+    }
+
+    #[test]
+    fn test_why_not_cclique() {
+        let subset = |party: &crate::Party, ids: &[usize]| {
+            crate::Party::from(
+                party
+                    .iter()
+                    .filter(|person| ids.contains(&person.id))
+                    .cloned()
+                    .collect::<HashSet<_>>(),
+            )
+        };
+
+        let party = crate::party! { 1 <=> 2; 3 => [1] };
+        assert_eq!(
+            subset(&party, &[1, 2]).why_not_cclique(&party),
+            Some(CcliqueViolation::NotKnownByEveryone {
+                guest: 3,
+                celebrity: 2
+            })
+        );
+        assert_eq!(
+            subset(&party, &[1]).why_not_cclique(&party),
+            Some(CcliqueViolation::KnowsOutsider {
+                celebrity: 1,
+                outsider: 2
+            })
+        );
+
+        let party = crate::party! { 1 => [2]; 2 => [1, 3]; 3 => [1, 2] };
+        assert_eq!(
+            subset(&party, &[1, 2]).why_not_cclique(&party),
+            Some(CcliqueViolation::KnowsOutsider {
+                celebrity: 2,
+                outsider: 3
+            })
+        );
+
+        let party = crate::party! { 1 <=> 2; 3 => [1, 2] };
+        assert_eq!(subset(&party, &[1, 2]).why_not_cclique(&party), None);
+    }
+
+    #[test]
+    fn test_cclique_violations() {
+        let party = crate::Party::demo();
+        let candidates = crate::Party::from(
+            party
+                .iter()
+                .filter(|person| person.id <= 2)
+                .cloned()
+                .collect::<HashSet<_>>(),
+        );
+
+        let mut violations = candidates.cclique_violations(&party, None);
+        violations.sort_unstable();
+        assert_eq!(
+            violations,
+            [
+                (3, 1, ViolationKind::KnowsOutsider),
+                (3, 2, ViolationKind::KnowsOutsider)
+            ]
+        );
+        assert_eq!(candidates.cclique_violations(&party, Some(1)).len(), 1);
+        assert!(
+            party
+                .cclique()
+                .is_some_and(|css| css.cclique_violations(&party, None).is_empty())
+        );
+    }
+
+    #[test]
+    fn test_max_clique() {
+        let mut party = crate::Party::demo();
+        party.absorb(crate::party! {
+            8 => [1, 2, 3, 9, 10, 11];
+            9 => [1, 2, 3, 8, 10, 11];
+            10 => [1, 2, 3, 8, 9, 11];
+            11 => [1, 2, 3, 8, 9, 10];
+        });
+        assert_eq!(
+            party.max_clique().map(|clique| clique.ids()),
+            Some(HashSet::from([8, 9, 10, 11]))
+        );
+        assert_eq!(
+            party.cclique().map(|css| css.ids()),
+            Some(HashSet::from([1, 2, 3]))
+        );
+
+        let strangers = crate::party! { 3 => [1]; 2 => []; 1 => [2] };
+        assert_eq!(
+            strangers.max_clique().map(|clique| clique.ids()),
+            Some(HashSet::from([1]))
+        );
+        assert_eq!(crate::Party::new().max_clique(), None);
+    }
+
+    #[test]
+    fn test_maximal_cliques() {
+        let party = crate::Party::demo();
+        let mut cliques = party
+            .maximal_cliques()
+            .iter()
+            .map(|clique| clique.sorted().iter().map(|p| p.id).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        cliques.sort_unstable();
+        assert_eq!(cliques, [vec![1, 2, 3], vec![4], vec![5], vec![6, 7]]);
+
+        let complete = crate::Party::from(
+            (1..=5_usize)
+                .map(|id| Person::new(id, 1..=5))
+                .collect::<HashSet<_>>(),
+        );
+        assert_eq!(complete.maximal_cliques(), std::slice::from_ref(&complete));
+        assert!(crate::Party::new().maximal_cliques().is_empty());
+    }
+
+    #[test]
+    fn test_clique_containing() {
+        let party = crate::Party::demo();
+        assert_eq!(
+            party.clique_containing(&7).map(|clique| clique.ids()),
+            Some(HashSet::from([6, 7]))
+        );
+        assert_eq!(
+            party.clique_containing(&2).map(|clique| clique.ids()),
+            Some(HashSet::from([1, 2, 3]))
+        );
+        assert_eq!(
+            party.clique_containing(&5).map(|clique| clique.ids()),
+            Some(HashSet::from([5]))
+        );
+        assert_eq!(party.clique_containing(&42), None);
+    }
+
+    #[test]
+    fn test_is_clique_maximal() {
+        let party = crate::Party::demo();
+        let subset = |ids: &[usize]| {
+            crate::Party::from(
+                party
+                    .iter()
+                    .filter(|person| ids.contains(&person.id))
+                    .cloned()
+                    .collect::<HashSet<_>>(),
+            )
+        };
+
+        assert!(subset(&[1, 2]).is_clique());
+        assert!(!subset(&[1, 2]).is_clique_maximal(&party));
+        assert!(subset(&[1, 2, 3]).is_clique_maximal(&party));
+        assert!(subset(&[6, 7]).is_clique_maximal(&party));
+        // 4 knows 42, but 42 is not at the party and cannot join
+        assert!(subset(&[4]).is_clique_maximal(&party));
+        assert!(!subset(&[1, 4]).is_clique_maximal(&party));
+    }
+
+    #[test]
+    fn test_k_subsets() {
+        let set = (0..5).collect::<HashSet<_>>();
+        for k in 0..=6 {
+            assert_eq!(k_subsets(&set, k).count(), binomial_approx(5, k));
+        }
+    }
+
+    #[test]
+    fn test_string_ids() {
+        let party = HashSet::from([
+            Person::new("ada".to_owned(), ["bob".to_owned()]),
+            Person::new("bob".to_owned(), ["ada".to_owned()]),
+            Person::new("cat".to_owned(), ["ada".to_owned(), "bob".to_owned()]),
+        ]);
+        let mut ids = party
+            .cclique()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|person| person.id)
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, ["ada", "bob"]);
+
+        let graph = clique2digraph(&party);
+        let dot = format!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
+        assert_eq!(graph.edge_count(), 4);
+        assert!(
+            ["ada", "bob", "cat"]
+                .iter()
+                .all(|id| dot.contains(&format!("label = \"{id}\"")))
+        );
+    }
+
+    #[test]
+    fn test_named_labels() {
+        let party = HashSet::from([
+            Person::named(1_usize, "Zoë", [2]),
+            Person::named(2, "Dwayne \"The Rock\" Johnson", [1]),
+            Person::from((3_usize, [1_usize])),
+        ]);
+        let graph = clique2digraph(&party);
+        let dot = format!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
+        assert!(dot.contains(r#"label = "Zoë (1)""#));
+        assert!(dot.contains(r#"label = "Dwayne \"The Rock\" Johnson (2)""#));
+        assert!(dot.contains(r#"label = "3""#));
+    }
+
+    #[test]
+    fn test_cclique_ref_allocates_less() {
+        let party = crate::Party::demo();
+        let (borrowed, by_ref) = counting::allocations(|| party.cclique_ref());
+        let (owned, by_clone) = counting::allocations(|| {
+            PowerSet::<_>::new(party.as_set())
+                .skip(1)
+                .find(|people| people.is_cclique(party.as_set()))
+        });
+
+        let mut ids = borrowed
+            .unwrap_or_default()
+            .iter()
+            .map(|p| p.id)
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, [1, 2, 3]);
+        assert_eq!(owned.map(|people| people.len()), Some(3));
+        assert!(by_ref < by_clone, "{by_ref} allocations vs {by_clone}");
+    }
+
+    /// Counts the allocations made by the current thread, and the bytes it holds, so that tests
+    /// running in parallel don't interfere.
+    pub(super) mod counting {
+        use std::alloc::{GlobalAlloc, Layout, System};
+        use std::cell::Cell;
+
+        struct Counting;
+
+        thread_local! {
+            static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+            static LIVE: Cell<isize> = const { Cell::new(0) };
+            static PEAK: Cell<isize> = const { Cell::new(0) };
+        }
+
+        fn track(bytes: isize) {
+            let live = LIVE.with(|live| {
+                live.set(live.get() + bytes);
+                live.get()
+            });
+            PEAK.with(|peak| peak.set(peak.get().max(live)));
+        }
+
+        #[global_allocator]
+        static ALLOCATOR: Counting = Counting;
+
+        unsafe impl GlobalAlloc for Counting {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                ALLOCATIONS.with(|count| count.set(count.get() + 1));
+                track(layout.size().cast_signed());
+                unsafe { System.alloc(layout) }
+            }
+
+            unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+                track(-layout.size().cast_signed());
+                unsafe { System.dealloc(ptr, layout) }
+            }
+        }
+
+        pub fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+            let before = ALLOCATIONS.with(Cell::get);
+            let result = f();
+            (result, ALLOCATIONS.with(Cell::get) - before)
+        }
+
+        /// The most bytes held at once while `f` runs, beyond those held when it started.
+        pub fn peak_bytes<R>(f: impl FnOnce() -> R) -> (R, usize) {
+            let before = LIVE.with(Cell::get);
+            PEAK.with(|peak| peak.set(before));
+            let result = f();
+            (result, (PEAK.with(Cell::get) - before).unsigned_abs())
+        }
+    }
+
+    #[test]
+    fn test_non_empty_subsets() {
+        assert_eq!(non_empty_subsets(0), 0);
+        assert_eq!(non_empty_subsets(10), 1023);
+        let bits = usize::BITS as usize;
+        assert_eq!(non_empty_subsets(bits - 1), usize::MAX >> 1);
+        // Too many to count: the sum of the binomials would overflow.
+        assert_eq!(non_empty_subsets(bits), usize::MAX);
+        assert_eq!(non_empty_subsets(200), usize::MAX);
+    }
+
     #[test]
     fn test_binomial_approx() {
         for n in 1..=3 {
@@ -146,4 +2041,68 @@ mod tests {
             }
         }
     }
+
+    mod properties {
+        use super::*;
+        use proptest::{collection::vec, prelude::*};
+
+        /// Who knows whom among guests `0..n`, as a matrix, and which guests form a planted
+        /// celebrity clique. Both shrink towards `false`, so counterexamples shrink towards
+        /// small parties with few acquaintances and no planted clique.
+        fn parties() -> impl Strategy<Value = HashSet<Person>> {
+            (0..=7_usize)
+                .prop_flat_map(|n| (vec(vec(any::<bool>(), n), n), vec(any::<bool>(), n)))
+                .prop_map(|(knows, planted)| {
+                    (0..knows.len())
+                        .map(|a| {
+                            let known = (0..knows.len())
+                                .filter(|&b| planted[b] || (!planted[a] && knows[a][b]));
+                            Person::new(a, known)
+                        })
+                        .collect()
+                })
+        }
+
+        /// A party together with a relabeling of its ids to arbitrary distinct ids.
+        fn relabeled_parties() -> impl Strategy<Value = (HashSet<Person>, Vec<usize>)> {
+            parties().prop_flat_map(|party| {
+                let ids = proptest::sample::subsequence((0..100).collect::<Vec<_>>(), party.len())
+                    .prop_shuffle();
+                (Just(party), ids)
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn cclique_is_a_clique(party in parties()) {
+                if let Some(css) = party.cclique() {
+                    prop_assert!(css.is_clique());
+                    prop_assert!(css.is_cclique(&party));
+                }
+            }
+
+            #[test]
+            fn cclique_is_unique(party in parties()) {
+                let css = party.cclique().unwrap_or_default();
+                for subset in PowerSet::<_>::new(&party).skip(1) {
+                    prop_assert_eq!(subset.is_cclique(&party), subset == css, "{:?}", subset);
+                }
+            }
+
+            #[test]
+            fn cclique_ignores_labels((party, ids) in relabeled_parties()) {
+                let relabel = |person: &Person| {
+                    Person::new(ids[person.id], person.known_people.iter().map(|&id| ids[id]))
+                };
+                let relabeled = party.iter().map(relabel).collect::<HashSet<_>>();
+                let id_sets = |css: Option<HashSet<Person>>| {
+                    css.map(|css| css.into_iter().map(|person| person.id).collect::<HashSet<_>>())
+                };
+                prop_assert_eq!(
+                    id_sets(relabeled.cclique()),
+                    id_sets(party.cclique().map(|css| css.iter().map(relabel).collect()))
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,119 @@
+use super::{Clique, Knows};
+use std::collections::{HashMap, HashSet};
+
+/// The celebrity clique queries for a party given as an adjacency map from each guest's id to
+/// the ids they know.
+///
+/// Ids that appear only among the acquaintances are not guests, exactly as with [`Person`](super::Person).
+///
+/// ```
+/// use celebrities::AdjacencyClique;
+/// use std::collections::{HashMap, HashSet};
+///
+/// let party = HashMap::from([
+///     (1, HashSet::from([2])),
+///     (2, HashSet::from([1])),
+///     (3, HashSet::from([1, 2])),
+/// ]);
+/// let css = party.cclique().unwrap_or_default();
+/// assert_eq!(css.keys().copied().collect::<HashSet<_>>(), HashSet::from([1, 2]));
+/// assert!(css.is_cclique(&party));
+/// ```
+pub trait AdjacencyClique: Sized {
+    #[must_use]
+    fn is_clique(&self) -> bool;
+
+    #[must_use]
+    fn is_cclique(&self, party: &Self) -> bool;
+
+    #[must_use]
+    fn cclique(&self) -> Option<Self>;
+}
+
+impl AdjacencyClique for HashMap<usize, HashSet<usize>> {
+    fn is_clique(&self) -> bool {
+        rows(self).is_clique()
+    }
+
+    fn is_cclique(&self, party: &Self) -> bool {
+        rows(self).is_cclique(&rows(party))
+    }
+
+    fn cclique(&self) -> Option<Self> {
+        rows(self).cclique().map(|css| {
+            css.into_iter()
+                .map(|row| (row.id, row.known_people.clone()))
+                .collect()
+        })
+    }
+}
+
+/// One guest of an adjacency map, borrowed.
+#[derive(Debug, Clone, Copy)]
+struct Row<'a> {
+    id: usize,
+    known_people: &'a HashSet<usize>,
+}
+
+impl PartialEq for Row<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Knows for Row<'_> {
+    type Key = usize;
+
+    fn key(&self) -> &usize {
+        &self.id
+    }
+
+    fn knows(&self, other: &Self) -> bool {
+        self == other || self.known_people.contains(&other.id)
+    }
+
+    fn acquaintances(&self) -> impl Iterator<Item = &usize> {
+        self.known_people.iter()
+    }
+}
+
+fn rows(map: &HashMap<usize, HashSet<usize>>) -> Vec<Row<'_>> {
+    map.iter()
+        .map(|(&id, known_people)| Row { id, known_people })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Party;
+
+    #[test]
+    fn test_agrees_with_party() {
+        let party = crate::Party::demo();
+        let map = party.to_adjacency();
+        assert_eq!(Party::from_adjacency(map.clone()), party);
+
+        let from_map = map.cclique().unwrap_or_default();
+        let from_party = crate::Clique::cclique(&party).unwrap_or_default();
+        assert_eq!(
+            from_map.keys().copied().collect::<HashSet<_>>(),
+            from_party.ids()
+        );
+        assert!(from_map.is_clique());
+        assert!(from_map.is_cclique(&map));
+    }
+
+    #[test]
+    fn test_self_loops_and_dangling_ids() {
+        let map = HashMap::from([(1, HashSet::from([1, 2, 9])), (2, HashSet::from([1]))]);
+        assert!(map.is_clique());
+        assert_eq!(map.cclique().map(|css| css.len()), Some(2));
+
+        let party = Party::from_adjacency(map);
+        assert_eq!(
+            party.get(1).map(|person| person.known_people.clone()),
+            Some(HashSet::from([2, 9]))
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use super::{Clique, DEFAULT_SEARCH_LIMIT, SearchLimitExceeded, person::Person};
+use std::collections::HashSet;
+
+/// The celebrity clique of every party, in the order the parties were given.
+///
+/// Each party is searched with [`Clique::cclique_with_limit`] and [`DEFAULT_SEARCH_LIMIT`], so a
+/// party that is too large to search fails on its own without holding up the rest.
+/// With the `rayon` feature, the parties are spread over rayon's thread pool.
+///
+/// ```
+/// use celebrities::{Person, clique::solve_batch};
+/// use std::collections::HashSet;
+///
+/// let solutions = solve_batch(vec![
+///     HashSet::from([(1_usize, vec![]), (2, vec![1])].map(Person::from)),
+///     HashSet::from([(1_usize, vec![2]), (2, vec![])].map(Person::from)),
+/// ]);
+/// let ids = solutions
+///     .into_iter()
+///     .map(|css| css.map(|css| css.map(|css| css.into_iter().map(|p| p.id).collect::<Vec<_>>())))
+///     .collect::<Vec<_>>();
+/// assert_eq!(ids, [Ok(Some(vec![1])), Ok(Some(vec![2]))]);
+/// ```
+#[must_use]
+pub fn solve_batch(
+    parties: Vec<HashSet<Person>>,
+) -> Vec<Result<Option<HashSet<Person>>, SearchLimitExceeded>> {
+    let solve = |party: HashSet<Person>| party.cclique_with_limit(DEFAULT_SEARCH_LIMIT);
+
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        parties.into_par_iter().map(solve).collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        parties.into_iter().map(solve).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::sample_parties;
+
+    #[test]
+    fn test_solve_batch() {
+        let too_large = (0..21)
+            .map(|id| Person::new(id, 0..21))
+            .collect::<HashSet<_>>();
+        let parties = sample_parties(1_000, 12, 0.6, 50)
+            .enumerate()
+            .map(|(i, party)| if i == 500 { too_large.clone() } else { party })
+            .collect::<Vec<_>>();
+        let ids = |css: HashSet<Person>| css.iter().map(|person| person.id).collect::<HashSet<_>>();
+        let serial = parties
+            .iter()
+            .map(|party| {
+                party
+                    .cclique_with_limit(DEFAULT_SEARCH_LIMIT)
+                    .map(|css| css.map(ids))
+            })
+            .collect::<Vec<_>>();
+        let batch = solve_batch(parties)
+            .into_iter()
+            .map(|css| css.map(|css| css.map(ids)))
+            .collect::<Vec<_>>();
+        assert_eq!(batch, serial);
+        assert!(batch[500].is_err());
+        assert!(
+            batch
+                .iter()
+                .filter(|css| matches!(css, Ok(Some(_))))
+                .count()
+                > 400
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+/// How many subsets a search inspects between two looks at its [`CancelToken`].
+pub const CANCEL_CHECK_INTERVAL: usize = 1 << 12;
+
+/// Asks a running search to stop. Clones share the same flag, so one can be handed to another thread.
+///
+/// ```
+/// use celebrities::{Clique, clique::{CancelToken, Cancelled}, party};
+///
+/// let token = CancelToken::new();
+/// let party = party! { 1 <=> 2; 3 => [1, 2] };
+/// assert_eq!(party.cclique_cancellable(&token), Ok(party.cclique()));
+///
+/// token.clone().cancel();
+/// assert!(token.is_cancelled());
+/// assert_eq!(party.cclique_cancellable(&token), Err(Cancelled));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// A search was stopped through its [`CancelToken`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl Display for Cancelled {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "the search was cancelled")
+    }
+}
+
+impl Error for Cancelled {}
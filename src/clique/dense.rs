@@ -0,0 +1,143 @@
+use super::person::Person;
+use fixedbitset::FixedBitSet;
+use std::collections::HashSet;
+
+/// A party stored as a bit matrix, for fast checks on large parties.
+///
+/// Ids are compacted to `0..n` in ascending order, and row `a` has bit `b` set when guest `a` knows
+/// guest `b`. Everybody knows themselves; acquaintances who are not at the party are dropped.
+/// Groups of guests are bit sets over the same indices, built with [`subset`](DenseParty::subset).
+///
+/// ```
+/// use celebrities::party;
+///
+/// let dense = party! { 1 <=> 2; 3 => [1, 2] }.to_dense();
+/// let css = dense.subset([1, 2]).unwrap_or_default();
+/// assert!(dense.is_clique(&css));
+/// assert!(dense.is_cclique(&css));
+/// assert!(!dense.is_cclique(&dense.subset([1]).unwrap_or_default()));
+/// assert_eq!(dense.subset([1, 42]), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DenseParty {
+    ids: Vec<usize>,
+    rows: Vec<FixedBitSet>,
+}
+
+impl DenseParty {
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// The id of every guest; the position of an id is its index.
+    #[must_use]
+    pub fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    #[must_use]
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    /// The group of guests with the given ids, or `None` if one of them is not at the party.
+    #[must_use]
+    pub fn subset<I: IntoIterator<Item = usize>>(&self, ids: I) -> Option<FixedBitSet> {
+        let mut set = FixedBitSet::with_capacity(self.len());
+        for id in ids {
+            set.insert(self.index_of(id)?);
+        }
+        Some(set)
+    }
+
+    /// The ids of a group of guests.
+    #[must_use]
+    pub fn ids_of(&self, set: &FixedBitSet) -> HashSet<usize> {
+        set.ones().map(|index| self.ids[index]).collect()
+    }
+
+    /// Whether everybody in `set` knows everybody else in it.
+    #[must_use]
+    pub fn is_clique(&self, set: &FixedBitSet) -> bool {
+        set.ones().all(|a| set.is_subset(&self.rows[a]))
+    }
+
+    /// Whether `set` is known by every guest and knows nobody outside itself.
+    #[must_use]
+    pub fn is_cclique(&self, set: &FixedBitSet) -> bool {
+        self.rows.iter().all(|row| set.is_subset(row))
+            && set.ones().all(|c| self.rows[c].is_subset(set))
+    }
+}
+
+impl From<&HashSet<Person>> for DenseParty {
+    fn from(people: &HashSet<Person>) -> Self {
+        let mut people = people.iter().collect::<Vec<_>>();
+        people.sort_unstable();
+        let mut dense = Self {
+            ids: people.iter().map(|person| person.id).collect(),
+            rows: Vec::with_capacity(people.len()),
+        };
+        for (index, person) in people.into_iter().enumerate() {
+            let mut row = FixedBitSet::with_capacity(dense.len());
+            row.extend(
+                person
+                    .known_people
+                    .iter()
+                    .filter_map(|&known| dense.index_of(known)),
+            );
+            row.insert(index);
+            dense.rows.push(row);
+        }
+        dense
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Clique, Party,
+        generator::{Rng, sample_parties},
+    };
+
+    #[test]
+    fn test_layout() {
+        let dense = crate::party! { 7 => [3, 42]; 3 => [] }.to_dense();
+        assert_eq!(dense.ids(), [3, 7]);
+        assert_eq!(dense.rows[0].ones().collect::<Vec<_>>(), [0]);
+        assert_eq!(dense.rows[1].ones().collect::<Vec<_>>(), [0, 1]);
+        assert!(DenseParty::default().is_empty());
+    }
+
+    #[test]
+    fn test_agrees_with_hash_sets() {
+        let mut rng = Rng::new(34);
+        for party in sample_parties(200, 11, 0.7, 34).map(Party::from) {
+            let dense = party.to_dense();
+            for _ in 0..20 {
+                let group = party
+                    .iter()
+                    .filter(|_| rng.chance(0.5))
+                    .cloned()
+                    .collect::<HashSet<_>>();
+                let set = dense
+                    .subset(group.iter().map(|person| person.id))
+                    .unwrap_or_default();
+                assert_eq!(dense.ids_of(&set), group.iter().map(|p| p.id).collect());
+                assert_eq!(dense.is_clique(&set), group.is_clique(), "{party:?}");
+                assert_eq!(
+                    dense.is_cclique(&set),
+                    group.is_cclique(party.as_set()),
+                    "{party:?}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+/// The largest number of subsets [`Clique::try_cclique`](super::Clique::try_cclique) is willing to inspect.
+pub const DEFAULT_SEARCH_LIMIT: usize = 1 << 20;
+
+/// Why a celebrity clique search could not give a trustworthy answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CliqueError<K = usize> {
+    /// Nobody is at the party.
+    EmptyParty,
+    /// Somebody claims to know people who are not at the party. The ids are listed once each, in no particular order.
+    InconsistentData { missing_ids: Vec<K> },
+    /// The exhaustive search would have to inspect more than `limit` subsets.
+    SearchLimitExceeded { party_size: usize, limit: usize },
+}
+
+impl<K: Debug> Display for CliqueError<K> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyParty => write!(f, "the party is empty; add at least one person"),
+            Self::InconsistentData { missing_ids } => write!(
+                f,
+                "people not at the party are referenced as acquaintances: {missing_ids:?}; add them or remove the references"
+            ),
+            &Self::SearchLimitExceeded { party_size, limit } => {
+                Display::fmt(&SearchLimitExceeded { party_size, limit }, f)
+            }
+        }
+    }
+}
+
+impl<K: Debug> Error for CliqueError<K> {}
+
+/// A search would have to inspect more than `limit` subsets of a party of `party_size` people.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchLimitExceeded {
+    pub party_size: usize,
+    pub limit: usize,
+}
+
+impl Display for SearchLimitExceeded {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Self { party_size, limit } = self;
+        write!(
+            f,
+            "a party of {party_size} people has more than {limit} subsets to search; use a faster solver"
+        )
+    }
+}
+
+impl Error for SearchLimitExceeded {}
+
+impl<K> From<SearchLimitExceeded> for CliqueError<K> {
+    fn from(SearchLimitExceeded { party_size, limit }: SearchLimitExceeded) -> Self {
+        Self::SearchLimitExceeded { party_size, limit }
+    }
+}
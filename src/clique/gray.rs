@@ -0,0 +1,195 @@
+use super::Knows;
+
+/// Searches the subsets of some candidates in Gray-code order, so that each subset differs from
+/// the previous one by a single person and the celebrity clique conditions are updated in `O(n)`
+/// rather than checked from scratch.
+///
+/// Candidates are numbered by their index among the candidates, and guests by their position in
+/// the guest list the search was built from. Only the candidates are looked up in advance.
+pub(super) struct GraySearch<'a, T> {
+    guests: Vec<&'a T>,
+    /// The position of each candidate.
+    candidates: Vec<usize>,
+    /// Who each candidate knows among the guests, themselves excluded.
+    known: Vec<Vec<usize>>,
+    /// Which candidates know each candidate, themselves excluded.
+    known_by: Vec<Vec<usize>>,
+    /// Which guests do not know each candidate.
+    strangers: Vec<Vec<usize>>,
+    /// Whether each guest is in the subset, which only ever holds candidates.
+    in_subset: Vec<bool>,
+    /// How many people in the subset each guest does not know.
+    unknown: Vec<usize>,
+    /// How many guests do not know somebody in the subset.
+    unaware: usize,
+    /// How many acquaintances the people in the subset have outside it.
+    leaks: usize,
+}
+
+impl<'a, T: Knows> GraySearch<'a, T> {
+    /// A search over the subsets of the `candidates` positions, starting from the empty one, or
+    /// `None` when there are no candidates and so nothing to search.
+    pub fn new(guests: Vec<&'a T>, candidates: Vec<usize>) -> Option<Self> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let n = guests.len();
+        let mut known = Vec::with_capacity(candidates.len());
+        let mut known_by = Vec::with_capacity(candidates.len());
+        let mut strangers = Vec::with_capacity(candidates.len());
+        for &c in &candidates {
+            let candidate = guests[c];
+            known.push(
+                (0..n)
+                    .filter(|&b| b != c && candidate.knows(guests[b]))
+                    .collect(),
+            );
+            known_by.push(
+                (0..candidates.len())
+                    .filter(|&k| candidates[k] != c && guests[candidates[k]].knows(candidate))
+                    .collect(),
+            );
+            strangers.push((0..n).filter(|&a| !guests[a].knows(candidate)).collect());
+        }
+        Some(Self {
+            guests,
+            candidates,
+            known,
+            known_by,
+            strangers,
+            in_subset: vec![false; n],
+            unknown: vec![0; n],
+            unaware: 0,
+            leaks: 0,
+        })
+    }
+
+    /// Adds the candidate `k` to the subset, or removes them if they are already in it.
+    fn toggle(&mut self, k: usize) {
+        let c = self.candidates[k];
+        let joining = !self.in_subset[c];
+        self.in_subset[c] = joining;
+        for &stranger in &self.strangers[k] {
+            if joining {
+                self.unknown[stranger] += 1;
+                if self.unknown[stranger] == 1 {
+                    self.unaware += 1;
+                }
+            } else {
+                self.unknown[stranger] -= 1;
+                if self.unknown[stranger] == 0 {
+                    self.unaware -= 1;
+                }
+            }
+        }
+        let own = self.known[k]
+            .iter()
+            .filter(|&&b| !self.in_subset[b])
+            .count();
+        let members_knowing = self.known_by[k]
+            .iter()
+            .filter(|&&a| self.in_subset[self.candidates[a]])
+            .count();
+        if joining {
+            self.leaks = self.leaks + own - members_knowing;
+        } else {
+            self.leaks = self.leaks + members_knowing - own;
+        }
+        #[cfg(debug_assertions)]
+        self.check();
+    }
+
+    /// Recomputes the counters from scratch and compares them with the incremental ones.
+    #[cfg(debug_assertions)]
+    fn check(&self) {
+        let in_subset = |b: &usize| self.in_subset[*b];
+        let unknown = (0..self.guests.len())
+            .map(|a| {
+                (0..self.guests.len())
+                    .filter(in_subset)
+                    .filter(|&b| !self.guests[a].knows(self.guests[b]))
+                    .count()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(self.unknown, unknown);
+        assert_eq!(self.unaware, unknown.iter().filter(|&&n| n > 0).count());
+        let leaks = (0..self.candidates.len())
+            .filter(|&k| in_subset(&self.candidates[k]))
+            .map(|k| self.known[k].iter().filter(|b| !in_subset(b)).count())
+            .sum::<usize>();
+        assert_eq!(self.leaks, leaks);
+    }
+
+    /// The positions of the non-empty celebrity clique among the subsets of the candidates.
+    ///
+    /// There must be fewer than 64 candidates, and the search must not have been run before.
+    pub fn first_cclique(&mut self) -> Option<Vec<usize>> {
+        debug_assert!(self.candidates.len() < 64);
+        for step in 1..1_u64 << self.candidates.len() {
+            self.toggle(step.trailing_zeros() as usize);
+            if self.unaware == 0 && self.leaks == 0 {
+                return Some(
+                    (0..self.guests.len())
+                        .filter(|&c| self.in_subset[c])
+                        .collect(),
+                );
+            }
+        }
+        None
+    }
+
+    /// The guests at `positions`.
+    pub fn members(&self, positions: &[usize]) -> Vec<&'a T> {
+        positions
+            .iter()
+            .map(|&position| self.guests[position])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Person, clique::is_cclique_among, generator::Rng};
+
+    #[test]
+    fn test_counters_follow_toggles() {
+        let mut rng = Rng::new(52);
+        for n in 1..10 {
+            let people = crate::generator::random_party(n, 0.6, rng.next_u64())
+                .into_iter()
+                .collect::<Vec<Person>>();
+            let guests = people.iter().collect::<Vec<_>>();
+            let mut search = GraySearch::new(guests.clone(), (0..n).collect()).expect("guests");
+            for _ in 0..50 {
+                // `toggle` checks the counters against recomputation in debug builds. Every guest
+                // is a candidate, at their own index.
+                search.toggle(rng.below(n));
+                let subset = (0..n).filter(|&c| search.in_subset[c]).collect::<Vec<_>>();
+                let members = search.members(&subset);
+                assert_eq!(
+                    search.unaware == 0 && search.leaks == 0 && !members.is_empty(),
+                    !members.is_empty() && is_cclique_among(&members, guests.iter().copied()),
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_some_candidates() {
+        let party = crate::generator::planted_party(40, 3, 0.5, 52);
+        let guests = party.iter().collect::<Vec<_>>();
+        assert!(GraySearch::new(guests.clone(), Vec::new()).is_none());
+        let candidates = (0..guests.len())
+            .filter(|&c| guests.iter().all(|guest| guest.knows(guests[c])))
+            .collect::<Vec<_>>();
+        let mut search = GraySearch::new(guests.clone(), candidates.clone()).expect("a clique");
+        assert_eq!(search.known.len(), candidates.len());
+        let css = search.first_cclique().expect("the planted clique");
+        assert_eq!(css.len(), 3);
+        assert!(is_cclique_among(
+            &search.members(&css),
+            guests.iter().copied()
+        ));
+    }
+}
@@ -0,0 +1,123 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Why a name could not be interned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InternError {
+    /// Names must not be empty.
+    EmptyName,
+    /// Two records describe the person with this name.
+    DuplicateName(String),
+    /// There are more names than `u32` symbols.
+    TooManyNames,
+}
+
+impl Display for InternError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::EmptyName => write!(f, "a name is empty; every person needs a name"),
+            Self::DuplicateName(name) => write!(f, "{name} is described more than once"),
+            Self::TooManyNames => write!(f, "there are more than {} names", u32::MAX),
+        }
+    }
+}
+
+impl Error for InternError {}
+
+/// Maps names to dense `u32` symbols, `0` for the first name interned, and back.
+///
+/// Each name is stored once, so people can be keyed by cheap integer ids and the names looked up
+/// again when results are printed.
+///
+/// ```
+/// use celebrities::clique::Interner;
+///
+/// let mut names = Interner::new();
+/// let alice = names.intern("alice")?;
+/// assert_eq!(names.intern("bob")?, 1);
+/// assert_eq!(names.intern("alice")?, alice);
+/// assert_eq!(names.resolve(alice), Some("alice"));
+/// assert_eq!(names.get("carol"), None);
+/// # Ok::<(), celebrities::clique::InternError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Interner {
+    symbols: HashMap<String, u32>,
+    names: Vec<String>,
+}
+
+impl Interner {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The symbol of `name`, which is given the next free one if it is new.
+    ///
+    /// # Errors
+    ///
+    /// Fails if `name` is empty, or if every symbol is taken.
+    pub fn intern(&mut self, name: &str) -> Result<u32, InternError> {
+        if name.is_empty() {
+            return Err(InternError::EmptyName);
+        }
+        if let Some(&symbol) = self.symbols.get(name) {
+            return Ok(symbol);
+        }
+        let symbol = u32::try_from(self.names.len()).map_err(|_| InternError::TooManyNames)?;
+        self.symbols.insert(name.to_owned(), symbol);
+        self.names.push(name.to_owned());
+        Ok(symbol)
+    }
+
+    /// The symbol of `name`, if it has been interned.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<u32> {
+        self.symbols.get(name).copied()
+    }
+
+    /// The name of `symbol`, if it was handed out by this interner.
+    #[must_use]
+    pub fn resolve(&self, symbol: u32) -> Option<&str> {
+        let position = usize::try_from(symbol).ok()?;
+        self.names.get(position).map(String::as_str)
+    }
+
+    /// The name of the person with id `id`, for people built by [`Party::intern`](crate::Party::intern).
+    #[must_use]
+    pub fn resolve_id(&self, id: usize) -> Option<&str> {
+        self.resolve(u32::try_from(id).ok()?)
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbols_are_dense() {
+        let mut names = Interner::new();
+        for (i, name) in ["a", "b", "a", "c", "b"].iter().enumerate() {
+            let symbol = names.intern(name).expect("not empty");
+            assert_eq!(names.resolve(symbol), Some(*name), "{i}");
+        }
+        assert_eq!(names.len(), 3);
+        assert_eq!(names.get("c"), Some(2));
+        assert_eq!(names.resolve(3), None);
+        assert_eq!(names.intern(""), Err(InternError::EmptyName));
+        assert_eq!(names.len(), 3);
+    }
+}
@@ -0,0 +1,116 @@
+use super::Knows;
+use std::collections::HashMap;
+
+/// A party of at most 128 guests as a table of bit masks over their positions, so that groups of
+/// guests are plain `u128`s rather than collections.
+///
+/// Row `a` has bit `b` set when guest `a` knows guest `b`, themselves included.
+pub(super) struct MaskTable<'a, T> {
+    guests: Vec<&'a T>,
+    rows: Vec<u128>,
+}
+
+impl<'a, T: Knows> MaskTable<'a, T> {
+    pub const CAPACITY: usize = u128::BITS as usize;
+
+    /// The table for `guests`, or `None` if there are too many of them.
+    pub fn new(guests: Vec<&'a T>) -> Option<Self> {
+        if guests.len() > Self::CAPACITY {
+            return None;
+        }
+        let rows = guests
+            .iter()
+            .map(|a| {
+                (guests.iter().enumerate())
+                    .filter(|(_, b)| a.knows(b))
+                    .fold(0, |row, (b, _)| row | 1 << b)
+            })
+            .collect();
+        Some(Self { guests, rows })
+    }
+
+    /// The mask of `members`; those who are not guests are left out.
+    pub fn mask_of(&self, members: &[&T]) -> u128 {
+        let positions = (self.guests.iter().enumerate())
+            .map(|(position, guest)| (guest.key(), position))
+            .collect::<HashMap<_, _>>();
+        members
+            .iter()
+            .filter_map(|member| positions.get(member.key()))
+            .fold(0, |mask, position| mask | 1 << position)
+    }
+
+    /// The guests in `mask`.
+    pub fn members(&self, mask: u128) -> Vec<&'a T> {
+        (self.guests.iter().enumerate())
+            .filter(|(position, _)| mask >> position & 1 == 1)
+            .map(|(_, &guest)| guest)
+            .collect()
+    }
+
+    /// Whether every guest knows everybody in `mask`, and nobody in `mask` knows anybody outside it.
+    pub fn is_cclique(&self, mask: u128) -> bool {
+        self.rows.iter().all(|&row| row & mask == mask)
+            && (self.rows.iter().enumerate())
+                .filter(|(position, _)| mask >> position & 1 == 1)
+                .all(|(_, &row)| row & !mask == 0)
+    }
+
+    /// The non-empty celebrity clique among the subsets of `candidates`.
+    ///
+    /// Subsets are visited in decreasing numeric order, starting from `candidates` itself.
+    /// `step` is called with the size of each one first, and the search stops with its error if it fails.
+    pub fn first_cclique<E>(
+        &self,
+        candidates: u128,
+        mut step: impl FnMut(usize) -> Result<(), E>,
+    ) -> Result<Option<u128>, E> {
+        let mut mask = candidates;
+        while mask != 0 {
+            step(mask.count_ones() as usize)?;
+            if self.is_cclique(mask) {
+                return Ok(Some(mask));
+            }
+            mask = (mask - 1) & candidates;
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clique, Party, clique::PowerSet, generator::sample_parties};
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_capacity() {
+        let people = (0..=128)
+            .map(|id| crate::Person::new(id, []))
+            .collect::<Vec<_>>();
+        assert!(MaskTable::new(people.iter().take(128).collect()).is_some());
+        assert!(MaskTable::new(people.iter().collect()).is_none());
+    }
+
+    #[test]
+    fn test_agrees_with_sets() {
+        for party in sample_parties(300, 9, 0.7, 42).map(Party::from) {
+            let guests = party.members().collect::<Vec<_>>();
+            let table = MaskTable::new(guests.clone()).expect("at most 9 guests");
+            let everyone = table.mask_of(&guests);
+            let Ok(with_masks) = table.first_cclique(everyone, |_| Ok::<_, Infallible>(()));
+            let with_masks = with_masks.map(|mask| {
+                table
+                    .members(mask)
+                    .into_iter()
+                    .cloned()
+                    .collect::<crate::Party>()
+            });
+            let with_sets = PowerSet::<_>::new(party.as_set())
+                .skip(1)
+                .find(|css| css.is_cclique(party.as_set()))
+                .map(crate::Party::from);
+            assert_eq!(with_masks, with_sets, "{party:?}");
+        }
+    }
+}
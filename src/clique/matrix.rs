@@ -0,0 +1,218 @@
+use super::{Knows, party::Party};
+use fixedbitset::FixedBitSet;
+
+/// Who knows whom in a party, as one bit per ordered pair, built once and queried by id.
+///
+/// Ids are compacted to `0..n` in ascending order. Unlike [`DenseParty`](super::DenseParty), the ids
+/// that only appear among somebody's acquaintances are kept, so that `knows` answers exactly as
+/// [`Person::knows`](super::Person::knows) would for them: they are known, but know nobody.
+/// Everybody knows themselves, even ids the matrix has never seen.
+///
+/// ```
+/// use celebrities::{clique::KnowsMatrix, party};
+///
+/// let matrix = KnowsMatrix::from_party(&party! { 1 <=> 2; 3 => [1, 42] });
+/// assert!(matrix.knows(3, 42));
+/// assert!(!matrix.knows(42, 3));
+/// assert!(matrix.knows(7, 7));
+/// assert_eq!(matrix.row(3).collect::<Vec<_>>(), [1, 42]);
+/// assert_eq!(matrix.column(1).collect::<Vec<_>>(), [2, 3]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnowsMatrix {
+    ids: Vec<usize>,
+    bits: FixedBitSet,
+}
+
+impl KnowsMatrix {
+    #[must_use]
+    pub fn from_party(party: &Party) -> Self {
+        let mut ids = party
+            .iter()
+            .flat_map(|person| person.known_iter().chain([person.id]))
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+        let n = ids.len();
+        let mut bits = FixedBitSet::with_capacity(n * n);
+        let index_of = |id| ids.partition_point(|&other| other < id);
+        for person in party {
+            let a = index_of(person.id);
+            for b in person.known_iter().chain([person.id]).map(index_of) {
+                bits.insert(a * n + b);
+            }
+        }
+        Self { ids, bits }
+    }
+
+    /// How many ids the matrix covers, guests and acquaintances alike.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Every id the matrix covers, in ascending order; the position of an id is its index.
+    #[must_use]
+    pub fn ids(&self) -> &[usize] {
+        &self.ids
+    }
+
+    #[must_use]
+    pub fn index_of(&self, id: usize) -> Option<usize> {
+        self.ids.binary_search(&id).ok()
+    }
+
+    fn knows_at(&self, a: usize, b: usize) -> bool {
+        self.bits.contains(a * self.len() + b)
+    }
+
+    /// The indices of the ids `id` knows, not counting themselves.
+    fn row_indices(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        let a = self.index_of(id);
+        a.into_iter()
+            .flat_map(move |a| (0..self.len()).filter(move |&b| b != a && self.knows_at(a, b)))
+    }
+
+    /// Whether `a` knows `b`, which is always the case when they are the same id.
+    #[must_use]
+    pub fn knows(&self, a: usize, b: usize) -> bool {
+        a == b
+            || self
+                .index_of(a)
+                .zip(self.index_of(b))
+                .is_some_and(|(a, b)| self.knows_at(a, b))
+    }
+
+    /// The ids `id` knows, in ascending order, not counting themselves.
+    pub fn row(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row_indices(id).map(|b| self.ids[b])
+    }
+
+    /// The ids who know `id`, in ascending order, not counting themselves.
+    pub fn column(&self, id: usize) -> impl Iterator<Item = usize> + '_ {
+        let b = self.index_of(id);
+        b.into_iter().flat_map(move |b| {
+            (0..self.len())
+                .filter(move |&a| a != b && self.knows_at(a, b))
+                .map(|a| self.ids[a])
+        })
+    }
+}
+
+/// A guest whose acquaintances are looked up in a [`KnowsMatrix`].
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Entry<'a> {
+    pub id: usize,
+    matrix: &'a KnowsMatrix,
+}
+
+impl<'a> Entry<'a> {
+    pub const fn new(id: usize, matrix: &'a KnowsMatrix) -> Self {
+        Self { id, matrix }
+    }
+}
+
+impl PartialEq for Entry<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Knows for Entry<'_> {
+    type Key = usize;
+
+    fn key(&self) -> &usize {
+        &self.id
+    }
+
+    fn knows(&self, other: &Self) -> bool {
+        self.matrix.knows(self.id, other.id)
+    }
+
+    fn acquaintances(&self) -> impl Iterator<Item = &usize> {
+        let matrix = self.matrix;
+        matrix.row_indices(self.id).map(|b| &matrix.ids[b])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Person,
+        generator::{Rng, sample_parties},
+    };
+
+    /// Sample parties where some guests also know a few people who never showed up.
+    fn parties() -> impl Iterator<Item = Party> {
+        let mut rng = Rng::new(56);
+        sample_parties(200, 9, 0.5, 56).map(move |party| {
+            party
+                .into_iter()
+                .map(|person| {
+                    let absent = (rng.chance(0.3)).then(|| 100 + rng.below(3));
+                    Person::new(person.id, person.known_iter().chain(absent))
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_agrees_with_person_knows() {
+        for party in parties() {
+            let matrix = KnowsMatrix::from_party(&party);
+            let people = matrix
+                .ids()
+                .iter()
+                .map(|&id| {
+                    party
+                        .get(id)
+                        .cloned()
+                        .unwrap_or_else(|| Person::new(id, []))
+                })
+                .collect::<Vec<_>>();
+            for a in &people {
+                for b in &people {
+                    assert_eq!(matrix.knows(a.id, b.id), a.knows(b), "{a:?} {b:?}");
+                }
+                let mut known = a.known_iter().collect::<Vec<_>>();
+                known.sort_unstable();
+                assert_eq!(matrix.row(a.id).collect::<Vec<_>>(), known);
+                let known_by = (people.iter())
+                    .filter(|b| b.id != a.id && b.knows(a))
+                    .map(|b| b.id)
+                    .collect::<Vec<_>>();
+                assert_eq!(matrix.column(a.id).collect::<Vec<_>>(), known_by);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unknown_ids() {
+        let matrix = KnowsMatrix::from_party(&crate::party! { 1 => [2] });
+        assert_eq!(matrix.ids(), [1, 2]);
+        assert!(matrix.knows(5, 5));
+        assert!(!matrix.knows(1, 5));
+        assert_eq!(matrix.row(5).count(), 0);
+        assert!(KnowsMatrix::from_party(&Party::new()).is_empty());
+    }
+
+    #[test]
+    fn test_cclique_with_matrix() {
+        use crate::Clique;
+
+        for party in parties() {
+            let matrix = KnowsMatrix::from_party(&party);
+            assert_eq!(
+                party.cclique_with_matrix(&matrix).map(|css| css.ids()),
+                party.cclique().map(|css| css.ids()),
+                "{party:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use super::Knows;
+use std::collections::BTreeSet;
+
+/// The symmetric "both know each other" relation over positions `0..n` of a member list.
+pub(super) struct MutualGraph {
+    neighbors: Vec<BTreeSet<usize>>,
+}
+
+impl MutualGraph {
+    pub(super) fn new<T: Knows>(members: &[&T]) -> Self {
+        let neighbors = members
+            .iter()
+            .enumerate()
+            .map(|(i, member)| {
+                members
+                    .iter()
+                    .enumerate()
+                    .filter(|&(j, other)| i != j && member.knows(other) && other.knows(member))
+                    .map(|(j, _)| j)
+                    .collect()
+            })
+            .collect();
+        Self { neighbors }
+    }
+
+    /// Reports every maximal clique exactly once, via Bron–Kerbosch with pivoting.
+    ///
+    /// An empty graph has no cliques at all, not even an empty one.
+    pub(super) fn maximal_cliques(&self, report: &mut impl FnMut(&BTreeSet<usize>)) {
+        if self.neighbors.is_empty() {
+            return;
+        }
+        let all = (0..self.neighbors.len()).collect();
+        self.bron_kerbosch(&mut BTreeSet::new(), all, BTreeSet::new(), report);
+    }
+
+    /// The first largest maximal clique, in the order [`maximal_cliques`](Self::maximal_cliques) reports them.
+    pub(super) fn largest_clique(&self) -> Option<BTreeSet<usize>> {
+        let mut largest = None::<BTreeSet<usize>>;
+        self.maximal_cliques(&mut |clique| {
+            if largest
+                .as_ref()
+                .is_none_or(|largest| largest.len() < clique.len())
+            {
+                largest = Some(clique.clone());
+            }
+        });
+        largest
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut BTreeSet<usize>,
+        mut candidates: BTreeSet<usize>,
+        mut excluded: BTreeSet<usize>,
+        report: &mut impl FnMut(&BTreeSet<usize>),
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                report(clique);
+            }
+            return;
+        }
+
+        // the pivot covers as many candidates as possible, so only its non-neighbours need a branch
+        let pivot = candidates
+            .union(&excluded)
+            .copied()
+            .max_by_key(|&u| {
+                (
+                    candidates.intersection(&self.neighbors[u]).count(),
+                    usize::MAX - u,
+                )
+            })
+            .unwrap_or_default();
+        let branches = candidates
+            .difference(&self.neighbors[pivot])
+            .copied()
+            .collect::<Vec<_>>();
+
+        for v in branches {
+            let neighbors = &self.neighbors[v];
+            clique.insert(v);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbors).copied().collect(),
+                excluded.intersection(neighbors).copied().collect(),
+                report,
+            );
+            clique.remove(&v);
+            candidates.remove(&v);
+            excluded.insert(v);
+        }
+    }
+}
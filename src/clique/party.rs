@@ -0,0 +1,685 @@
+mod builder;
+mod dot;
+mod identifiers;
+mod incremental;
+mod known_by;
+mod parse;
+mod problems;
+#[cfg(feature = "serde")]
+mod serialize;
+mod stats;
+
+pub use builder::{BuildError, DuplicatePolicy, PartyBuilder};
+pub use dot::ParseDotError;
+pub use identifiers::Identifiers;
+pub use incremental::IncrementalParty;
+pub use known_by::KnownBy;
+pub use parse::{ParseEdgesError, ParseMatrixError, ParsePartyError, looks_like_edges};
+pub use problems::Problem;
+pub use stats::Stats;
+
+use super::{
+    Clique, DenseParty, DuplicateId, InternError, Interner, KnowsMatrix, PowerSet, clique2digraph,
+    matrix::Entry, person::Person,
+};
+use petgraph::{Direction, algo::tarjan_scc};
+use std::collections::{HashMap, HashSet, hash_set};
+
+/// The guests of a party, keyed by their id.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Party {
+    people: HashSet<Person>,
+}
+
+impl Party {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The party of the book's example. Its celebrity clique is 1, 2 and 3; 4 also knows 42, who
+    /// is not at the party, and 5 lists themselves.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = Party::demo();
+    /// assert_eq!(party.len(), 7);
+    /// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([1, 2, 3])));
+    /// ```
+    #[must_use]
+    pub fn demo() -> Self {
+        crate::party! {
+            1 => [1, 2, 3];
+            2 => [1, 3];
+            3 => [1, 2];
+            4 => [1, 2, 3, 42];
+            5 => [1, 2, 3, 4, 5];
+            6 => [1, 2, 3, 7];
+            7 => [1, 2, 3, 5, 6];
+        }
+    }
+
+    #[must_use]
+    pub fn builder() -> PartyBuilder {
+        PartyBuilder::new()
+    }
+
+    /// Adds a guest, returning `false` if somebody with the same id is already at the party.
+    ///
+    /// Unlike [`Extend`], the existing guest is left untouched in that case.
+    pub fn insert(&mut self, person: Person) -> bool {
+        self.people.insert(person)
+    }
+
+    /// Like [`insert`](Party::insert), but reports the id already at the party as an error.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DuplicateId`] if somebody with the same id is already at the party.
+    pub fn add_person(&mut self, person: Person) -> Result<(), DuplicateId> {
+        let id = person.id;
+        if self.insert(person) {
+            Ok(())
+        } else {
+            Err(DuplicateId(id))
+        }
+    }
+
+    /// Removes a guest, leaving any mention of them in other guests' acquaintances.
+    pub fn remove_person(&mut self, id: usize) -> Option<Person> {
+        self.people.take(&id)
+    }
+
+    /// Removes a guest and strips their id from everybody else's acquaintances.
+    pub fn remove_person_and_references(&mut self, id: usize) -> Option<Person> {
+        let removed = self.remove_person(id)?;
+        self.people = self
+            .people
+            .drain()
+            .map(|mut person| {
+                person.known_people.remove(&id);
+                person
+            })
+            .collect();
+        Some(removed)
+    }
+
+    /// Records that guest `who` now knows `whom`, who need not be at the party.
+    ///
+    /// Returns whether anything changed; it does not if `who` is not at the party, already knew
+    /// `whom`, or is `whom`.
+    pub fn learn(&mut self, who: usize, whom: usize) -> bool {
+        self.update(who, |person| {
+            who != whom && person.known_people.insert(whom)
+        })
+    }
+
+    /// Records that guest `who` no longer knows `whom`.
+    ///
+    /// Returns whether anything changed; it does not if `who` is not at the party or did not know `whom`.
+    pub fn forget(&mut self, who: usize, whom: usize) -> bool {
+        self.update(who, |person| person.known_people.remove(&whom))
+    }
+
+    fn update(&mut self, id: usize, f: impl FnOnce(&mut Person) -> bool) -> bool {
+        let Some(mut person) = self.people.take(&id) else {
+            return false;
+        };
+        let changed = f(&mut person);
+        self.people.insert(person);
+        changed
+    }
+
+    /// Adds a guest, or unions their acquaintances into the guest with the same id.
+    /// A name already known is kept.
+    pub fn merge(&mut self, person: Person) {
+        let merged = match self.people.take(&person.id) {
+            None => person,
+            Some(mut existing) => {
+                existing.known_people.extend(person.known_people);
+                existing.name = existing.name.or(person.name);
+                existing
+            }
+        };
+        self.people.insert(merged);
+    }
+
+    /// Merges every guest of `other` into this party.
+    pub fn absorb(&mut self, other: Self) {
+        self.extend(other);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.people.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.people.is_empty()
+    }
+
+    #[must_use]
+    pub fn iter(&self) -> hash_set::Iter<'_, Person> {
+        self.people.iter()
+    }
+
+    #[must_use]
+    pub fn contains_id(&self, id: usize) -> bool {
+        self.people.contains(&id)
+    }
+
+    #[must_use]
+    pub fn get(&self, id: usize) -> Option<&Person> {
+        self.people.get(&id)
+    }
+
+    /// The ids of every guest.
+    #[must_use]
+    pub fn ids(&self) -> HashSet<usize> {
+        self.people.iter().map(|person| person.id).collect()
+    }
+
+    /// The guests in ascending id order.
+    #[must_use]
+    pub fn sorted(&self) -> Vec<Person> {
+        let mut people = self.people.iter().cloned().collect::<Vec<_>>();
+        people.sort_unstable();
+        people
+    }
+
+    /// A party from an adjacency map of each guest's id to the ids they know.
+    /// Guests listed as knowing themselves lose that entry, as with [`Person::new`].
+    #[must_use]
+    pub fn from_adjacency(map: HashMap<usize, HashSet<usize>>) -> Self {
+        map.into_iter()
+            .map(|(id, known_people)| Person::new(id, known_people))
+            .collect()
+    }
+
+    /// A party from records of each guest's name and the names they know, with the names interned.
+    ///
+    /// Every name, including those only known by somebody, gets the id of its symbol in the
+    /// returned [`Interner`], and every guest keeps their name, so labels and DOT output show it.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Party};
+    ///
+    /// let (party, names) = Party::intern([
+    ///     ("alice", vec!["bob"]),
+    ///     ("bob", vec!["alice"]),
+    ///     ("carol", vec!["alice", "bob"]),
+    /// ])?;
+    /// let mut css = party.cclique().unwrap_or_default().ids().into_iter().collect::<Vec<_>>();
+    /// css.sort_unstable();
+    /// let css = css.into_iter().map(|id| names.resolve_id(id)).collect::<Vec<_>>();
+    /// assert_eq!(css, [Some("alice"), Some("bob")]);
+    /// # Ok::<(), celebrities::clique::InternError>(())
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if a name is empty or if two records have the same name.
+    pub fn intern<I, N, K>(records: I) -> Result<(Self, Interner), InternError>
+    where
+        I: IntoIterator<Item = (N, K)>,
+        N: AsRef<str>,
+        K: IntoIterator,
+        K::Item: AsRef<str>,
+    {
+        let mut names = Interner::new();
+        let mut party = Self::new();
+        for (name, known) in records {
+            let name = name.as_ref();
+            let id = names.intern(name)?;
+            let known = (known.into_iter())
+                .map(|other| names.intern(other.as_ref()).map(|symbol| symbol as usize))
+                .collect::<Result<Vec<_>, _>>()?;
+            if !party.insert(Person::named(id as usize, name, known)) {
+                return Err(InternError::DuplicateName(name.to_owned()));
+            }
+        }
+        Ok((party, names))
+    }
+
+    /// Each guest's id mapped to the ids they know. Names are dropped.
+    #[must_use]
+    pub fn to_adjacency(&self) -> HashMap<usize, HashSet<usize>> {
+        self.people
+            .iter()
+            .map(|person| (person.id, person.known_people.clone()))
+            .collect()
+    }
+
+    /// The celebrity clique, found through the strongly connected components of the acquaintance graph.
+    ///
+    /// A celebrity clique knows nobody outside and is known by everybody, so it is the only
+    /// component without edges leaving it. That sink is verified with [`is_cclique`](Clique::is_cclique),
+    /// which takes polynomial time instead of the exponential exhaustive search.
+    ///
+    /// ```
+    /// use celebrities::party;
+    /// use std::collections::HashSet;
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// assert_eq!(party.cclique_scc().map(|css| css.ids()), Some(HashSet::from([1, 2])));
+    /// assert_eq!(party! { 1 => [2]; 2 => [3]; 3 => [1]; 4 => [1] }.cclique_scc(), None);
+    /// ```
+    #[must_use]
+    pub fn cclique_scc(&self) -> Option<Self> {
+        let people = self.people.iter().collect::<Vec<_>>();
+        let graph = clique2digraph(&self.people);
+        let components = tarjan_scc(&graph);
+
+        let mut component_of = vec![0; people.len()];
+        for (component, nodes) in components.iter().enumerate() {
+            for node in nodes {
+                component_of[node.index()] = component;
+            }
+        }
+        let mut sinks = components.iter().enumerate().filter(|(component, nodes)| {
+            nodes.iter().all(|&node| {
+                graph
+                    .neighbors_directed(node, Direction::Outgoing)
+                    .all(|known| component_of[known.index()] == *component)
+            })
+        });
+        let (_, sink) = sinks.next()?;
+        if sinks.next().is_some() {
+            return None;
+        }
+
+        let css = sink
+            .iter()
+            .map(|node| people[node.index()].clone())
+            .collect::<Self>();
+        css.is_cclique(self).then_some(css)
+    }
+
+    /// Like [`cclique`](Clique::cclique), but every subset is checked against a [`KnownBy`] index
+    /// built once for the whole search.
+    #[must_use]
+    pub fn cclique_indexed(&self) -> Option<Self> {
+        let index = KnownBy::new(self);
+        let candidates = self
+            .iter()
+            .filter(|person| index.is_known_by_everyone(person.id))
+            .collect::<Vec<_>>();
+        PowerSet::<_, Vec<_>>::new(&candidates)
+            .skip(1)
+            .find(|celebrities| index.is_cclique(celebrities.iter().copied(), self))
+            .map(|celebrities| celebrities.into_iter().cloned().collect())
+    }
+
+    /// The party as a bit matrix, for fast checks on large parties.
+    #[must_use]
+    pub fn to_dense(&self) -> DenseParty {
+        DenseParty::from(&self.people)
+    }
+
+    /// Like [`cclique`](Clique::cclique), but every `knows` query is answered by `matrix`, so that
+    /// a matrix built once can serve many searches.
+    ///
+    /// The matrix should have been built from this party; guests it does not cover know nobody.
+    ///
+    /// ```
+    /// use celebrities::{Clique, clique::KnowsMatrix, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2] };
+    /// let matrix = KnowsMatrix::from_party(&party);
+    /// assert_eq!(party.cclique_with_matrix(&matrix), party.cclique());
+    /// ```
+    #[must_use]
+    pub fn cclique_with_matrix(&self, matrix: &KnowsMatrix) -> Option<Self> {
+        let entries = (self.people.iter())
+            .map(|person| Entry::new(person.id, matrix))
+            .collect::<Vec<_>>();
+        let css = entries.cclique()?;
+        Some(
+            css.iter()
+                .filter_map(|entry| self.get(entry.id).cloned())
+                .collect(),
+        )
+    }
+
+    #[must_use]
+    pub const fn as_set(&self) -> &HashSet<Person> {
+        &self.people
+    }
+}
+
+impl Clique for Party {
+    type Member = Person;
+
+    fn members(&self) -> impl Iterator<Item = &Person> + Clone {
+        self.people.iter()
+    }
+
+    fn contains_member(&self, member: &Person) -> bool {
+        self.people.contains(member)
+    }
+
+    fn from_members<I: IntoIterator<Item = Person>>(members: I) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+/// Repeated ids are [merged](Party::merge) rather than dropped.
+impl FromIterator<Person> for Party {
+    fn from_iter<I: IntoIterator<Item = Person>>(people: I) -> Self {
+        let mut party = Self::new();
+        party.extend(people);
+        party
+    }
+}
+
+/// Repeated ids are [merged](Party::merge) rather than dropped.
+impl Extend<Person> for Party {
+    fn extend<I: IntoIterator<Item = Person>>(&mut self, people: I) {
+        for person in people {
+            self.merge(person);
+        }
+    }
+}
+
+/// Repeated ids are [merged](Party::merge), as when collecting.
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for Party {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_iter::<Person>()?.collect()
+    }
+
+    fn arbitrary_take_rest(u: arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary_take_rest_iter::<Person>()?.collect()
+    }
+}
+
+impl From<HashSet<Person>> for Party {
+    fn from(people: HashSet<Person>) -> Self {
+        Self { people }
+    }
+}
+
+impl From<Party> for HashSet<Person> {
+    fn from(party: Party) -> Self {
+        party.people
+    }
+}
+
+impl IntoIterator for Party {
+    type Item = Person;
+    type IntoIter = hash_set::IntoIter<Person>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.people.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Party {
+    type Item = &'a Person;
+    type IntoIter = hash_set::Iter<'a, Person>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.people.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::sample_parties;
+    use petgraph::dot::{Config, Dot};
+
+    #[test]
+    fn test_ids_and_contains_id() {
+        let party = Party::demo();
+        assert_eq!(party.ids(), HashSet::from([1, 2, 3, 4, 5, 6, 7]));
+        assert!(party.contains_id(4));
+        assert!(!party.contains_id(42));
+    }
+
+    #[test]
+    fn test_sorted() {
+        let party = crate::party! { 7 => [1]; 3 => []; 5 => [3]; 1 => [7] };
+        let ids = party
+            .sorted()
+            .iter()
+            .map(|person| person.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, [1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_extend_merges() {
+        let mut party = Party::demo();
+        party.extend([
+            Person::from((3_usize, [4_usize])),
+            Person::from((8_usize, [1_usize])),
+        ]);
+        assert_eq!(
+            party.get(3).map(|person| person.known_people.clone()),
+            Some(HashSet::from([1, 2, 4]))
+        );
+        assert!(party.contains_id(8));
+
+        let collected = [(1_usize, vec![2]), (1, vec![3])]
+            .map(Person::from)
+            .into_iter()
+            .collect::<Party>();
+        assert_eq!(collected.len(), 1);
+        assert_eq!(
+            collected.get(1).map(|person| person.known_people.clone()),
+            Some(HashSet::from([2, 3]))
+        );
+    }
+
+    #[test]
+    fn test_absorb() {
+        let mut party = Party::demo();
+        party.absorb(crate::party! { 4 => [5]; 8 => [4] });
+        assert_eq!(party.ids(), HashSet::from([1, 2, 3, 4, 5, 6, 7, 8]));
+        assert_eq!(
+            party.get(4).map(|person| person.known_people.clone()),
+            Some(HashSet::from([1, 2, 3, 5, 42]))
+        );
+    }
+
+    #[test]
+    fn test_add_person() {
+        let mut party = Party::demo();
+        assert_eq!(party.add_person(Person::new(8, [1])), Ok(()));
+        assert_eq!(party.add_person(Person::new(8, [2])), Err(DuplicateId(8)));
+        assert_eq!(
+            party.get(8).map(|person| person.known_people.clone()),
+            Some(HashSet::from([1]))
+        );
+    }
+
+    #[test]
+    fn test_remove_celebrity() {
+        let mut party = Party::demo();
+        assert_eq!(party.remove_person(3).map(|person| person.id), Some(3));
+        assert_eq!(party.remove_person(3), None);
+        assert!(
+            party
+                .get(1)
+                .is_some_and(|person| person.known_people.contains(&3))
+        );
+        assert_eq!(
+            party.cclique().map(|css| css.ids()),
+            Some(HashSet::from([1, 2]))
+        );
+
+        let mut party = Party::demo();
+        assert!(party.remove_person_and_references(3).is_some());
+        assert!(party.iter().all(|person| !person.known_people.contains(&3)));
+        assert_eq!(
+            party.cclique().map(|css| css.ids()),
+            Some(HashSet::from([1, 2]))
+        );
+        assert_eq!(party.remove_person_and_references(3), None);
+    }
+
+    #[test]
+    fn test_learn_and_forget() {
+        let mut party = Party::demo();
+        assert!(party.learn(4, 5));
+        assert!(!party.learn(4, 5));
+        assert!(!party.learn(4, 4));
+        assert!(!party.learn(42, 1));
+        assert!(
+            party
+                .get(4)
+                .is_some_and(|person| person.known_people.contains(&5))
+        );
+
+        assert!(party.forget(2, 3));
+        assert!(!party.forget(2, 3));
+        assert!(!party.forget(42, 1));
+        assert_eq!(
+            party.get(2).map(|person| person.known_people.clone()),
+            Some(HashSet::from([1]))
+        );
+        assert_eq!(party.cclique(), None);
+    }
+
+    #[test]
+    fn test_cclique_scc() {
+        assert_eq!(
+            Party::demo().cclique_scc().map(|css| css.ids()),
+            Some(HashSet::from([1, 2, 3]))
+        );
+        assert_eq!(Party::new().cclique_scc(), None);
+        // The sink is reached by everyone but is not a clique.
+        assert_eq!(
+            crate::party! { 1 => [2]; 2 => [3]; 3 => [1]; 4 => [1, 2, 3] }.cclique_scc(),
+            None
+        );
+        // Two sinks.
+        assert_eq!(
+            crate::party! { 1 => []; 2 => []; 3 => [1, 2] }.cclique_scc(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cclique_scc_agrees_with_exhaustive_search() {
+        for party in sample_parties(300, 8, 0.5, 32).map(Party::from) {
+            assert_eq!(
+                party.cclique_scc().map(|css| css.ids()),
+                party.cclique().map(|css| css.ids()),
+                "{party:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_solvers_agree() {
+        use crate::AdjacencyClique;
+
+        for party in sample_parties(400, 10, 0.6, 46).map(Party::from) {
+            let expected = party.cclique().map(|css| css.ids());
+            let ids = |css: Option<Party>| css.map(|css| css.ids());
+            assert_eq!(ids(party.cclique_scc()), expected, "scc {party:?}");
+            assert_eq!(ids(party.cclique_indexed()), expected, "indexed {party:?}");
+            assert_eq!(ids(party.cclique_gray()), expected, "gray {party:?}");
+            assert_eq!(
+                ids(party.cclique_elimination()),
+                expected,
+                "elimination {party:?}"
+            );
+            assert_eq!(
+                party.cclique_with_limit(usize::MAX).map(ids),
+                Ok(expected.clone()),
+                "with_limit {party:?}"
+            );
+            #[cfg(feature = "rayon")]
+            assert_eq!(ids(party.cclique_par()), expected, "par {party:?}");
+            assert_eq!(
+                AdjacencyClique::cclique(&party.to_adjacency())
+                    .map(|css| css.into_keys().collect::<HashSet<_>>()),
+                expected,
+                "adjacency {party:?}"
+            );
+            let singleton = expected.clone().filter(|ids| ids.len() == 1);
+            assert_eq!(
+                party.celebrity().map(|person| HashSet::from([person.id])),
+                singleton,
+                "celebrity {party:?}"
+            );
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        let mut rng = crate::generator::Rng::new(62);
+        for len in [0, 1, 16, 256, 4096] {
+            let bytes = (0..len)
+                .map(|_| rng.next_u64().to_le_bytes()[0])
+                .collect::<Vec<_>>();
+            let party = Party::arbitrary_take_rest(Unstructured::new(&bytes)).expect("any bytes");
+            assert!(
+                party
+                    .iter()
+                    .all(|person| !person.known_people.contains(&person.id))
+            );
+            let again = Party::arbitrary_take_rest(Unstructured::new(&bytes)).expect("any bytes");
+            assert_eq!(party.to_adjacency(), again.to_adjacency());
+        }
+    }
+
+    #[test]
+    fn test_intern_round_trip() {
+        let (party, names) = Party::intern([
+            ("Ada", vec!["Grace"]),
+            ("Grace", vec!["Ada"]),
+            ("Linus", vec!["Ada", "Grace", "Ken"]),
+            ("Ken", vec!["Ada", "Grace"]),
+        ])
+        .expect("valid names");
+        assert_eq!(names.len(), 4);
+        let css = party.cclique().unwrap_or_default();
+        let mut celebrities = (css.ids().into_iter())
+            .filter_map(|id| names.resolve_id(id))
+            .collect::<Vec<_>>();
+        celebrities.sort_unstable();
+        assert_eq!(celebrities, ["Ada", "Grace"]);
+
+        let graph = clique2digraph(css.as_set());
+        let dot = format!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]));
+        for person in &css {
+            let name = names.resolve_id(person.id).unwrap_or_default();
+            let label = format!(r#"label = "{name} ({})""#, person.id);
+            assert!(dot.contains(&label), "{dot}");
+        }
+    }
+
+    #[test]
+    fn test_intern_rejects_bad_names() {
+        assert_eq!(
+            Party::intern([("Ada", vec![""])]).map(|_| ()),
+            Err(InternError::EmptyName)
+        );
+        assert_eq!(
+            Party::intern([("", Vec::<&str>::new())]).map(|_| ()),
+            Err(InternError::EmptyName)
+        );
+        assert_eq!(
+            Party::intern([("Ada", vec!["Grace"]), ("Grace", vec![]), ("Ada", vec![])]).map(|_| ()),
+            Err(InternError::DuplicateName("Ada".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_cclique_delegates() {
+        let party = Party::demo();
+        let css = party.cclique().unwrap_or_default();
+        assert_eq!(css.ids(), HashSet::from([1, 2, 3]));
+        assert!(css.is_cclique(&party));
+    }
+}
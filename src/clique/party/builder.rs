@@ -0,0 +1,149 @@
+use super::{Party, Person};
+use std::{
+    collections::{HashMap, HashSet, hash_map::Entry},
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// What [`PartyBuilder::build`] does when two people share an id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Union the `known_people` of every person with the same id.
+    Merge,
+    /// Fail with the offending id.
+    #[default]
+    Error,
+    /// Keep the person that was added first and drop the others.
+    KeepFirst,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    DuplicateId(usize),
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::DuplicateId(id) => write!(f, "person {id} is defined more than once"),
+        }
+    }
+}
+
+impl Error for BuildError {}
+
+/// Collects people and resolves duplicate ids according to a [`DuplicatePolicy`].
+///
+/// ```
+/// use celebrities::clique::party::{BuildError, DuplicatePolicy, PartyBuilder};
+///
+/// let party = PartyBuilder::new()
+///     .policy(DuplicatePolicy::Merge)
+///     .add(1_usize, [2_usize])
+///     .add(2_usize, [1_usize])
+///     .add(1_usize, [3_usize])
+///     .build()?;
+/// assert_eq!(party.len(), 2);
+///
+/// let err = PartyBuilder::new().add(1_usize, [2_usize]).add(1_usize, [3_usize]).build();
+/// assert_eq!(err, Err(BuildError::DuplicateId(1)));
+/// # Ok::<(), BuildError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PartyBuilder {
+    policy: DuplicatePolicy,
+    people: Vec<Person>,
+}
+
+impl PartyBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub const fn policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    #[must_use]
+    pub fn add<N, V>(self, id: N, knows: V) -> Self
+    where
+        V: IntoIterator<Item = N>,
+        N: Into<usize>,
+    {
+        self.add_person(Person::from((id, knows)))
+    }
+
+    #[must_use]
+    pub fn add_person(mut self, person: Person) -> Self {
+        self.people.push(person);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Returns [`BuildError::DuplicateId`] for the first repeated id under [`DuplicatePolicy::Error`].
+    pub fn build(self) -> Result<Party, BuildError> {
+        let mut people: HashMap<usize, Person> = HashMap::with_capacity(self.people.len());
+        for person in self.people {
+            match people.entry(person.id) {
+                Entry::Vacant(entry) => {
+                    entry.insert(person);
+                }
+                Entry::Occupied(mut entry) => match self.policy {
+                    DuplicatePolicy::Merge => {
+                        let merged = entry.get_mut();
+                        merged.known_people.extend(person.known_people);
+                        merged.name = merged.name.take().or(person.name);
+                    }
+                    DuplicatePolicy::Error => return Err(BuildError::DuplicateId(person.id)),
+                    DuplicatePolicy::KeepFirst => {}
+                },
+            }
+        }
+        Ok(Party::from(people.into_values().collect::<HashSet<_>>()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflicting(policy: DuplicatePolicy) -> Result<Party, BuildError> {
+        PartyBuilder::new()
+            .policy(policy)
+            .add(1_usize, vec![2, 3])
+            .add(2_usize, vec![1])
+            .add(1_usize, vec![4])
+            .build()
+    }
+
+    #[test]
+    fn test_merge() {
+        let party = conflicting(DuplicatePolicy::Merge).expect("merge never fails");
+        assert_eq!(party.len(), 2);
+        assert_eq!(
+            party.get(1).map(|person| person.known_people.clone()),
+            Some(HashSet::from([2, 3, 4]))
+        );
+    }
+
+    #[test]
+    fn test_error() {
+        assert_eq!(
+            conflicting(DuplicatePolicy::Error),
+            Err(BuildError::DuplicateId(1))
+        );
+    }
+
+    #[test]
+    fn test_keep_first() {
+        let party = conflicting(DuplicatePolicy::KeepFirst).expect("keep first never fails");
+        assert_eq!(
+            party.get(1).map(|person| person.known_people.clone()),
+            Some(HashSet::from([2, 3]))
+        );
+    }
+}
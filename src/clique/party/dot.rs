@@ -0,0 +1,701 @@
+use super::{Identifiers, Party, Person};
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Why a text could not be read as a DOT [`Party`]. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseDotError {
+    /// The graph is a `graph`, or has a `--` edge, so it does not say who knows whom.
+    Undirected { line: usize },
+    /// Something other than the DOT this reader understands.
+    Unexpected {
+        line: usize,
+        found: String,
+        expected: &'static str,
+    },
+    /// A quoted string, HTML string or comment is not closed.
+    Unterminated { line: usize },
+    /// Neither the label nor the name of this node is a person's id.
+    NotAnId { line: usize, node: String },
+    /// This node is a person another node already is.
+    DuplicateId { line: usize, id: usize },
+    /// Like [`DuplicateId`](ParseDotError::DuplicateId), for a person known by name.
+    DuplicateName { line: usize, name: String },
+}
+
+impl ParseDotError {
+    /// The line the problem was found on.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        match self {
+            Self::Undirected { line }
+            | Self::Unexpected { line, .. }
+            | Self::Unterminated { line }
+            | Self::NotAnId { line, .. }
+            | Self::DuplicateId { line, .. }
+            | Self::DuplicateName { line, .. } => *line,
+        }
+    }
+}
+
+impl Display for ParseDotError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Undirected { line } => write!(
+                f,
+                "line {line}: the graph is undirected, so it does not say who knows whom"
+            ),
+            Self::Unexpected {
+                line,
+                found,
+                expected,
+            } => write!(f, "line {line}: expected {expected}, found {found}"),
+            Self::Unterminated { line } => write!(f, "line {line}: unterminated string or comment"),
+            Self::NotAnId { line, node } => {
+                write!(f, "line {line}: node `{node}` is not labelled with an id")
+            }
+            Self::DuplicateId { line, id } => {
+                write!(f, "line {line}: person {id} is described more than once")
+            }
+            Self::DuplicateName { line, name } => {
+                write!(f, "line {line}: `{name}` is described more than once")
+            }
+        }
+    }
+}
+
+impl Error for ParseDotError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// A name, numeral or string; keywords are the unquoted ones.
+    Id {
+        text: String,
+        quoted: bool,
+    },
+    Arrow,
+    UndirectedEdge,
+    Punct(char),
+}
+
+impl Token {
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Self::Id { text, quoted: false } if text.eq_ignore_ascii_case(keyword))
+    }
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Id { text, .. } => write!(f, "`{text}`"),
+            Self::Arrow => f.write_str("`->`"),
+            Self::UndirectedEdge => f.write_str("`--`"),
+            Self::Punct(c) => write!(f, "`{c}`"),
+        }
+    }
+}
+
+/// Splits DOT into tokens with their lines, dropping comments and `#` lines.
+fn tokenize(text: &str) -> Result<Vec<(Token, usize)>, ParseDotError> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    let mut line = 1;
+    let mut line_start = true;
+    while let Some(c) = chars.next() {
+        let start = line;
+        match c {
+            '\n' => {
+                line += 1;
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '#' if line_start => while chars.next_if(|&c| c != '\n').is_some() {},
+            '/' if chars.next_if_eq(&'/').is_some() => {
+                while chars.next_if(|&c| c != '\n').is_some() {}
+            }
+            '/' if chars.next_if_eq(&'*').is_some() => loop {
+                match chars.next() {
+                    None => return Err(ParseDotError::Unterminated { line: start }),
+                    Some('*') if chars.next_if_eq(&'/').is_some() => break,
+                    Some('\n') => line += 1,
+                    Some(_) => {}
+                }
+            },
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err(ParseDotError::Unterminated { line: start }),
+                        Some('"') => break,
+                        Some('\\') if chars.next_if_eq(&'"').is_some() => text.push('"'),
+                        // A backslash before the end of a line continues the string.
+                        Some('\\') if chars.next_if_eq(&'\n').is_some() => line += 1,
+                        Some(c) => {
+                            line += usize::from(c == '\n');
+                            text.push(c);
+                        }
+                    }
+                }
+                tokens.push((Token::Id { text, quoted: true }, start));
+            }
+            '<' => {
+                let (mut text, mut depth) = (String::new(), 1);
+                while depth > 0 {
+                    let c = chars
+                        .next()
+                        .ok_or(ParseDotError::Unterminated { line: start })?;
+                    depth = match c {
+                        '<' => depth + 1,
+                        '>' => depth - 1,
+                        _ => depth,
+                    };
+                    line += usize::from(c == '\n');
+                    if depth > 0 {
+                        text.push(c);
+                    }
+                }
+                tokens.push((Token::Id { text, quoted: true }, start));
+            }
+            '-' if chars.next_if_eq(&'>').is_some() => tokens.push((Token::Arrow, start)),
+            '-' if chars.next_if_eq(&'-').is_some() => tokens.push((Token::UndirectedEdge, start)),
+            c if c.is_alphanumeric() || matches!(c, '_' | '.' | '-') => {
+                let mut text = String::from(c);
+                while let Some(c) =
+                    chars.next_if(|&c| c.is_alphanumeric() || matches!(c, '_' | '.'))
+                {
+                    text.push(c);
+                }
+                tokens.push((
+                    Token::Id {
+                        text,
+                        quoted: false,
+                    },
+                    start,
+                ));
+            }
+            c => tokens.push((Token::Punct(c), start)),
+        }
+        line_start = false;
+    }
+    Ok(tokens)
+}
+
+/// The nodes and edges of a digraph, in the order they are first mentioned.
+#[derive(Default)]
+struct Graph {
+    /// Each node with its `label`, if it has one, and the line it was first seen on.
+    nodes: Vec<(String, Option<String>, usize)>,
+    positions: HashMap<String, usize>,
+    edges: Vec<(usize, usize)>,
+}
+
+impl Graph {
+    fn node(&mut self, name: &str, line: usize) -> usize {
+        *self.positions.entry(name.to_owned()).or_insert_with(|| {
+            self.nodes.push((name.to_owned(), None, line));
+            self.nodes.len() - 1
+        })
+    }
+}
+
+/// The attributes of a statement that say who is who and who knows whom.
+#[derive(Default)]
+struct Attributes {
+    /// The first line of the `label`, before any `\n`.
+    label: Option<String>,
+    /// The `dir` of the arrows, `forward` by default.
+    dir: Option<String>,
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    next: usize,
+    graph: Graph,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next).map(|(token, _)| token)
+    }
+
+    fn line(&self) -> usize {
+        self.tokens
+            .get(self.next)
+            .or_else(|| self.tokens.last())
+            .map_or(1, |&(_, line)| line)
+    }
+
+    fn eat(&mut self, punct: char) -> bool {
+        let found = self.peek() == Some(&Token::Punct(punct));
+        self.next += usize::from(found);
+        found
+    }
+
+    fn unexpected(&self, expected: &'static str) -> ParseDotError {
+        ParseDotError::Unexpected {
+            line: self.line(),
+            found: self
+                .peek()
+                .map_or_else(|| "the end".to_owned(), ToString::to_string),
+            expected,
+        }
+    }
+
+    fn expect(&mut self, punct: char, expected: &'static str) -> Result<(), ParseDotError> {
+        if self.eat(punct) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn id(&mut self, expected: &'static str) -> Result<String, ParseDotError> {
+        match self.peek() {
+            Some(Token::Id { text, .. }) => {
+                let text = text.clone();
+                self.next += 1;
+                Ok(text)
+            }
+            Some(Token::UndirectedEdge) => Err(ParseDotError::Undirected { line: self.line() }),
+            _ => Err(self.unexpected(expected)),
+        }
+    }
+
+    /// `[strict] digraph [name] { ... }`
+    fn parse(mut self) -> Result<Graph, ParseDotError> {
+        if self.peek().is_some_and(|token| token.is_keyword("strict")) {
+            self.next += 1;
+        }
+        match self.peek() {
+            Some(token) if token.is_keyword("digraph") => self.next += 1,
+            Some(token) if token.is_keyword("graph") => {
+                return Err(ParseDotError::Undirected { line: self.line() });
+            }
+            _ => return Err(self.unexpected("`digraph`")),
+        }
+        if !matches!(self.peek(), Some(Token::Punct('{'))) {
+            self.id("the name of the graph")?;
+        }
+        self.expect('{', "`{`")?;
+        self.statements()?;
+        if self.next < self.tokens.len() {
+            return Err(self.unexpected("the end of the graph"));
+        }
+        Ok(self.graph)
+    }
+
+    /// Statements up to and including the closing `}`.
+    fn statements(&mut self) -> Result<Vec<usize>, ParseDotError> {
+        let mut nodes = Vec::new();
+        while !self.eat('}') {
+            if self.peek().is_none() {
+                return Err(self.unexpected("`}`"));
+            }
+            nodes.extend(self.statement()?);
+            while self.eat(';') || self.eat(',') {}
+        }
+        Ok(nodes)
+    }
+
+    /// A statement, returning the nodes it mentions.
+    fn statement(&mut self) -> Result<Vec<usize>, ParseDotError> {
+        let keyword = ["graph", "node", "edge"]
+            .into_iter()
+            .any(|keyword| self.peek().is_some_and(|token| token.is_keyword(keyword)));
+        if keyword
+            && self.tokens.get(self.next + 1).map(|(token, _)| token) != Some(&Token::Punct('='))
+        {
+            self.next += 1;
+            self.attributes()?;
+            return Ok(Vec::new());
+        }
+        let line = self.line();
+        let mut group = self.endpoint()?;
+        if group.is_empty() && self.eat('=') {
+            // `name = value` sets an attribute of the graph.
+            self.id("a value")?;
+            return Ok(Vec::new());
+        }
+        let mut mentioned = group.clone();
+        let mut edges = Vec::new();
+        let mut is_edge = false;
+        loop {
+            match self.peek() {
+                Some(Token::Arrow) => self.next += 1,
+                Some(Token::UndirectedEdge) => {
+                    return Err(ParseDotError::Undirected { line: self.line() });
+                }
+                _ => break,
+            }
+            is_edge = true;
+            let targets = self.endpoint()?;
+            for &from in &group {
+                for &to in &targets {
+                    edges.push((from, to));
+                }
+            }
+            mentioned.extend(&targets);
+            group = targets;
+        }
+        let Attributes { label, dir } = self.attributes()?;
+        // `dir` only changes the arrowheads drawn, which are whom the guests know.
+        for (from, to) in edges {
+            match dir.as_deref() {
+                Some("both") => self.graph.edges.extend([(from, to), (to, from)]),
+                Some("back") => self.graph.edges.push((to, from)),
+                _ => self.graph.edges.push((from, to)),
+            }
+        }
+        if let (false, Some(label), [node]) = (is_edge, label, mentioned.as_slice()) {
+            self.graph.nodes[*node].1 = Some(label);
+            self.graph.nodes[*node].2 = line;
+        }
+        Ok(mentioned)
+    }
+
+    /// A node or a subgraph, as the nodes it stands for. A node named before an `=` is not one,
+    /// so it is left to the caller as no nodes.
+    fn endpoint(&mut self) -> Result<Vec<usize>, ParseDotError> {
+        if self
+            .peek()
+            .is_some_and(|token| token.is_keyword("subgraph"))
+        {
+            self.next += 1;
+            if !matches!(self.peek(), Some(Token::Punct('{'))) {
+                self.id("the name of the subgraph")?;
+            }
+        }
+        if self.eat('{') {
+            return self.statements();
+        }
+        let line = self.line();
+        let name = self.id("a node")?;
+        if self.peek() == Some(&Token::Punct('=')) {
+            return Ok(Vec::new());
+        }
+        if self.eat(':') {
+            return Err(ParseDotError::Unexpected {
+                line,
+                found: "a port".to_owned(),
+                expected: "a node without one",
+            });
+        }
+        Ok(vec![self.graph.node(&name, line)])
+    }
+
+    /// Any number of `[name = value, ...]` lists, keeping the last `label` and `dir`.
+    fn attributes(&mut self) -> Result<Attributes, ParseDotError> {
+        let mut attributes = Attributes::default();
+        while self.eat('[') {
+            while !self.eat(']') {
+                let name = self.id("an attribute or `]`")?;
+                if self.eat('=') {
+                    let value = self.id("the value of an attribute")?;
+                    match name.as_str() {
+                        "label" => attributes.label = value.split("\\n").next().map(str::to_owned),
+                        "dir" => attributes.dir = Some(value),
+                        _ => {}
+                    }
+                }
+                while self.eat(';') || self.eat(',') {}
+            }
+        }
+        Ok(attributes)
+    }
+}
+
+/// The person a node stands for: when identifiers are names, the one its label or else its name
+/// is; otherwise the one [`numbered`] finds.
+fn person(name: &str, label: Option<&str>, ids: &mut Identifiers) -> Option<Person> {
+    if !ids.is_names() {
+        if let Some(person) = numbered(name, label) {
+            ids.numbers();
+            return Some(person);
+        }
+        if *ids == Identifiers::Numbers {
+            return None;
+        }
+    }
+    let id = ids.resolve(label.unwrap_or(name))?;
+    Some(ids.person(id, []))
+}
+
+/// The person a node stands for, going by its label, as written by
+/// [`clique2digraph`](crate::clique2digraph), or else its name.
+fn numbered(name: &str, label: Option<&str>) -> Option<Person> {
+    let Some(label) = label else {
+        return Some(Person::new(name.trim().parse().ok()?, []));
+    };
+    if let Ok(id) = label.trim().parse() {
+        return Some(Person::new(id, []));
+    }
+    // `Name (id)`, or just a name when the node's name is the id.
+    let named = label
+        .strip_suffix(')')
+        .and_then(|rest| rest.rsplit_once(" ("))
+        .and_then(|(person, id)| Some((person, id.parse().ok()?)));
+    let (person, id) = named.or_else(|| Some((label, name.trim().parse().ok()?)))?;
+    Some(Person::named(id, person, []))
+}
+
+impl Party {
+    /// Reads a party from a DOT digraph, where each node is a guest and `a -> b` means that `a`
+    /// knows `b`.
+    ///
+    /// A node is the person the first line of its `label` names, as `7` or `Alice (7)` in the
+    /// graphs this crate writes, or else the person its name is the id of. An edge with
+    /// `dir = both` means that both ends know each other, and one with `dir = back` that the
+    /// head knows the tail. Other attributes are skipped, subgraphs are flattened, and every
+    /// node mentioned is a guest.
+    ///
+    /// # Errors
+    ///
+    /// Fails on undirected graphs, on DOT beyond nodes, edges, attributes and subgraphs, and on
+    /// nodes that are not a person or the same person as another one.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = Party::from_dot("digraph { 1 -> 2 -> 1; 3 -> { 1 2 } [color = red] }")?;
+    /// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([1, 2])));
+    ///
+    /// let err = Party::from_dot("graph {\n  1 -- 2\n}").unwrap_err();
+    /// assert_eq!(err.line(), 1);
+    /// # Ok::<(), celebrities::clique::party::ParseDotError>(())
+    /// ```
+    pub fn from_dot(text: &str) -> Result<Self, ParseDotError> {
+        Self::parse_dot(text, &mut Identifiers::Numbers)
+    }
+
+    /// Like [`from_dot`](Party::from_dot), reading the nodes with `ids`. When they are names, a
+    /// node is the person its label, or else its name, is.
+    ///
+    /// # Errors
+    ///
+    /// As with [`from_dot`](Party::from_dot).
+    pub fn parse_dot(text: &str, ids: &mut Identifiers) -> Result<Self, ParseDotError> {
+        Self::parse_dot_nodes(text, ids).map(|(party, _)| party)
+    }
+
+    /// Like [`parse_dot`](Party::parse_dot), also returning the name of the node of each guest,
+    /// by id, so that statements about them can be added to the graph.
+    ///
+    /// # Errors
+    ///
+    /// As with [`from_dot`](Party::from_dot).
+    ///
+    /// ```
+    /// use celebrities::{Party, clique::party::Identifiers};
+    ///
+    /// let dot = "digraph { a [label = 1]; b [label = \"2\"]; a -> b; 3 -> a }";
+    /// let (party, nodes) = Party::parse_dot_nodes(dot, &mut Identifiers::Numbers)?;
+    /// assert_eq!(party.len(), 3);
+    /// assert_eq!(nodes[&1], "a");
+    /// assert_eq!(nodes[&3], "3");
+    /// # Ok::<(), celebrities::clique::party::ParseDotError>(())
+    /// ```
+    pub fn parse_dot_nodes(
+        text: &str,
+        ids: &mut Identifiers,
+    ) -> Result<(Self, HashMap<usize, String>), ParseDotError> {
+        let parser = Parser {
+            tokens: tokenize(text)?,
+            next: 0,
+            graph: Graph::default(),
+        };
+        let graph = parser.parse()?;
+
+        let mut nodes = Vec::with_capacity(graph.nodes.len());
+        let mut people = HashMap::with_capacity(graph.nodes.len());
+        let mut names = HashMap::with_capacity(graph.nodes.len());
+        for (name, label, line) in graph.nodes {
+            let Some(person) = person(&name, label.as_deref(), ids) else {
+                return Err(ParseDotError::NotAnId {
+                    line,
+                    node: label.unwrap_or(name),
+                });
+            };
+            let id = person.id;
+            match people.entry(id) {
+                Entry::Occupied(_) => {
+                    return Err(ids.name(id).map_or(
+                        ParseDotError::DuplicateId { line, id },
+                        |name| ParseDotError::DuplicateName {
+                            line,
+                            name: name.to_owned(),
+                        },
+                    ));
+                }
+                Entry::Vacant(entry) => entry.insert(person),
+            };
+            nodes.push(id);
+            names.insert(id, name);
+        }
+        let mut party = people.into_values().collect::<Self>();
+        for (from, to) in graph.edges {
+            party.learn(nodes[from], nodes[to]);
+        }
+        Ok((party, names))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clique, clique2digraph, generator::sample_parties};
+    use petgraph::dot::{Config, Dot};
+
+    fn to_dot(party: &Party) -> String {
+        let graph = clique2digraph(party.as_set());
+        format!("{:?}", Dot::with_config(&graph, &[Config::EdgeNoLabel]))
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for party in sample_parties(200, 8, 0.6, 70) {
+            let party = party.into_iter().collect::<Party>();
+            let back = Party::from_dot(&to_dot(&party)).expect("our own output reads back");
+            // The graph leaves out references to people who did not come.
+            let mut expected = party.to_adjacency();
+            let ids = party.ids();
+            for known in expected.values_mut() {
+                known.retain(|id| ids.contains(id));
+            }
+            assert_eq!(back.to_adjacency(), expected);
+            assert_eq!(
+                back.cclique().map(|css| css.ids()),
+                party.cclique().map(|css| css.ids())
+            );
+        }
+    }
+
+    #[test]
+    fn test_round_trip_keeps_names() {
+        let mut party = crate::party! { 1 => [2]; 2 => [1] };
+        party.merge(Person::named(3, "Carol \"C\" Jones", [1, 2]));
+        let back = Party::from_dot(&to_dot(&party)).expect("our own output reads back");
+        assert_eq!(back.to_adjacency(), party.to_adjacency());
+        assert_eq!(
+            back.get(3).and_then(|carol| carol.name.as_deref()),
+            Some("Carol \"C\" Jones")
+        );
+    }
+
+    #[test]
+    fn test_plain_dot() {
+        let dot = r#"
+            // written by hand
+            strict digraph "party" {
+                graph [rankdir = LR]; node [shape = box]
+                label = "A party"
+                /* 4 came alone */
+                4
+            # a line for the preprocessor
+                1 -> 2 -> 3 [color = "red", style = bold];
+                3 -> 1 -> 3
+                subgraph cluster_fans { 5; 6 [label = "Frank"] } -> { 1; 2; 3 }
+            }
+        "#;
+        let party = Party::from_dot(dot).expect("valid DOT");
+        let expected = crate::party! {
+            1 => [2, 3];
+            2 => [3];
+            3 => [1];
+            4 => [];
+            5 => [1, 2, 3];
+            6 => [1, 2, 3];
+        };
+        assert_eq!(party.to_adjacency(), expected.to_adjacency());
+        assert_eq!(
+            party.get(6).and_then(|frank| frank.name.as_deref()),
+            Some("Frank")
+        );
+    }
+
+    #[test]
+    fn test_dir() {
+        let dot = "digraph { 1 -> 2 [dir = both]; 3 -> { 1 2 } [dir = back]; 4 -> 1 [dir = none] }";
+        let party = Party::from_dot(dot).expect("valid DOT");
+        let expected = crate::party! { 1 <=> 2; 1 => [3]; 2 => [3]; 3 => []; 4 => [1] };
+        assert_eq!(party.to_adjacency(), expected.to_adjacency());
+    }
+
+    #[test]
+    fn test_names() {
+        let dot = "digraph {\n  alice -> bob -> alice\n  \"Mary Ann\" -> { alice bob }\n  x [label = Bob]\n}";
+        let mut ids = Identifiers::Auto;
+        let party = Party::parse_dot(dot, &mut ids).expect("valid DOT");
+        let named = |name| {
+            let id = ids.names().and_then(|names| names.get(name))? as usize;
+            party.get(id)?.name.as_deref()
+        };
+        assert_eq!(named("Mary Ann"), Some("Mary Ann"));
+        // Names are compared exactly: the label `Bob` is somebody else.
+        assert_eq!(party.len(), 4);
+        assert_eq!(named("Bob"), Some("Bob"));
+        // Only the first line of a label names somebody.
+        let mut ids = Identifiers::Auto;
+        let carol = Party::parse_dot(r#"digraph { x [label = "Carol\nknows 0"] }"#, &mut ids)
+            .expect("valid DOT");
+        assert_eq!(carol.sorted()[0].name.as_deref(), Some("Carol"));
+        assert_eq!(
+            party.cclique().map(|css| css.len()),
+            None,
+            "Bob knows nobody and is known by nobody"
+        );
+
+        let err = Party::parse_dot(
+            "digraph { a [label = x]; b [label = x] }",
+            &mut Identifiers::Auto,
+        );
+        assert_eq!(
+            err,
+            Err(ParseDotError::DuplicateName {
+                line: 1,
+                name: "x".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = |dot| Party::from_dot(dot).expect_err("invalid DOT");
+        assert_eq!(
+            err("graph { 1 -- 2 }"),
+            ParseDotError::Undirected { line: 1 }
+        );
+        assert_eq!(
+            err("digraph {\n1 -> 2\n2 -- 1\n}"),
+            ParseDotError::Undirected { line: 3 }
+        );
+        assert_eq!(
+            err("digraph {\n\"1 -> 2\n}"),
+            ParseDotError::Unterminated { line: 2 }
+        );
+        assert_eq!(
+            err("digraph {\n  alice -> 1\n}"),
+            ParseDotError::NotAnId {
+                line: 2,
+                node: "alice".to_owned()
+            }
+        );
+        assert_eq!(
+            err("digraph {\n0 [label = 1]\n1\n}"),
+            ParseDotError::DuplicateId { line: 3, id: 1 }
+        );
+        assert_eq!(err("digraph { 1 -> }").line(), 1);
+        assert_eq!(err("digraph { 1 -> 2").line(), 1);
+        assert!(matches!(
+            err("party { }"),
+            ParseDotError::Unexpected {
+                expected: "`digraph`",
+                ..
+            }
+        ));
+    }
+}
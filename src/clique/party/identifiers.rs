@@ -0,0 +1,129 @@
+use super::Person;
+use crate::clique::Interner;
+
+/// How the parsers of a [`Party`](super::Party) turn the identifiers of their input into ids.
+///
+/// Identifiers are either numbers, which are the ids, or names, which are given ids in the order
+/// they first appear and compared exactly. [`Auto`](Identifiers::Auto) goes by the first
+/// identifier: if it is a number, they all must be.
+///
+/// ```
+/// use celebrities::{Clique, Party, clique::party::Identifiers};
+///
+/// let mut ids = Identifiers::Auto;
+/// let party = Party::parse_text("alice: bob\nbob: alice\n\"Mary Ann\": alice, bob\n", &mut ids)?;
+/// let css = party.cclique().unwrap_or_default();
+/// let mut names = css.iter().filter_map(|person| ids.name(person.id)).collect::<Vec<_>>();
+/// names.sort_unstable();
+/// assert_eq!(names, ["alice", "bob"]);
+/// assert_eq!(party.get(2).and_then(|mary| mary.name.as_deref()), Some("Mary Ann"));
+/// # Ok::<(), celebrities::clique::party::ParsePartyError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Identifiers {
+    /// Numbers or names, whichever the first identifier is.
+    #[default]
+    Auto,
+    /// Non-negative integers, which are the ids.
+    Numbers,
+    /// Names, the ids being the symbols they are interned as.
+    Names(Interner),
+}
+
+impl Identifiers {
+    /// The id of `identifier`, or `None` if it is not a number when numbers are expected, or an
+    /// empty name.
+    ///
+    /// Numbers may have whitespace around them, but names are taken as they are.
+    pub fn resolve(&mut self, identifier: &str) -> Option<usize> {
+        if matches!(self, Self::Auto) {
+            *self = match identifier.trim().parse::<usize>() {
+                Ok(_) => Self::Numbers,
+                Err(_) => Self::Names(Interner::new()),
+            };
+        }
+        match self {
+            Self::Auto | Self::Numbers => identifier.trim().parse().ok(),
+            Self::Names(names) => names.intern(identifier).ok().map(|symbol| symbol as usize),
+        }
+    }
+
+    /// Settles [`Auto`](Identifiers::Auto) on numbers, for an input whose first id is one.
+    pub(crate) fn numbers(&mut self) {
+        if matches!(self, Self::Auto) {
+            *self = Self::Numbers;
+        }
+    }
+
+    #[must_use]
+    pub const fn is_names(&self) -> bool {
+        matches!(self, Self::Names(_))
+    }
+
+    /// The name of the person with id `id`, when identifiers are names.
+    #[must_use]
+    pub fn name(&self, id: usize) -> Option<&str> {
+        match self {
+            Self::Names(names) => names.resolve_id(id),
+            Self::Auto | Self::Numbers => None,
+        }
+    }
+
+    /// The names of the ids, when identifiers are names.
+    #[must_use]
+    pub const fn names(&self) -> Option<&Interner> {
+        match self {
+            Self::Names(names) => Some(names),
+            Self::Auto | Self::Numbers => None,
+        }
+    }
+
+    #[must_use]
+    pub fn into_names(self) -> Option<Interner> {
+        match self {
+            Self::Names(names) => Some(names),
+            Self::Auto | Self::Numbers => None,
+        }
+    }
+
+    /// The person with id `id`, named after their identifier when it is a name.
+    #[must_use]
+    pub fn person(&self, id: usize, known_people: impl IntoIterator<Item = usize>) -> Person {
+        match self.name(id) {
+            Some(name) => Person::named(id, name, known_people),
+            None => Person::new(id, known_people),
+        }
+    }
+
+    /// How the person with id `id` was identified in the input, for error messages.
+    #[must_use]
+    pub fn describe(&self, id: usize) -> String {
+        self.name(id)
+            .map_or_else(|| id.to_string(), |name| format!("`{name}`"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_identifier_decides() {
+        let mut ids = Identifiers::Auto;
+        assert_eq!(ids.resolve(" 4"), Some(4));
+        assert_eq!(ids, Identifiers::Numbers);
+        assert_eq!(ids.resolve("alice"), None);
+
+        let mut ids = Identifiers::Auto;
+        assert_eq!(ids.resolve("alice"), Some(0));
+        // Once names, numbers are names too, and nothing is trimmed or folded.
+        assert_eq!(ids.resolve("4"), Some(1));
+        assert_eq!(ids.resolve("Alice"), Some(2));
+        assert_eq!(ids.resolve("alice "), Some(3));
+        assert_eq!(ids.resolve("alice"), Some(0));
+        assert_eq!(ids.resolve(""), None);
+        assert_eq!(ids.name(1), Some("4"));
+        assert_eq!(ids.describe(2), "`Alice`");
+        assert_eq!(Identifiers::Numbers.describe(2), "2");
+    }
+}
@@ -0,0 +1,280 @@
+use super::{Clique, DuplicateId, Party, Person};
+use std::collections::HashSet;
+
+/// A party that keeps its celebrity clique up to date as people arrive and acquaintances change.
+///
+/// A celebrity clique of the larger party, minus the newcomer, is still a celebrity clique of the
+/// smaller one, and there is at most one of those. So with `C` the current clique and `x` the
+/// newcomer, the new clique can only be:
+///
+/// - `C`, if `x` knows every celebrity and no celebrity knows `x`;
+/// - `C` and `x`, if `x` knows exactly the celebrities and everybody already knew `x`;
+/// - `x` alone, if everybody already knew `x` and `x` knows nobody at the party;
+/// - none at all, otherwise. In particular it is invalidated when `x` does not know a celebrity.
+///
+/// The clique cannot shrink: celebrities know each other, so dropping one leaves the others
+/// knowing an outsider. Each arrival takes `O(n)` lookups instead of a new search.
+///
+/// When `a` learns or forgets `b`, the clique stays unless `a` is a celebrity learning about an
+/// outsider `b`, or `b` is a celebrity forgotten by `a`. Otherwise any new clique must contain `b`
+/// after learning and `a` after forgetting, so that person is checked to be known by everyone
+/// before falling back to [`Clique::cclique_elimination`].
+///
+/// ```
+/// use celebrities::{Person, clique::party::IncrementalParty};
+///
+/// let mut party = IncrementalParty::new();
+/// assert_eq!(party.add_person(Person::new(1, [2])).map(|css| css.map(|css| css.len())), Ok(Some(1)));
+/// assert_eq!(party.add_person(Person::new(2, [1])).map(|css| css.map(|css| css.len())), Ok(Some(2)));
+/// assert_eq!(party.add_person(Person::new(3, [1])).map(|css| css.is_some()), Ok(false));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalParty {
+    party: Party,
+    cclique: Option<HashSet<Person>>,
+}
+
+impl IncrementalParty {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub const fn party(&self) -> &Party {
+        &self.party
+    }
+
+    /// The celebrity clique of everybody who arrived so far.
+    #[must_use]
+    pub const fn cclique(&self) -> Option<&HashSet<Person>> {
+        self.cclique.as_ref()
+    }
+
+    /// Lets `newcomer` in and returns the updated celebrity clique.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`DuplicateId`], leaving the party untouched, if somebody with the same id is already there.
+    pub fn add_person(
+        &mut self,
+        newcomer: Person,
+    ) -> Result<Option<&HashSet<Person>>, DuplicateId> {
+        if self.party.contains_id(newcomer.id) {
+            return Err(DuplicateId(newcomer.id));
+        }
+
+        let known_by_all = self
+            .party
+            .iter()
+            .all(|guest| guest.known_people.contains(&newcomer.id));
+        let knows = |guest: &Person| newcomer.known_people.contains(&guest.id);
+        self.cclique = match self.cclique.take() {
+            Some(mut css) if css.iter().all(knows) => {
+                let known_by_celebrity = css
+                    .iter()
+                    .any(|celebrity| celebrity.known_people.contains(&newcomer.id));
+                let knows_outsider = self
+                    .party
+                    .iter()
+                    .any(|guest| knows(guest) && !css.contains(guest));
+                if !known_by_celebrity {
+                    Some(css)
+                } else if known_by_all && !knows_outsider {
+                    css.insert(newcomer.clone());
+                    Some(css)
+                } else {
+                    None
+                }
+            }
+            _ if known_by_all && !self.party.iter().any(knows) => {
+                Some(HashSet::from([newcomer.clone()]))
+            }
+            _ => None,
+        };
+        self.party.insert(newcomer);
+        Ok(self.cclique.as_ref())
+    }
+
+    /// Records that guest `who` now knows `whom` and returns the updated celebrity clique.
+    pub fn learn(&mut self, who: usize, whom: usize) -> Option<&HashSet<Person>> {
+        if self.party.learn(who, whom) && self.party.contains_id(whom) {
+            let evicted = self
+                .cclique
+                .as_ref()
+                .is_none_or(|css| css.contains(&who) && !css.contains(&whom));
+            if evicted {
+                self.resolve_around(whom);
+            }
+        }
+        self.refresh(who)
+    }
+
+    /// Records that guest `who` no longer knows `whom` and returns the updated celebrity clique.
+    pub fn forget(&mut self, who: usize, whom: usize) -> Option<&HashSet<Person>> {
+        if self.party.forget(who, whom) && self.party.contains_id(whom) {
+            let evicted = self.cclique.as_ref().is_none_or(|css| css.contains(&whom));
+            if evicted {
+                self.resolve_around(who);
+            }
+        }
+        self.refresh(who)
+    }
+
+    /// Solves again, knowing that the clique must contain `id`.
+    fn resolve_around(&mut self, id: usize) {
+        let known_by_all = self.party.iter().all(|guest| guest.knows_id(&id));
+        self.cclique = if known_by_all {
+            self.party.cclique_elimination().map(HashSet::from)
+        } else {
+            None
+        };
+    }
+
+    /// Replaces the celebrity `id`, if they are one, with their current record.
+    fn refresh(&mut self, id: usize) -> Option<&HashSet<Person>> {
+        if let (Some(css), Some(person)) = (self.cclique.as_mut(), self.party.get(id))
+            && css.contains(person)
+        {
+            css.replace(person.clone());
+        }
+        self.cclique.as_ref()
+    }
+}
+
+/// Solves the party once, with [`Clique::cclique_elimination`].
+impl From<Party> for IncrementalParty {
+    fn from(party: Party) -> Self {
+        let cclique = party.cclique_elimination().map(HashSet::from);
+        Self { party, cclique }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::{Rng, planted_party, sample_parties};
+
+    fn ids(css: Option<&HashSet<Person>>) -> Option<HashSet<usize>> {
+        Some(css?.iter().map(|person| person.id).collect())
+    }
+
+    #[test]
+    fn test_keeps_clique() {
+        let mut party = IncrementalParty::from(Party::demo());
+        assert_eq!(ids(party.cclique()), Some(HashSet::from([1, 2, 3])));
+        let css = party.add_person(Person::new(8, [1, 2, 3, 4]));
+        assert_eq!(css.map(ids), Ok(Some(HashSet::from([1, 2, 3]))));
+    }
+
+    #[test]
+    fn test_invalidates_clique() {
+        let mut party = IncrementalParty::from(Party::demo());
+        let css = party.add_person(Person::new(8, [1, 2]));
+        assert_eq!(css.map(ids), Ok(None));
+    }
+
+    #[test]
+    fn test_joins_clique() {
+        let mut party = IncrementalParty::from(crate::party! {
+            1 => [2, 3];
+            2 => [1, 3];
+            4 => [1, 2, 3];
+        });
+        // 3 is not at the party yet, so knowing them does not count against 1 and 2.
+        assert_eq!(ids(party.cclique()), Some(HashSet::from([1, 2])));
+        let css = party.add_person(Person::new(3, [1, 2]));
+        assert_eq!(css.map(ids), Ok(Some(HashSet::from([1, 2, 3]))));
+    }
+
+    #[test]
+    fn test_gains_clique() {
+        let mut party = IncrementalParty::from(crate::party! { 1 => [2, 9]; 2 => [1] });
+        assert_eq!(ids(party.cclique()), Some(HashSet::from([1, 2])));
+        assert_eq!(party.add_person(Person::new(3, [])).map(ids), Ok(None));
+        let css = party.add_person(Person::new(9, []));
+        assert_eq!(css.map(ids), Ok(None));
+
+        let mut party = IncrementalParty::from(crate::party! { 1 => [3]; 2 => [3] });
+        assert_eq!(ids(party.cclique()), None);
+        let css = party.add_person(Person::new(3, []));
+        assert_eq!(css.map(ids), Ok(Some(HashSet::from([3]))));
+    }
+
+    #[test]
+    fn test_duplicate() {
+        let mut party = IncrementalParty::from(Party::demo());
+        assert_eq!(party.add_person(Person::new(4, [])), Err(DuplicateId(4)));
+        assert_eq!(party.party().len(), 7);
+    }
+
+    #[test]
+    fn test_learn_and_forget() {
+        let mut party = IncrementalParty::from(Party::demo());
+        // A guest learning about a celebrity changes nothing, nor does one outside the party.
+        assert_eq!(ids(party.learn(4, 1)), Some(HashSet::from([1, 2, 3])));
+        assert_eq!(ids(party.learn(1, 42)), Some(HashSet::from([1, 2, 3])));
+        assert!(
+            party
+                .cclique()
+                .and_then(|css| css.get(&1))
+                .is_some_and(|person| person.known_people.contains(&42))
+        );
+        // A celebrity learning about an outsider evicts everybody.
+        assert_eq!(ids(party.learn(1, 4)), None);
+        // Until the outsider is known by everyone.
+        for who in [2, 3, 6] {
+            assert_eq!(ids(party.learn(who, 4)), None);
+        }
+        assert_eq!(ids(party.learn(7, 4)), Some(HashSet::from([1, 2, 3, 4])));
+        // Forgetting a celebrity drops the clique.
+        assert_eq!(ids(party.forget(4, 3)), None);
+        assert_eq!(ids(party.learn(4, 3)), Some(HashSet::from([1, 2, 3, 4])));
+        assert_eq!(ids(party.forget(9, 3)), Some(HashSet::from([1, 2, 3, 4])));
+    }
+
+    #[test]
+    fn test_forget_creates_clique() {
+        let mut party = IncrementalParty::from(crate::party! { 1 => [2]; 2 => [1]; 3 => [1, 2] });
+        assert!(party.learn(1, 3).is_none());
+        assert_eq!(ids(party.forget(1, 3)), Some(HashSet::from([1, 2])));
+    }
+
+    #[test]
+    fn test_events_agree_with_recomputation() {
+        let mut rng = Rng::new(39);
+        for round in 0..100 {
+            let n = round % 7 + 1;
+            let guests = planted_party(n, 1 + rng.below(n), 0.7, rng.next_u64());
+            let mut party = IncrementalParty::from(Party::from(guests));
+            for _ in 0..40 {
+                let who = rng.below(n + 1);
+                let whom = rng.below(n + 1);
+                let incremental = if rng.chance(0.5) {
+                    ids(party.learn(who, whom))
+                } else {
+                    ids(party.forget(who, whom))
+                };
+                let from_scratch = party.party().cclique().map(|css| css.ids());
+                assert_eq!(
+                    incremental,
+                    from_scratch,
+                    "{who} {whom} {:?}",
+                    party.party()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_recomputation() {
+        for guests in sample_parties(200, 8, 0.7, 38) {
+            let mut party = IncrementalParty::new();
+            for person in Party::from(guests).sorted() {
+                let incremental = party.add_person(person).map(ids);
+                let from_scratch = party.party().cclique().map(|css| css.ids());
+                assert_eq!(incremental, Ok(from_scratch), "{:?}", party.party());
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use super::{Party, Person};
+use std::collections::{HashMap, HashSet};
+
+/// Who knows whom, the other way around: each guest's id mapped to the ids of the other guests who know them.
+///
+/// Building it takes one pass over every acquaintance, after which checking that somebody is
+/// known by everyone is a length comparison. Acquaintances who are not at the party are left out.
+///
+/// ```
+/// use celebrities::{clique::party::KnownBy, party};
+///
+/// let party = party! { 1 <=> 2; 3 => [1, 2, 42] };
+/// let index = KnownBy::new(&party);
+/// assert!(index.is_known_by_everyone(1));
+/// assert!(!index.is_known_by_everyone(3));
+/// assert!(index.is_cclique(party.iter().filter(|person| person.id != 3), &party));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KnownBy {
+    known_by: HashMap<usize, HashSet<usize>>,
+}
+
+impl KnownBy {
+    #[must_use]
+    pub fn new(party: &Party) -> Self {
+        let mut known_by = party
+            .iter()
+            .map(|person| (person.id, HashSet::new()))
+            .collect::<HashMap<_, _>>();
+        for person in party {
+            for &known in &person.known_people {
+                if known != person.id
+                    && let Some(knowers) = known_by.get_mut(&known)
+                {
+                    knowers.insert(person.id);
+                }
+            }
+        }
+        Self { known_by }
+    }
+
+    /// The other guests who know guest `id`.
+    #[must_use]
+    pub fn known_by(&self, id: usize) -> Option<&HashSet<usize>> {
+        self.known_by.get(&id)
+    }
+
+    #[must_use]
+    pub fn is_known_by_everyone(&self, id: usize) -> bool {
+        self.known_by(id)
+            .is_some_and(|knowers| knowers.len() + 1 == self.known_by.len())
+    }
+
+    /// Like [`Clique::is_cclique`](super::Clique::is_cclique) for the party this index was built from.
+    ///
+    /// Every celebrity must be known by everyone, which the index answers directly, and must know
+    /// no guest outside `celebrities`.
+    pub fn is_cclique<'a, C>(&self, celebrities: C, party: &Party) -> bool
+    where
+        C: IntoIterator<Item = &'a Person> + Clone,
+    {
+        let others = celebrities.clone();
+        let is_celebrity = |id: usize| others.clone().into_iter().any(|c| c.id == id);
+        celebrities.into_iter().all(|celebrity| {
+            self.is_known_by_everyone(celebrity.id)
+                && celebrity
+                    .known_people
+                    .iter()
+                    .all(|&known| !party.contains_id(known) || is_celebrity(known))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Clique,
+        generator::{Rng, sample_parties},
+    };
+
+    #[test]
+    fn test_index() {
+        let party = crate::party! { 1 => [2, 3, 42]; 2 => [1]; 3 => [] };
+        let index = KnownBy::new(&party);
+        assert_eq!(index.known_by(1), Some(&HashSet::from([2])));
+        assert_eq!(index.known_by(3), Some(&HashSet::from([1])));
+        assert_eq!(index.known_by(42), None);
+        assert!(!index.is_known_by_everyone(42));
+        assert!(KnownBy::new(&crate::party! { 1 => [] }).is_known_by_everyone(1));
+    }
+
+    #[test]
+    fn test_agrees_with_is_cclique() {
+        let mut rng = Rng::new(40);
+        for party in sample_parties(200, 9, 0.7, 40).map(Party::from) {
+            let index = KnownBy::new(&party);
+            for _ in 0..20 {
+                let group = party
+                    .iter()
+                    .filter(|_| rng.chance(0.5))
+                    .cloned()
+                    .collect::<HashSet<_>>();
+                assert_eq!(
+                    index.is_cclique(&group, &party),
+                    group.is_cclique(party.as_set()),
+                    "{party:?}"
+                );
+            }
+            assert_eq!(party.cclique_indexed(), party.cclique(), "{party:?}");
+        }
+    }
+}
@@ -0,0 +1,499 @@
+use super::{Identifiers, Party, Person};
+use crate::clique::person::{ParsePersonError, split_line, tokens};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Why a text could not be read as a [`Party`]. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePartyError {
+    /// The line is not a valid [`Person`].
+    Person {
+        line: usize,
+        error: ParsePersonError,
+    },
+    /// The person on this line was already described on an earlier one.
+    DuplicateId { line: usize, id: usize },
+    /// Like [`DuplicateId`](ParsePartyError::DuplicateId), for a person known by name.
+    DuplicateName { line: usize, name: String },
+}
+
+impl ParsePartyError {
+    /// The line the problem was found on.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        match self {
+            Self::Person { line, .. }
+            | Self::DuplicateId { line, .. }
+            | Self::DuplicateName { line, .. } => *line,
+        }
+    }
+}
+
+impl Display for ParsePartyError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Person { line, error } => write!(f, "line {line}: {error}"),
+            Self::DuplicateId { line, id } => {
+                write!(f, "line {line}: person {id} is described more than once")
+            }
+            Self::DuplicateName { line, name } => {
+                write!(f, "line {line}: `{name}` is described more than once")
+            }
+        }
+    }
+}
+
+impl Error for ParsePartyError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Person { error, .. } => Some(error),
+            Self::DuplicateId { .. } | Self::DuplicateName { .. } => None,
+        }
+    }
+}
+
+/// Why a text could not be read as a 0/1 matrix by [`Party::from_matrix`]. Rows and columns are
+/// numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMatrixError {
+    /// An entry of `row` is something other than `0` or `1`.
+    InvalidEntry {
+        row: usize,
+        column: usize,
+        found: String,
+    },
+    /// `row` does not have as many entries as the first one.
+    RowLength {
+        row: usize,
+        len: usize,
+        expected: usize,
+    },
+    /// There are not as many rows as columns.
+    NotSquare { rows: usize, columns: usize },
+}
+
+impl Display for ParseMatrixError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidEntry { row, column, found } => {
+                write!(f, "row {row}: `{found}` in column {column} is not 0 or 1")
+            }
+            Self::RowLength { row, len, expected } => write!(
+                f,
+                "row {row}: {len} entries, but the first row has {expected}"
+            ),
+            Self::NotSquare { rows, columns } => write!(
+                f,
+                "{rows} rows of {columns} entries; the matrix must be square"
+            ),
+        }
+    }
+}
+
+impl Error for ParseMatrixError {}
+
+/// Why a text could not be read as edges by [`Party::from_edges`]. Lines are numbered from 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEdgesError {
+    /// The line is not two ids.
+    NotAnEdge { line: usize, found: String },
+    /// One of the two tokens on the line is not an id.
+    InvalidId { line: usize, token: String },
+}
+
+impl ParseEdgesError {
+    /// The line the problem was found on.
+    #[must_use]
+    pub const fn line(&self) -> usize {
+        match self {
+            Self::NotAnEdge { line, .. } | Self::InvalidId { line, .. } => *line,
+        }
+    }
+}
+
+impl Display for ParseEdgesError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotAnEdge { line, found } => {
+                write!(f, "line {line}: expected two ids, `a b`, found `{found}`")
+            }
+            Self::InvalidId { line, token } => write!(f, "line {line}: `{token}` is not an id"),
+        }
+    }
+}
+
+impl Error for ParseEdgesError {}
+
+/// Whether every line of `text` that is not blank or a `#` comment is two tokens that cannot be
+/// an `id: known, ...` line, so that [`Party::from_edges`] is the likelier reading of it.
+#[must_use]
+pub fn looks_like_edges(text: &str) -> bool {
+    let mut lines = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .peekable();
+    lines.peek().is_some()
+        && lines.all(|line| {
+            split_line(line).is_err()
+                && tokens(line, char::is_whitespace).is_ok_and(|tokens| tokens.len() == 2)
+        })
+}
+
+/// Calls `person` with the number, id and acquaintances of each `id: known, ...` line of `text`,
+/// resolved with `ids`. The acquaintances are as written, so they may include the id itself.
+pub(super) fn read_lines<F>(
+    text: &str,
+    ids: &mut Identifiers,
+    mut person: F,
+) -> Result<(), ParsePartyError>
+where
+    F: FnMut(usize, usize, Vec<usize>, &Identifiers) -> Result<(), ParsePartyError>,
+{
+    for (line, text) in (1..).zip(text.lines()) {
+        let trimmed = text.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let invalid = |error| ParsePartyError::Person { line, error };
+        let mut resolve = |(offset, token): (usize, &str)| {
+            ids.resolve(token).ok_or_else(|| {
+                invalid(ParsePersonError::InvalidId {
+                    offset,
+                    token: token.to_owned(),
+                })
+            })
+        };
+        let (id, known) = split_line(text).map_err(invalid)?;
+        let id = resolve(id)?;
+        let known = (known.into_iter())
+            .map(resolve)
+            .collect::<Result<Vec<_>, _>>()?;
+        person(line, id, known, ids)?;
+    }
+    Ok(())
+}
+
+/// Reads a party with one [`Person`] per line, in the `id: known, ...` format.
+///
+/// Blank lines and lines starting with `#` are skipped. Each guest must be described once.
+///
+/// ```
+/// use celebrities::{Clique, Party};
+/// use std::collections::HashSet;
+///
+/// let party = "# the demo\n1: 2\n2: 1\n\n3: 1, 2\n".parse::<Party>()?;
+/// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([1, 2])));
+///
+/// let err = "1: 2\n2: x".parse::<Party>().unwrap_err();
+/// assert_eq!(err.line(), 2);
+/// # Ok::<(), celebrities::clique::party::ParsePartyError>(())
+/// ```
+impl FromStr for Party {
+    type Err = ParsePartyError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Self::parse_text(text, &mut Identifiers::Numbers)
+    }
+}
+
+impl Party {
+    /// Like [`from_str`](str::parse), reading the identifiers with `ids`, so that they may be
+    /// names. Names with commas or whitespace are written in double quotes, as in
+    /// `"Mary Ann": alice, "Bob Jones"`.
+    ///
+    /// # Errors
+    ///
+    /// As with [`from_str`](str::parse).
+    pub fn parse_text(text: &str, ids: &mut Identifiers) -> Result<Self, ParsePartyError> {
+        let mut party = Self::new();
+        read_lines(text, ids, |line, id, known, ids| {
+            if party.insert(ids.person(id, known)) {
+                return Ok(());
+            }
+            Err(ids
+                .name(id)
+                .map_or(ParsePartyError::DuplicateId { line, id }, |name| {
+                    ParsePartyError::DuplicateName {
+                        line,
+                        name: name.to_owned(),
+                    }
+                }))
+        })?;
+        Ok(party)
+    }
+
+    /// Reads a party from `n` rows of `n` whitespace-separated `0`s and `1`s, where a `1` in row
+    /// `i` and column `j` means that person `i` knows person `j`, for ids from 1 to `n`.
+    ///
+    /// The diagonal is ignored, and blank lines and lines starting with `#` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Fails on entries other than `0` and `1`, and on matrices that are not square.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Party};
+    /// use std::collections::HashSet;
+    ///
+    /// let party = Party::from_matrix("1 1 0\n1 0 0\n1 1 1\n")?;
+    /// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([1, 2])));
+    /// # Ok::<(), celebrities::clique::party::ParseMatrixError>(())
+    /// ```
+    pub fn from_matrix(text: &str) -> Result<Self, ParseMatrixError> {
+        let rows = text.lines().filter(|line| {
+            let trimmed = line.trim_start();
+            !trimmed.is_empty() && !trimmed.starts_with('#')
+        });
+        let mut people = Vec::new();
+        let mut columns = None;
+        for (row, line) in (1..).zip(rows) {
+            let mut known = Vec::new();
+            let mut len = 0;
+            for (column, entry) in (1..).zip(line.split_whitespace()) {
+                match entry {
+                    "0" => {}
+                    "1" => known.push(column),
+                    found => {
+                        return Err(ParseMatrixError::InvalidEntry {
+                            row,
+                            column,
+                            found: found.to_owned(),
+                        });
+                    }
+                }
+                len = column;
+            }
+            let expected = *columns.get_or_insert(len);
+            if len != expected {
+                return Err(ParseMatrixError::RowLength { row, len, expected });
+            }
+            people.push(Person::new(row, known));
+        }
+        let columns = columns.unwrap_or(0);
+        if people.len() != columns {
+            return Err(ParseMatrixError::NotSquare {
+                rows: people.len(),
+                columns,
+            });
+        }
+        Ok(people.into_iter().collect())
+    }
+
+    /// Reads a party with one `a b` line per edge, meaning that `a` knows `b`.
+    ///
+    /// Everybody on either end of an edge is a guest, and the edges of each are merged, so an edge
+    /// may be given twice. Blank lines and lines starting with `#` are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Fails on lines that are not two ids.
+    ///
+    /// ```
+    /// use celebrities::{Clique, Party};
+    /// use std::collections::HashSet;
+    ///
+    /// // Nobody says whom 3 knows, but they are at the party.
+    /// let party = Party::from_edges("# fans\n1 3\n2 3\n2 3\n")?;
+    /// assert_eq!(party.len(), 3);
+    /// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([3])));
+    /// # Ok::<(), celebrities::clique::party::ParseEdgesError>(())
+    /// ```
+    pub fn from_edges(text: &str) -> Result<Self, ParseEdgesError> {
+        Self::parse_edges(text, &mut Identifiers::Numbers)
+    }
+
+    /// Like [`from_edges`](Party::from_edges), reading the identifiers with `ids`, so that they
+    /// may be names, in double quotes if they have whitespace.
+    ///
+    /// # Errors
+    ///
+    /// As with [`from_edges`](Party::from_edges).
+    pub fn parse_edges(text: &str, ids: &mut Identifiers) -> Result<Self, ParseEdgesError> {
+        let mut party = Self::new();
+        for (line, text) in (1..).zip(text.lines()) {
+            let trimmed = text.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            let not_an_edge = || ParseEdgesError::NotAnEdge {
+                line,
+                found: trimmed.to_owned(),
+            };
+            let mut id = |token: &str| {
+                ids.resolve(token)
+                    .ok_or_else(|| ParseEdgesError::InvalidId {
+                        line,
+                        token: token.to_owned(),
+                    })
+            };
+            let tokens = tokens(trimmed, char::is_whitespace).map_err(|_| not_an_edge())?;
+            let (who, whom) = match tokens[..] {
+                [(_, who), (_, whom)] => (id(who)?, id(whom)?),
+                _ => return Err(not_an_edge()),
+            };
+            party.merge(ids.person(who, [whom]));
+            party.merge(ids.person(whom, []));
+        }
+        Ok(party)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skips_blank_lines_and_comments() {
+        let party = "\n# guests\n  # indented\n1: 2, 3\r\n\t\n2: 1\n3:".parse::<Party>();
+        assert_eq!(
+            party.map(|party| party.to_adjacency()),
+            Ok(crate::party! { 1 => [2, 3]; 2 => [1]; 3 => [] }.to_adjacency())
+        );
+        assert_eq!("".parse::<Party>(), Ok(Party::new()));
+    }
+
+    #[test]
+    fn test_errors_name_the_line() {
+        assert_eq!(
+            "1: 2\n\n3 4".parse::<Party>(),
+            Err(ParsePartyError::Person {
+                line: 3,
+                error: ParsePersonError::MissingSeparator { offset: 1 }
+            })
+        );
+        assert_eq!(
+            "1: 2\n2: 1\n1: 3".parse::<Party>(),
+            Err(ParsePartyError::DuplicateId { line: 3, id: 1 })
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        let text = "alice: bob, \"Mary Ann\"\n\"Mary Ann\" -> bob\nbob:\nMary Ann: alice\n";
+        let mut ids = Identifiers::Auto;
+        let err = Party::parse_text(text, &mut ids).expect_err("Mary Ann is described twice");
+        assert_eq!(
+            err,
+            ParsePartyError::DuplicateName {
+                line: 4,
+                name: "Mary Ann".to_owned()
+            }
+        );
+
+        let mut ids = Identifiers::Auto;
+        let party = Party::parse_text(&text.replace("Mary Ann: alice\n", ""), &mut ids);
+        assert_eq!(
+            party.map(|party| party.to_adjacency()),
+            Ok(crate::party! { 0 => [1, 2]; 2 => [1]; 1 => [] }.to_adjacency())
+        );
+        assert_eq!(ids.name(2), Some("Mary Ann"));
+
+        // The first identifier is a number, so `two` is a mistake rather than a name.
+        assert!(matches!(
+            Party::parse_text("1: 2\n2: two\n", &mut Identifiers::Auto),
+            Err(ParsePartyError::Person { line: 2, .. })
+        ));
+        assert_eq!(
+            Party::parse_text("\"alice: bob\n", &mut Identifiers::Auto),
+            Err(ParsePartyError::Person {
+                line: 1,
+                error: ParsePersonError::UnterminatedQuote { offset: 0 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_named_edges() {
+        let mut ids = Identifiers::Auto;
+        let party = Party::parse_edges("\"Mary Ann\" bob\nbob \"Mary Ann\"\ncarol bob\n", &mut ids);
+        assert_eq!(
+            party.map(|party| party.to_adjacency()),
+            Ok(crate::party! { 0 => [1]; 1 => [0]; 2 => [1] }.to_adjacency())
+        );
+        assert_eq!(ids.name(0), Some("Mary Ann"));
+        assert!(looks_like_edges("\"Mary Ann\" bob\n"));
+        assert!(looks_like_edges("\"Mary: Ann\" bob\n"));
+    }
+
+    #[test]
+    fn test_edges() {
+        // 4 is only ever somebody else's acquaintance, and 1 -> 2 is given twice.
+        let party = Party::from_edges("1 2\n  # again\n1\t2\n\n2 1\r\n3 4\n1 1\n");
+        assert_eq!(
+            party.map(|party| party.to_adjacency()),
+            Ok(crate::party! { 1 => [2]; 2 => [1]; 3 => [4]; 4 => [] }.to_adjacency())
+        );
+        assert_eq!(
+            Party::from_edges("1 2\n1 2 3\n"),
+            Err(ParseEdgesError::NotAnEdge {
+                line: 2,
+                found: "1 2 3".to_owned()
+            })
+        );
+        assert_eq!(
+            Party::from_edges("1 two").map_err(|err| err.to_string()),
+            Err("line 1: `two` is not an id".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_looks_like_edges() {
+        assert!(looks_like_edges("# edges\n1 2\n\n2 3\n"));
+        for text in [
+            "1: 2\n",
+            "1 -> 2\n",
+            "1 2\n3: 4\n",
+            "1\n",
+            "1 2 3\n",
+            "# nothing\n",
+            "",
+        ] {
+            assert!(!looks_like_edges(text), "{text:?}");
+        }
+    }
+
+    #[test]
+    fn test_matrix() {
+        let party = Party::from_matrix("# who knows whom\n0 1 1\n\n1 1 0\n0  0\t0\n");
+        assert_eq!(
+            party.map(|party| party.to_adjacency()),
+            Ok(crate::party! { 1 => [2, 3]; 2 => [1]; 3 => [] }.to_adjacency())
+        );
+        assert_eq!(Party::from_matrix("\n"), Ok(Party::new()));
+    }
+
+    #[test]
+    fn test_matrix_errors() {
+        assert_eq!(
+            Party::from_matrix("0 1\n1 x\n"),
+            Err(ParseMatrixError::InvalidEntry {
+                row: 2,
+                column: 2,
+                found: "x".to_owned()
+            })
+        );
+        assert_eq!(
+            Party::from_matrix("0 1\n1 0 1\n"),
+            Err(ParseMatrixError::RowLength {
+                row: 2,
+                len: 3,
+                expected: 2
+            })
+        );
+        assert_eq!(
+            Party::from_matrix("0 1\n"),
+            Err(ParseMatrixError::NotSquare {
+                rows: 1,
+                columns: 2
+            })
+        );
+        assert!(matches!(
+            Party::from_matrix("01\n10\n"),
+            Err(ParseMatrixError::InvalidEntry { row: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use super::{Identifiers, KnownBy, ParsePartyError, Party, parse::read_lines};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Something wrong with the data of a party that does not stop it from being solved, as
+/// [`Party::problems`] and [`Party::check_text`] find them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Problem {
+    /// Nobody is at the party.
+    Empty,
+    /// `id` is not at the party, but the guests `known_by` know them.
+    Stranger { id: usize, known_by: Vec<usize> },
+    /// Guest `id` knows nobody at the party, and nobody there knows them.
+    Isolated { id: usize },
+    /// The line describing `id` lists them among the people they know.
+    SelfReference { line: usize, id: usize },
+    /// `id` was already described on line `first`. Their acquaintances are merged.
+    Duplicate {
+        line: usize,
+        first: usize,
+        id: usize,
+    },
+}
+
+impl Party {
+    /// The problems with the guests and their acquaintances: strangers in increasing id order, then
+    /// isolated guests in increasing id order.
+    ///
+    /// ```
+    /// use celebrities::{clique::party::Problem, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => []; 4 => [1, 42] };
+    /// assert_eq!(
+    ///     party.problems(),
+    ///     [
+    ///         Problem::Stranger { id: 42, known_by: vec![4] },
+    ///         Problem::Isolated { id: 3 },
+    ///     ]
+    /// );
+    /// ```
+    #[must_use]
+    pub fn problems(&self) -> Vec<Problem> {
+        if self.is_empty() {
+            return vec![Problem::Empty];
+        }
+        let mut strangers = BTreeMap::<_, Vec<_>>::new();
+        for person in self {
+            for id in person.known_iter().filter(|&id| !self.contains_id(id)) {
+                strangers.entry(id).or_default().push(person.id);
+            }
+        }
+        let known_by = KnownBy::new(self);
+        let mut isolated = (self.iter())
+            .filter(|person| person.known_iter().all(|id| !self.contains_id(id)))
+            .filter(|person| known_by.known_by(person.id).is_none_or(HashSet::is_empty))
+            .map(|person| person.id)
+            .collect::<Vec<_>>();
+        isolated.sort_unstable();
+
+        let strangers = strangers.into_iter().map(|(id, mut known_by)| {
+            known_by.sort_unstable();
+            Problem::Stranger { id, known_by }
+        });
+        strangers
+            .chain(isolated.into_iter().map(|id| Problem::Isolated { id }))
+            .collect()
+    }
+
+    /// Like [`parse_text`](Party::parse_text), but merging the people described more than once
+    /// instead of failing, and also returning the problems found in the text along the way, in
+    /// line order, followed by the [`problems`](Party::problems) of the party.
+    ///
+    /// # Errors
+    ///
+    /// On lines that are not valid [`Person`](crate::Person)s.
+    ///
+    /// ```
+    /// use celebrities::{Party, clique::party::{Identifiers, Problem}};
+    ///
+    /// let (party, problems) = Party::check_text("1: 1, 2\n2: 1\n1: 3\n", &mut Identifiers::Auto)?;
+    /// assert_eq!(party.len(), 2);
+    /// assert_eq!(
+    ///     problems,
+    ///     [
+    ///         Problem::SelfReference { line: 1, id: 1 },
+    ///         Problem::Duplicate { line: 3, first: 1, id: 1 },
+    ///         Problem::Stranger { id: 3, known_by: vec![1] },
+    ///     ]
+    /// );
+    /// # Ok::<(), celebrities::clique::party::ParsePartyError>(())
+    /// ```
+    pub fn check_text(
+        text: &str,
+        ids: &mut Identifiers,
+    ) -> Result<(Self, Vec<Problem>), ParsePartyError> {
+        let mut party = Self::new();
+        let mut problems = Vec::new();
+        let mut lines = HashMap::new();
+        read_lines(text, ids, |line, id, known, ids| {
+            if known.contains(&id) {
+                problems.push(Problem::SelfReference { line, id });
+            }
+            if let Some(&first) = lines.get(&id) {
+                problems.push(Problem::Duplicate { line, first, id });
+            } else {
+                lines.insert(id, line);
+            }
+            party.merge(ids.person(id, known));
+            Ok(())
+        })?;
+        problems.extend(party.problems());
+        Ok((party, problems))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::party;
+
+    #[test]
+    fn test_problems() {
+        assert_eq!(Party::new().problems(), [Problem::Empty]);
+        assert_eq!(party! { 1 <=> 2; 3 => [1, 2] }.problems(), []);
+        // Knowing only strangers is knowing nobody at the party.
+        assert_eq!(
+            party! { 1 => [7, 5]; 2 => [7]; 3 => [2] }.problems(),
+            [
+                Problem::Stranger {
+                    id: 5,
+                    known_by: vec![1]
+                },
+                Problem::Stranger {
+                    id: 7,
+                    known_by: vec![1, 2]
+                },
+                Problem::Isolated { id: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_check_text() {
+        let text = "# a comment\n2: 2\n\n3: 2, 3\n2: 3\n";
+        let (party, problems) =
+            Party::check_text(text, &mut Identifiers::Numbers).expect("a valid party");
+        assert_eq!(
+            party.get(2).map(|person| person.known_iter().count()),
+            Some(1)
+        );
+        assert_eq!(
+            problems,
+            [
+                Problem::SelfReference { line: 2, id: 2 },
+                Problem::SelfReference { line: 4, id: 3 },
+                Problem::Duplicate {
+                    line: 5,
+                    first: 2,
+                    id: 2
+                },
+            ]
+        );
+
+        let mut ids = Identifiers::Auto;
+        let (_, problems) = Party::check_text("alice: bob\nbob: alice\n", &mut ids).expect("names");
+        assert_eq!(problems, []);
+        assert!(Party::check_text("1: 2\n2: x\n", &mut Identifiers::Auto).is_err());
+    }
+}
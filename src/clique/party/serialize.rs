@@ -0,0 +1,371 @@
+use super::{Identifiers, Party, Person};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, SeqAccess, Unexpected, Visitor},
+};
+use std::fmt::{self, Formatter};
+
+/// A person as `{"id": 1, "name": "Alice", "knows": [2, 3]}`, with acquaintances in increasing
+/// order.
+#[derive(Serialize)]
+struct Record {
+    id: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    knows: Vec<usize>,
+}
+
+/// A party as `{"people": [...]}`.
+#[derive(Serialize)]
+struct Records {
+    people: Vec<Record>,
+}
+
+/// Serialized as `{"id": 1, "name": "Alice", "knows": [2, 3]}`; see [`Party`]'s implementation.
+impl Serialize for Person {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Record::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Person {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PersonSeed(&mut Identifiers::Numbers).deserialize(deserializer)
+    }
+}
+
+/// Serialized as `{"people": [{"id": 1, "knows": [2, 3]}, ...]}`, people in increasing id order.
+///
+/// Deserializing accepts numeric strings for ids, ignores unknown fields, and rejects people
+/// described twice. To read names rather than ids, deserialize with a `&mut`
+/// [`Identifiers`] as the seed.
+///
+/// ```
+/// use celebrities::{Clique, Party, clique::party::Identifiers};
+/// use serde::de::DeserializeSeed;
+/// use std::collections::HashSet;
+///
+/// let json = r#"{"people": [{"id": 1, "knows": [2]}, {"id": "2", "knows": ["1"]}, {"id": 3, "knows": [1, 2], "age": 40}]}"#;
+/// let party = serde_json::from_str::<Party>(json)?;
+/// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([1, 2])));
+///
+/// let err = serde_json::from_str::<Party>(r#"{"people": [{"id": 1}, {"id": 1}]}"#).unwrap_err();
+/// assert!(err.to_string().contains("person 1 is described more than once"));
+///
+/// let json = r#"{"people": [{"id": "Ada", "knows": ["Alan"]}, {"id": "Alan", "knows": ["Ada"]}]}"#;
+/// let mut ids = Identifiers::Auto;
+/// let party = (&mut ids).deserialize(&mut serde_json::Deserializer::from_str(json))?;
+/// assert_eq!(party.cclique().map(|css| css.len()), Some(2));
+/// assert_eq!(ids.name(1), Some("Alan"));
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+impl Serialize for Party {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let people = self.sorted().iter().map(Record::from).collect();
+        Records { people }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Party {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        (&mut Identifiers::Numbers).deserialize(deserializer)
+    }
+}
+
+/// Reads a [`Party`] as its [`Deserialize`] implementation does, with the ids read by these
+/// [`Identifiers`], so that they may be names.
+impl<'de> DeserializeSeed<'de> for &mut Identifiers {
+    type Value = Party;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Party, D::Error> {
+        deserializer.deserialize_struct("Party", &["people"], PartyVisitor(self))
+    }
+}
+
+impl From<&Person> for Record {
+    fn from(person: &Person) -> Self {
+        let mut knows = person.known_iter().collect::<Vec<_>>();
+        knows.sort_unstable();
+        Self {
+            id: person.id,
+            name: person.name.clone(),
+            knows,
+        }
+    }
+}
+
+struct PartyVisitor<'a>(&'a mut Identifiers);
+
+impl<'de> Visitor<'de> for PartyVisitor<'_> {
+    type Value = Party;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a party, as `{\"people\": [...]}`")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Party, A::Error> {
+        let mut party = None;
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "people" {
+                party = Some(map.next_value_seed(PeopleSeed(&mut *self.0))?);
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        party.ok_or_else(|| de::Error::missing_field("people"))
+    }
+}
+
+/// The list of people, each described once.
+struct PeopleSeed<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for PeopleSeed<'_> {
+    type Value = Party;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Party, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PeopleSeed<'_> {
+    type Value = Party;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a list of people")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Party, A::Error> {
+        let mut party = Party::new();
+        while let Some(person) = seq.next_element_seed(PersonSeed(&mut *self.0))? {
+            let id = person.id;
+            if !party.insert(person) {
+                return Err(de::Error::custom(format!(
+                    "person {} is described more than once",
+                    self.0.describe(id)
+                )));
+            }
+        }
+        Ok(party)
+    }
+}
+
+/// A person as `{"id": 1, "name": "Alice", "knows": [2, 3]}`, where only the id is needed and
+/// other fields are ignored.
+struct PersonSeed<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for PersonSeed<'_> {
+    type Value = Person;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Person, D::Error> {
+        deserializer.deserialize_struct("Person", &["id", "name", "knows"], self)
+    }
+}
+
+impl<'de> Visitor<'de> for PersonSeed<'_> {
+    type Value = Person;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a person, as `{\"id\": 1, \"knows\": [2, 3]}`")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Person, A::Error> {
+        let (mut id, mut name, mut knows) = (None, None, Vec::new());
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "id" => id = Some(map.next_value_seed(IdSeed(&mut *self.0))?),
+                "name" => name = map.next_value()?,
+                "knows" => knows = map.next_value_seed(KnowsSeed(&mut *self.0))?,
+                _ => drop(map.next_value::<IgnoredAny>()?),
+            }
+        }
+        let id = id.ok_or_else(|| de::Error::missing_field("id"))?;
+        let person = self.0.person(id, knows);
+        Ok(Person {
+            name: name.or(person.name),
+            ..person
+        })
+    }
+}
+
+/// The acquaintances of a person, as a list of ids.
+struct KnowsSeed<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for KnowsSeed<'_> {
+    type Value = Vec<usize>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Vec<usize>, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for KnowsSeed<'_> {
+    type Value = Vec<usize>;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("a list of ids")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<usize>, A::Error> {
+        let mut known = Vec::new();
+        while let Some(id) = seq.next_element_seed(IdSeed(&mut *self.0))? {
+            known.push(id);
+        }
+        Ok(known)
+    }
+}
+
+/// An id given as a number or as a string, which is a name when the identifiers are.
+struct IdSeed<'a>(&'a mut Identifiers);
+
+impl<'de> DeserializeSeed<'de> for IdSeed<'_> {
+    type Value = usize;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl Visitor<'_> for IdSeed<'_> {
+    type Value = usize;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        if self.0.is_names() {
+            f.write_str("a non-empty name")
+        } else {
+            f.write_str("a non-negative integer id, or a string of one")
+        }
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<usize, E> {
+        self.0
+            .resolve(&v.to_string())
+            .ok_or_else(|| E::custom(format!("id {v} is too large")))
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<usize, E> {
+        match u64::try_from(v) {
+            Ok(v) => self.visit_u64(v),
+            Err(_) => Err(E::invalid_value(Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<usize, E> {
+        let id = self.0.resolve(v);
+        id.ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_round_trip() {
+        let mut party = Party::demo();
+        party.merge(Person::named(8, "Eve", [1, 2, 3]));
+        let json = serde_json::to_string(&party).expect("parties serialize");
+        assert!(
+            json.starts_with(r#"{"people":[{"id":1,"knows":[2,3]},"#),
+            "{json}"
+        );
+        assert!(
+            json.contains(r#"{"id":8,"name":"Eve","knows":[1,2,3]}"#),
+            "{json}"
+        );
+        let back = serde_json::from_str::<Party>(&json).expect("round trip");
+        assert_eq!(back.to_adjacency(), party.to_adjacency());
+        assert_eq!(back.get(8).and_then(|eve| eve.name.as_deref()), Some("Eve"));
+    }
+
+    #[test]
+    fn test_lenient_input() {
+        let json = r#"{
+            "source": "survey",
+            "people": [
+                {"id": " 1 ", "knows": ["2", 3, 1], "extra": {"nested": true}},
+                {"id": 2},
+                {"id": 3, "knows": []}
+            ]
+        }"#;
+        let party = serde_json::from_str::<Party>(json).expect("valid input");
+        assert_eq!(
+            party.to_adjacency(),
+            crate::party! { 1 => [2, 3]; 2 => []; 3 => [] }.to_adjacency()
+        );
+    }
+
+    #[test]
+    fn test_names() {
+        let read = |json, ids: &mut Identifiers| {
+            ids.deserialize(&mut serde_json::Deserializer::from_str(json))
+        };
+        let json = r#"{"people": [
+            {"id": "Mary Ann", "knows": ["bob", 7]},
+            {"id": "bob", "name": "Bob Jones", "knows": ["Mary Ann"]}
+        ]}"#;
+        let mut ids = Identifiers::Auto;
+        let party = read(json, &mut ids).expect("valid input");
+        assert_eq!(
+            ids.name(2),
+            Some("7"),
+            "numbers are names once names come first"
+        );
+        assert_eq!(
+            party.get(0).and_then(|mary| mary.name.as_deref()),
+            Some("Mary Ann")
+        );
+        assert_eq!(
+            party.get(1).and_then(|bob| bob.name.as_deref()),
+            Some("Bob Jones")
+        );
+        assert_eq!(party.to_adjacency()[&0], HashSet::from([1, 2]));
+
+        let err = read(
+            r#"{"people": [{"id": "bob"}, {"id": "bob"}]}"#,
+            &mut Identifiers::Auto,
+        )
+        .expect_err("bob is described twice");
+        assert!(
+            err.to_string()
+                .contains("person `bob` is described more than once"),
+            "{err}"
+        );
+        let err = read(
+            r#"{"people": [{"id": 1}, {"id": "bob"}]}"#,
+            &mut Identifiers::Auto,
+        )
+        .expect_err("names do not mix with ids");
+        assert!(err.to_string().contains("\"bob\""), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let err = |json| {
+            serde_json::from_str::<Party>(json)
+                .map(drop)
+                .expect_err("invalid")
+        };
+        assert!(
+            err(r#"{"people": [{"id": 2}, {"id": "2"}]}"#)
+                .to_string()
+                .contains("person 2 is described more than once")
+        );
+        assert!(
+            err(r#"{"people": [{"id": "two"}]}"#)
+                .to_string()
+                .contains("\"two\"")
+        );
+        assert!(
+            err(r#"{"people": [{"id": -1}]}"#)
+                .to_string()
+                .contains("-1")
+        );
+        assert!(
+            err(r#"{"people": [{"knows": [1]}]}"#)
+                .to_string()
+                .contains("`id`")
+        );
+        assert!(err("[]").is_data());
+    }
+}
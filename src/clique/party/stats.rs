@@ -0,0 +1,95 @@
+use super::Party;
+use std::collections::HashSet;
+
+/// A quick profile of a party, as [`Party::stats`] counts it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// The number of guests.
+    pub people: usize,
+    /// The number of directed acquaintances, strangers included: the sum of the out-degrees.
+    pub acquaintances: usize,
+    /// The fewest people a guest knows, or 0 for an empty party.
+    pub min_out_degree: usize,
+    /// The most people a guest knows, or 0 for an empty party.
+    pub max_out_degree: usize,
+    /// The number of distinct ids that guests know but that are not at the party.
+    pub strangers: usize,
+}
+
+impl Stats {
+    /// How many people a guest knows on average, or 0 for an empty party.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn mean_out_degree(&self) -> f64 {
+        if self.people == 0 {
+            0.0
+        } else {
+            self.acquaintances as f64 / self.people as f64
+        }
+    }
+}
+
+impl Party {
+    /// Counts the guests and their acquaintances, in one pass over the party.
+    ///
+    /// ```
+    /// use celebrities::{clique::party::Stats, party};
+    ///
+    /// let party = party! { 1 <=> 2; 3 => [1, 2, 42] };
+    /// let stats = party.stats();
+    /// assert_eq!(
+    ///     stats,
+    ///     Stats { people: 3, acquaintances: 5, min_out_degree: 1, max_out_degree: 3, strangers: 1 }
+    /// );
+    /// assert!((stats.mean_out_degree() - 5.0 / 3.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats {
+            people: self.len(),
+            min_out_degree: usize::MAX,
+            ..Stats::default()
+        };
+        let mut strangers = HashSet::new();
+        for person in self {
+            let mut degree = 0;
+            for id in person.known_iter() {
+                degree += 1;
+                if !self.contains_id(id) {
+                    strangers.insert(id);
+                }
+            }
+            stats.acquaintances += degree;
+            stats.min_out_degree = stats.min_out_degree.min(degree);
+            stats.max_out_degree = stats.max_out_degree.max(degree);
+        }
+        stats.min_out_degree = stats.min_out_degree.min(stats.max_out_degree);
+        stats.strangers = strangers.len();
+        stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::party;
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(Party::new().stats(), Stats::default());
+        assert!(Party::new().stats().mean_out_degree().abs() < f64::EPSILON);
+
+        // Strangers known by several guests count once.
+        let party = party! { 1 => [7, 8]; 2 => [7]; 3 => [] };
+        assert_eq!(
+            party.stats(),
+            Stats {
+                people: 3,
+                acquaintances: 3,
+                min_out_degree: 0,
+                max_out_degree: 2,
+                strangers: 2,
+            }
+        );
+    }
+}
@@ -1,21 +1,144 @@
+mod builder;
+mod parse;
+mod small;
+mod sorted;
+
+pub use builder::PersonBuilder;
+pub use parse::ParsePersonError;
+pub(crate) use parse::{split_line, tokens};
+pub use small::{Acquaintances, SmallPerson};
+pub use sorted::SortedPerson;
+
+use super::Knows;
 use std::{
+    borrow::Borrow,
+    cmp::Ordering,
     collections::HashSet,
     convert::Into,
-    fmt::{Display, Error, Formatter},
+    fmt::{Debug, Display, Error, Formatter},
     hash::{Hash, Hasher},
 };
 
+/// A guest, identified by `id`. Ids are `usize` unless stated otherwise.
+///
+/// The optional `name` is only used for display; equality and hashing look at the id alone.
+///
+/// ```
+/// use celebrities::Person;
+///
+/// let alice = Person::builder(1).name("Alice").knows_many([2, 3]).build();
+/// assert_eq!(alice.to_string(), "Alice (1) knows {2, 3}");
+/// ```
 #[derive(Debug, Clone)]
-pub struct Person {
-    pub id: usize,
-    pub known_people: HashSet<usize>,
+pub struct Person<Id = usize> {
+    pub id: Id,
+    pub name: Option<String>,
+    pub known_people: HashSet<Id>,
 }
 
-impl Person {
+impl<Id: Eq + Hash> Person<Id> {
+    /// Starts a [`PersonBuilder`], the most readable way to describe a guest.
+    pub fn builder(id: Id) -> PersonBuilder<Id> {
+        PersonBuilder::new(id)
+    }
+
+    /// ```
+    /// use celebrities::Person;
+    ///
+    /// let alice = Person::new("alice", ["bob", "alice"]);
+    /// assert_eq!(alice.known_people.len(), 1); // alice is not listed as her own acquaintance.
+    /// ```
+    #[must_use]
+    pub fn new<V>(id: Id, known_people: V) -> Self
+    where
+        V: IntoIterator<Item = Id>,
+    {
+        let known_people = known_people
+            .into_iter()
+            .filter(|people_id| *people_id != id) // remove myself.
+            .collect();
+        Self {
+            id,
+            name: None,
+            known_people,
+        }
+    }
+
+    /// ```
+    /// use celebrities::Person;
+    ///
+    /// let alice = Person::named(1, "Alice", [2]);
+    /// assert_eq!(alice.label(), "Alice (1)");
+    /// assert_eq!(alice, Person::builder(1).knows(3).build());
+    /// ```
+    #[must_use]
+    pub fn named<V>(id: Id, name: impl Into<String>, known_people: V) -> Self
+    where
+        V: IntoIterator<Item = Id>,
+    {
+        Self {
+            name: Some(name.into()),
+            ..Self::new(id, known_people)
+        }
+    }
+
+    /// ```
+    /// use celebrities::Person;
+    ///
+    /// let alice = Person::builder(1).knows(2).build();
+    /// let bob = Person::builder(2).build();
+    /// assert!(alice.knows(&bob));
+    /// assert!(!bob.knows(&alice));
+    /// assert!(bob.knows(&bob));
+    /// ```
+    #[must_use]
     pub fn knows(&self, other: &Self) -> bool {
         self == other // x `knows` x, for all x.
             || self.known_people.contains(&other.id)
     }
+
+    /// Whether this person knows the person with the given id, themselves included.
+    #[must_use]
+    pub fn knows_id(&self, id: &Id) -> bool {
+        self.id == *id || self.known_people.contains(id)
+    }
+
+    /// Whether this person knows everybody in `ids`; vacuously true when there is nobody.
+    #[must_use]
+    pub fn knows_all<I: IntoIterator<Item = Id>>(&self, ids: I) -> bool {
+        ids.into_iter().all(|id| self.knows_id(&id))
+    }
+
+    /// Whether this person knows anybody in `ids`.
+    #[must_use]
+    pub fn knows_any<I: IntoIterator<Item = Id>>(&self, ids: I) -> bool {
+        ids.into_iter().any(|id| self.knows_id(&id))
+    }
+
+    /// The ids among `ids` this person does not know.
+    #[must_use]
+    pub fn strangers_among<I: IntoIterator<Item = Id>>(&self, ids: I) -> HashSet<Id> {
+        ids.into_iter().filter(|id| !self.knows_id(id)).collect()
+    }
+
+    /// The ids this person knows, not counting themselves.
+    pub fn known_iter(&self) -> impl Iterator<Item = Id> + '_
+    where
+        Id: Clone,
+    {
+        self.known_people.iter().cloned()
+    }
+}
+
+impl<Id: Display> Person<Id> {
+    /// The name followed by the id in parentheses, or just the id for anonymous people.
+    #[must_use]
+    pub fn label(&self) -> String {
+        self.name.as_ref().map_or_else(
+            || self.id.to_string(),
+            |name| format!("{name} ({})", self.id),
+        )
+    }
 }
 
 impl<N, V> From<(N, V)> for Person
@@ -24,33 +147,138 @@ where
     N: Into<usize>,
 {
     fn from((id, known_people): (N, V)) -> Self {
-        let id = id.into();
-        Self {
-            id,
-            known_people: known_people
-                .into_iter()
-                .map(Into::into)
-                .filter(|people_id| *people_id != id) // remove myself.
-                .collect(),
-        }
+        Self::new(id.into(), known_people.into_iter().map(Into::into))
     }
 }
 
-impl PartialEq for Person {
+impl<Id: PartialEq> PartialEq for Person<Id> {
     fn eq(&self, other: &Self) -> bool {
         self.id.eq(&other.id)
     }
 }
-impl Eq for Person {}
+impl<Id: Eq> Eq for Person<Id> {}
 
-impl Hash for Person {
+impl<Id: Ord> PartialOrd for Person<Id> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<Id: Ord> Ord for Person<Id> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<Id: Hash> Hash for Person<Id> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.id.hash(state);
     }
 }
 
-impl Display for Person {
+impl<Id: Eq + Hash + Clone> Knows for Person<Id> {
+    type Key = Id;
+
+    fn key(&self) -> &Id {
+        &self.id
+    }
+
+    fn knows(&self, other: &Self) -> bool {
+        self.knows(other)
+    }
+
+    fn acquaintances(&self) -> impl Iterator<Item = &Id> {
+        self.known_people.iter()
+    }
+}
+
+/// Like [`Person::new`], nobody generated this way is listed as their own acquaintance.
+#[cfg(feature = "arbitrary")]
+impl<'a, Id> arbitrary::Arbitrary<'a> for Person<Id>
+where
+    Id: arbitrary::Arbitrary<'a> + Eq + Hash,
+{
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let id = Id::arbitrary(u)?;
+        let name = u.arbitrary()?;
+        let known_people = u
+            .arbitrary_iter::<Id>()?
+            .collect::<arbitrary::Result<Vec<_>>>()?;
+        Ok(Self {
+            name,
+            ..Self::new(id, known_people)
+        })
+    }
+}
+
+// Consistent with `Hash` and `Eq`, which only look at the id.
+impl Borrow<usize> for Person {
+    fn borrow(&self) -> &usize {
+        &self.id
+    }
+}
+
+/// The alternate form, `{:#}`, is the `id: known ...` line read by [`FromStr`](std::str::FromStr),
+/// without the name. Both forms write the acquaintances in ascending order.
+impl<Id: Display + Debug + Ord> Display for Person<Id> {
     fn fmt(&self, f: &mut Formatter) -> Result<(), Error> {
-        write!(f, "id: {} knows {:?}", self.id, self.known_people)
+        let mut known = self.known_people.iter().collect::<Vec<_>>();
+        known.sort_unstable();
+        if f.alternate() {
+            write!(f, "{}:", self.id)?;
+            return known.into_iter().try_for_each(|id| write!(f, " {id}"));
+        }
+        match &self.name {
+            None => write!(f, "id: {} knows {{", self.id)?,
+            Some(name) => write!(f, "{name} ({}) knows {{", self.id)?,
+        }
+        for (i, id) in known.into_iter().enumerate() {
+            let separator = if i == 0 { "" } else { ", " };
+            write!(f, "{separator}{id:?}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alice() -> Person {
+        Person::new(1, [2, 3])
+    }
+
+    #[test]
+    fn test_knows_all_and_any() {
+        let alice = alice();
+        assert!(alice.knows_all([]));
+        assert!(!alice.knows_any([]));
+        assert!(alice.knows_all([1, 2, 3]));
+        assert!(!alice.knows_all([2, 4]));
+        assert!(alice.knows_any([4, 1]));
+        assert!(!alice.knows_any([4, 5]));
+    }
+
+    #[test]
+    fn test_strangers_among() {
+        let alice = alice();
+        assert!(alice.strangers_among([]).is_empty());
+        assert!(alice.strangers_among([1, 2]).is_empty());
+        assert_eq!(alice.strangers_among([1, 3, 4, 5]), HashSet::from([4, 5]));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Person::new(1, [40, 3, 7]).to_string(),
+            "id: 1 knows {3, 7, 40}"
+        );
+        assert_eq!(Person::new(3, []).to_string(), "id: 3 knows {}");
+    }
+
+    #[test]
+    fn test_known_iter() {
+        let mut known = alice().known_iter().collect::<Vec<_>>();
+        known.sort_unstable();
+        assert_eq!(known, [2, 3]);
     }
 }
@@ -0,0 +1,85 @@
+use super::Person;
+use std::{collections::HashSet, hash::Hash};
+
+/// Builds a [`Person`] one acquaintance at a time.
+///
+/// ```
+/// use celebrities::Person;
+///
+/// let alice = Person::builder(1).name("Alice").knows(2).knows_many([3, 1]).build();
+/// assert_eq!(alice.label(), "Alice (1)");
+/// assert_eq!(alice.known_people.len(), 2); // Alice is not listed as her own acquaintance.
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct PersonBuilder<Id = usize> {
+    id: Id,
+    name: Option<String>,
+    known_people: HashSet<Id>,
+}
+
+impl<Id: Eq + Hash> PersonBuilder<Id> {
+    pub fn new(id: Id) -> Self {
+        Self {
+            id,
+            name: None,
+            known_people: HashSet::new(),
+        }
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn knows(mut self, id: Id) -> Self {
+        self.known_people.insert(id);
+        self
+    }
+
+    pub fn knows_many<I: IntoIterator<Item = Id>>(mut self, ids: I) -> Self {
+        self.known_people.extend(ids);
+        self
+    }
+
+    /// The person, without themselves among their acquaintances.
+    #[must_use]
+    pub fn build(self) -> Person<Id> {
+        Person {
+            name: self.name,
+            ..Person::new(self.id, self.known_people)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chained_calls() {
+        let person = PersonBuilder::new(3)
+            .knows(1)
+            .name("Carol")
+            .knows_many([2, 4])
+            .knows(5)
+            .build();
+        assert_eq!(person.id, 3);
+        assert_eq!(person.name.as_deref(), Some("Carol"));
+        assert_eq!(person.known_people, HashSet::from([1, 2, 4, 5]));
+        assert!(PersonBuilder::new(3).build().name.is_none());
+    }
+
+    #[test]
+    fn test_duplicate_acquaintances() {
+        let person = PersonBuilder::new(1).knows(2).knows_many([2, 2]).build();
+        assert_eq!(person.known_people, HashSet::from([2]));
+    }
+
+    #[test]
+    fn test_self_id() {
+        let person = PersonBuilder::new(1).knows(1).knows_many([1, 2]).build();
+        assert_eq!(person.known_people, HashSet::from([2]));
+        assert_eq!(person, Person::from((1_usize, [1_usize, 2])));
+    }
+}
@@ -0,0 +1,255 @@
+use super::Person;
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// Why a line could not be read as a [`Person`]. Offsets are in bytes from the start of the line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePersonError {
+    /// The line has nothing before the separator.
+    MissingId { offset: usize },
+    /// The id is not followed by `:` or `->`.
+    MissingSeparator { offset: usize },
+    /// A token is not a non-negative integer.
+    InvalidId { offset: usize, token: String },
+    /// A double quote is not closed.
+    UnterminatedQuote { offset: usize },
+}
+
+impl ParsePersonError {
+    /// Where in the line the problem was found.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        match self {
+            Self::MissingId { offset }
+            | Self::MissingSeparator { offset }
+            | Self::InvalidId { offset, .. }
+            | Self::UnterminatedQuote { offset } => *offset,
+        }
+    }
+}
+
+impl Display for ParsePersonError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MissingId { offset } => write!(f, "expected an id at byte {offset}"),
+            Self::MissingSeparator { offset } => {
+                write!(f, "expected `:` or `->` after the id at byte {offset}")
+            }
+            Self::InvalidId { offset, token } => {
+                write!(f, "`{token}` at byte {offset} is not a valid id")
+            }
+            Self::UnterminatedQuote { offset } => {
+                write!(f, "the quote at byte {offset} is not closed")
+            }
+        }
+    }
+}
+
+impl Error for ParsePersonError {}
+
+/// Reads a person from a line of the form `id: known, ...` or `id -> known ...`.
+///
+/// The acquaintances are separated by commas, whitespace, or both, and may be absent.
+/// Whitespace around the line and the separator is ignored, repeated acquaintances are kept once,
+/// and the person's own id is dropped as with [`Person::new`].
+/// The alternate [`Display`] form, `{:#}`, writes a line in this format.
+///
+/// ```
+/// use celebrities::Person;
+///
+/// let carol = "3: 1, 2".parse::<Person>()?;
+/// assert_eq!(carol.known_people, "3 -> 1 2".parse::<Person>()?.known_people);
+/// assert!(carol.knows_all([1, 2]));
+///
+/// let err = "3: 1 two".parse::<Person>().unwrap_err();
+/// assert_eq!(err.offset(), 5);
+/// # Ok::<(), celebrities::clique::person::ParsePersonError>(())
+/// ```
+impl FromStr for Person {
+    type Err = ParsePersonError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let ((offset, id), known_people) = split_line(line)?;
+        let id = parse_id(offset, id)?;
+        let known_people = (known_people.into_iter())
+            .map(|(offset, token)| parse_id(offset, token))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(id, known_people))
+    }
+}
+
+/// A token of a line, and its offset in it.
+pub type Token<'a> = (usize, &'a str);
+
+/// Splits an `id: known, ...` line into the id and the acquaintances, each with its offset, but
+/// leaves reading them to the caller.
+///
+/// Double quotes around a token keep commas, whitespace and separators in it; an id before the
+/// separator may also have spaces without them.
+pub fn split_line(line: &str) -> Result<(Token<'_>, Vec<Token<'_>>), ParsePersonError> {
+    let Some((at, len)) = separator(line)? else {
+        let tokens = tokens(line, |c| c == ',' || c.is_whitespace())?;
+        return Err(match tokens.first() {
+            None => ParsePersonError::MissingId { offset: line.len() },
+            Some((offset, token)) => ParsePersonError::MissingSeparator {
+                offset: offset + token.len(),
+            },
+        });
+    };
+
+    let head = line[..at].trim();
+    if head.is_empty() {
+        return Err(ParsePersonError::MissingId { offset: at });
+    }
+    let id = match head
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    {
+        Some(quoted) if !quoted.contains('"') => (offset_in(line, quoted), quoted),
+        _ => (offset_in(line, head), head),
+    };
+    let known_people = tokens(&line[at + len..], |c| c == ',' || c.is_whitespace())?
+        .into_iter()
+        .map(|(offset, token)| (at + len + offset, token))
+        .collect();
+    Ok((id, known_people))
+}
+
+/// Where the first `:` or `->` outside double quotes is, and how long it is.
+fn separator(line: &str) -> Result<Option<(usize, usize)>, ParsePersonError> {
+    let mut quote = None;
+    for (at, c) in line.char_indices() {
+        match (c, quote) {
+            ('"', None) => quote = Some(at),
+            ('"', Some(_)) => quote = None,
+            (':', None) => return Ok(Some((at, 1))),
+            ('-', None) if line[at..].starts_with("->") => return Ok(Some((at, 2))),
+            _ => {}
+        }
+    }
+    quote.map_or(Ok(None), |offset| {
+        Err(ParsePersonError::UnterminatedQuote { offset })
+    })
+}
+
+/// The non-empty tokens of `s` between the characters `is_separator` accepts, with their
+/// offsets. A token in double quotes may hold separators, and is given without the quotes.
+pub fn tokens(
+    s: &str,
+    is_separator: impl Fn(char) -> bool,
+) -> Result<Vec<Token<'_>>, ParsePersonError> {
+    let mut tokens = Vec::new();
+    let mut rest = s;
+    loop {
+        rest = rest.trim_start_matches(&is_separator);
+        let offset = offset_in(s, rest);
+        if let Some(quoted) = rest.strip_prefix('"') {
+            let end = quoted
+                .find('"')
+                .ok_or(ParsePersonError::UnterminatedQuote { offset })?;
+            tokens.push((offset + 1, &quoted[..end]));
+            rest = &quoted[end + 1..];
+            continue;
+        }
+        let end = rest
+            .find(|c| is_separator(c) || c == '"')
+            .unwrap_or(rest.len());
+        if end == 0 {
+            return Ok(tokens);
+        }
+        tokens.push((offset, &rest[..end]));
+        rest = &rest[end..];
+    }
+}
+
+/// The offset of `part`, a substring borrowed from `whole`.
+fn offset_in(whole: &str, part: &str) -> usize {
+    part.as_ptr().addr() - whole.as_ptr().addr()
+}
+
+fn parse_id(offset: usize, token: &str) -> Result<usize, ParsePersonError> {
+    token.parse().map_err(|_| ParsePersonError::InvalidId {
+        offset,
+        token: token.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn known(line: &str) -> Result<HashSet<usize>, ParsePersonError> {
+        line.parse::<Person>().map(|person| person.known_people)
+    }
+
+    #[test]
+    fn test_separators() {
+        let carol = "3: 1, 2".parse::<Person>();
+        assert_eq!(carol.as_ref().map(|person| person.id), Ok(3));
+        assert_eq!(known("3: 1, 2"), Ok(HashSet::from([1, 2])));
+        assert_eq!(known("3 -> 1 2"), Ok(HashSet::from([1, 2])));
+        assert_eq!(known("3:1,2,"), Ok(HashSet::from([1, 2])));
+    }
+
+    #[test]
+    fn test_empty_acquaintances() {
+        assert_eq!(known("3:"), Ok(HashSet::new()));
+        assert_eq!(known("3 ->  "), Ok(HashSet::new()));
+    }
+
+    #[test]
+    fn test_duplicates_and_self() {
+        assert_eq!(known("3: 1, 1 2, 3"), Ok(HashSet::from([1, 2])));
+    }
+
+    #[test]
+    fn test_surrounding_whitespace() {
+        assert_eq!(known("  3 :  1\t2 \n"), Ok(HashSet::from([1, 2])));
+    }
+
+    #[test]
+    fn test_garbage() {
+        assert_eq!(
+            known(" x: 1"),
+            Err(ParsePersonError::InvalidId {
+                offset: 1,
+                token: "x".to_owned()
+            })
+        );
+        assert_eq!(
+            known("3: 1, -2"),
+            Err(ParsePersonError::InvalidId {
+                offset: 6,
+                token: "-2".to_owned()
+            })
+        );
+        assert_eq!(
+            known("3 1 2"),
+            Err(ParsePersonError::MissingSeparator { offset: 1 })
+        );
+        assert_eq!(
+            known(" : 1"),
+            Err(ParsePersonError::MissingId { offset: 1 })
+        );
+        assert_eq!(known("   "), Err(ParsePersonError::MissingId { offset: 3 }));
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        let person = Person::builder(3).name("Carol").knows_many([1, 2]).build();
+        let line = format!("{person:#}");
+        let parsed = line.parse::<Person>();
+        assert_eq!(parsed.as_ref().map(|p| p.id), Ok(3));
+        assert_eq!(parsed.map(|p| p.known_people), Ok(person.known_people));
+        assert_eq!(format!("{:#}", Person::new(5, [])), "5:");
+        // The same person is always written the same way.
+        assert_eq!(
+            format!("{:#}", Person::new(5, [9, 2, 40, 7, 1])),
+            "5: 1 2 7 9 40"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use super::{Knows, Person};
+use std::{
+    cmp::Ordering,
+    collections::HashSet,
+    hash::{Hash, Hasher},
+};
+
+/// Acquaintance ids kept inline, without allocating, up to `N` of them, and in a `HashSet` beyond.
+///
+/// Once spilled, the set is kept even if it shrinks again, so a list never moves back and forth.
+///
+/// ```
+/// use celebrities::clique::person::Acquaintances;
+///
+/// let mut known = Acquaintances::<usize, 2>::new();
+/// assert!(known.insert(1) && known.insert(2) && !known.insert(2));
+/// assert!(!known.is_spilled());
+/// assert!(known.insert(3));
+/// assert!(known.is_spilled() && known.contains(&1));
+/// assert_eq!(known.len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Acquaintances<Id = usize, const N: usize = 12> {
+    storage: Storage<Id, N>,
+}
+
+#[derive(Debug, Clone)]
+enum Storage<Id, const N: usize> {
+    /// The first `len` ids are the acquaintances; the rest are placeholders.
+    Inline {
+        ids: [Id; N],
+        len: usize,
+    },
+    Spilled(HashSet<Id>),
+}
+
+impl<Id: Copy + Default, const N: usize> Default for Acquaintances<Id, N> {
+    fn default() -> Self {
+        Self {
+            storage: Storage::Inline {
+                ids: [Id::default(); N],
+                len: 0,
+            },
+        }
+    }
+}
+
+impl<Id: Copy + Default + Eq + Hash, const N: usize> Acquaintances<Id, N> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn contains(&self, id: &Id) -> bool {
+        match &self.storage {
+            Storage::Inline { ids, len } => ids[..*len].contains(id),
+            Storage::Spilled(set) => set.contains(id),
+        }
+    }
+
+    /// Adds `id`, returning whether it was new. The `N + 1`-th id moves them all to a set.
+    pub fn insert(&mut self, id: Id) -> bool {
+        if self.contains(&id) {
+            return false;
+        }
+        match &mut self.storage {
+            Storage::Inline { ids, len } if *len < N => {
+                ids[*len] = id;
+                *len += 1;
+            }
+            Storage::Inline { ids, .. } => {
+                let mut set = ids.iter().copied().collect::<HashSet<_>>();
+                set.insert(id);
+                self.storage = Storage::Spilled(set);
+            }
+            Storage::Spilled(set) => {
+                set.insert(id);
+            }
+        }
+        true
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Spilled(set) => set.len(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether there were ever more than `N` ids, so that they are kept on the heap.
+    #[must_use]
+    pub const fn is_spilled(&self) -> bool {
+        matches!(self.storage, Storage::Spilled(_))
+    }
+
+    /// The ids, in insertion order while they are inline and in no particular order once spilled.
+    pub fn iter(&self) -> impl Iterator<Item = &Id> {
+        let (inline, spilled) = match &self.storage {
+            Storage::Inline { ids, len } => (&ids[..*len], None),
+            Storage::Spilled(set) => (&[][..], Some(set)),
+        };
+        inline.iter().chain(spilled.into_iter().flatten())
+    }
+}
+
+impl<Id: Copy + Default + Eq + Hash, const N: usize> FromIterator<Id> for Acquaintances<Id, N> {
+    fn from_iter<I: IntoIterator<Item = Id>>(ids: I) -> Self {
+        let mut known = Self::new();
+        for id in ids {
+            known.insert(id);
+        }
+        known
+    }
+}
+
+/// A guest whose acquaintances are kept in [`Acquaintances`], so that people who know at most `N`
+/// others need no allocation of their own.
+///
+/// Like [`Person`], equality and hashing look at the id alone, and nobody is listed as their own
+/// acquaintance.
+///
+/// ```
+/// use celebrities::{Clique, clique::person::SmallPerson};
+/// use std::collections::HashSet;
+///
+/// let party = [(1_usize, vec![1, 2]), (2, vec![1, 1])]
+///     .map(SmallPerson::<_, 4>::from)
+///     .into_iter()
+///     .collect::<HashSet<_>>();
+/// assert!(party.iter().all(|person| person.known_people().len() == 1));
+/// assert_eq!(party.cclique().map(|css| css.len()), Some(2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SmallPerson<Id = usize, const N: usize = 12> {
+    pub id: Id,
+    pub name: Option<String>,
+    known_people: Acquaintances<Id, N>,
+}
+
+impl<Id: Copy + Default + Eq + Hash, const N: usize> SmallPerson<Id, N> {
+    /// Drops duplicates of `known_people` and `id` itself.
+    #[must_use]
+    pub fn new<V>(id: Id, known_people: V) -> Self
+    where
+        V: IntoIterator<Item = Id>,
+    {
+        Self {
+            id,
+            name: None,
+            known_people: (known_people.into_iter())
+                .filter(|people_id| *people_id != id)
+                .collect(),
+        }
+    }
+
+    /// The ids this person knows, not counting themselves.
+    #[must_use]
+    pub const fn known_people(&self) -> &Acquaintances<Id, N> {
+        &self.known_people
+    }
+
+    /// Whether this person knows the person with the given id, themselves included.
+    #[must_use]
+    pub fn knows_id(&self, id: &Id) -> bool {
+        self.id == *id || self.known_people.contains(id)
+    }
+
+    /// Adds `id` to the acquaintances, returning whether it was new.
+    pub fn learn(&mut self, id: Id) -> bool {
+        id != self.id && self.known_people.insert(id)
+    }
+}
+
+impl<T, V, const N: usize> From<(T, V)> for SmallPerson<usize, N>
+where
+    V: IntoIterator<Item = T>,
+    T: Into<usize>,
+{
+    fn from((id, known_people): (T, V)) -> Self {
+        Self::new(id.into(), known_people.into_iter().map(Into::into))
+    }
+}
+
+impl<Id: Copy + Default + Eq + Hash, const N: usize> From<Person<Id>> for SmallPerson<Id, N> {
+    fn from(person: Person<Id>) -> Self {
+        Self {
+            name: person.name,
+            ..Self::new(person.id, person.known_people)
+        }
+    }
+}
+
+impl<Id: Copy + Default + Eq + Hash, const N: usize> From<SmallPerson<Id, N>> for Person<Id> {
+    fn from(person: SmallPerson<Id, N>) -> Self {
+        Self {
+            name: person.name,
+            ..Self::new(person.id, person.known_people.iter().copied())
+        }
+    }
+}
+
+impl<Id: PartialEq, const N: usize> PartialEq for SmallPerson<Id, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id.eq(&other.id)
+    }
+}
+impl<Id: Eq, const N: usize> Eq for SmallPerson<Id, N> {}
+
+impl<Id: Ord, const N: usize> PartialOrd for SmallPerson<Id, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<Id: Ord, const N: usize> Ord for SmallPerson<Id, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<Id: Hash, const N: usize> Hash for SmallPerson<Id, N> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<Id: Copy + Default + Eq + Hash, const N: usize> Knows for SmallPerson<Id, N> {
+    type Key = Id;
+
+    fn key(&self) -> &Id {
+        &self.id
+    }
+
+    fn knows(&self, other: &Self) -> bool {
+        self.knows_id(&other.id)
+    }
+
+    fn acquaintances(&self) -> impl Iterator<Item = &Id> {
+        self.known_people.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clique, clique::tests::counting, generator::sample_parties};
+
+    const N: usize = 4;
+
+    fn sorted(known: &Acquaintances<usize, N>) -> Vec<usize> {
+        let mut ids = known.iter().copied().collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_spills_past_n() {
+        let mut known = Acquaintances::<usize, N>::new();
+        for id in 0..N {
+            assert!(known.insert(id));
+            assert!(!known.insert(id));
+        }
+        assert!(!known.is_spilled());
+        assert_eq!(known.len(), N);
+        // A duplicate does not spill a full list.
+        assert!(!known.insert(0));
+        assert!(!known.is_spilled());
+
+        assert!(known.insert(N));
+        assert!(known.is_spilled());
+        assert!(!known.insert(N));
+        assert_eq!(known.len(), N + 1);
+        assert_eq!(sorted(&known), (0..=N).collect::<Vec<_>>());
+        assert!((0..=N).all(|id| known.contains(&id)));
+        assert!(!known.contains(&(N + 1)));
+    }
+
+    #[test]
+    fn test_inline_does_not_allocate() {
+        let (person, allocations) =
+            counting::allocations(|| SmallPerson::<usize, N>::new(0, [1, 2, 0, 3, 4, 2]));
+        assert_eq!(allocations, 0);
+        assert!(!person.known_people().is_spilled());
+        assert_eq!(sorted(person.known_people()), [1, 2, 3, 4]);
+
+        let (person, allocations) =
+            counting::allocations(|| SmallPerson::<usize, N>::new(0, 1..=N + 1));
+        assert!(allocations > 0);
+        assert!(person.known_people().is_spilled());
+    }
+
+    #[test]
+    fn test_construction_matches_person() {
+        // Around the boundary, with duplicates and the person themselves thrown in.
+        for len in N - 1..=N + 2 {
+            let known = (1..=len).chain([0, 1, len]).collect::<Vec<_>>();
+            let small = SmallPerson::<usize, N>::from((0_usize, known.clone()));
+            let person = Person::from((0_usize, known));
+            let mut expected = person.known_iter().collect::<Vec<_>>();
+            expected.sort_unstable();
+            assert_eq!(small.known_people().is_spilled(), len > N);
+            assert_eq!(sorted(small.known_people()), expected, "{len}");
+            assert_eq!(Person::from(small).known_people, person.known_people);
+        }
+    }
+
+    #[test]
+    fn test_learn() {
+        let mut alice = SmallPerson::<usize, N>::new(0, 1..N);
+        assert!(!alice.learn(0));
+        assert!(alice.learn(N));
+        assert!(!alice.known_people().is_spilled());
+        assert!(!alice.learn(N));
+        assert!(alice.learn(N + 1));
+        assert!(alice.known_people().is_spilled());
+        assert!(alice.knows_id(&0) && alice.knows_id(&(N + 1)));
+    }
+
+    #[test]
+    fn test_cclique_agrees_with_hash_sets() {
+        for party in sample_parties(300, 9, 0.6, 61) {
+            let small = party
+                .iter()
+                .cloned()
+                .map(SmallPerson::<usize, N>::from)
+                .collect::<HashSet<_>>();
+            assert_eq!(
+                small
+                    .cclique()
+                    .map(|css| css.into_iter().map(|p| p.id).collect::<HashSet<_>>()),
+                party
+                    .cclique()
+                    .map(|css| css.into_iter().map(|p| p.id).collect()),
+            );
+        }
+    }
+}
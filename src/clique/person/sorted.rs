@@ -0,0 +1,192 @@
+use super::{Knows, Person};
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+/// A guest whose acquaintances are kept in a sorted `Vec` and looked up by binary search.
+///
+/// This uses less memory than [`Person`]'s `HashSet` and is often faster to query for the short
+/// acquaintance lists typical of a party, at the cost of `O(n)` insertion. Like [`Person`], equality
+/// and hashing look at the id alone, and nobody is listed as their own acquaintance.
+///
+/// ```
+/// use celebrities::{Clique, Person, clique::person::SortedPerson};
+/// use std::collections::HashSet;
+///
+/// let bob = SortedPerson::new(2, [3, 1, 3, 2]);
+/// assert_eq!(bob.known_people(), [1, 3]);
+///
+/// let party = [(1, vec![]), (2, vec![1])]
+///     .map(|(id, known)| SortedPerson::new(id, known))
+///     .into_iter()
+///     .collect::<HashSet<_>>();
+/// assert_eq!(party.cclique().map(|css| css.len()), Some(1));
+/// assert_eq!(Person::from(bob), Person::new(2, [1, 3]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SortedPerson<Id = usize> {
+    pub id: Id,
+    pub name: Option<String>,
+    known_people: Vec<Id>,
+}
+
+impl<Id: Ord> SortedPerson<Id> {
+    /// Sorts `known_people`, dropping duplicates and `id` itself.
+    #[must_use]
+    pub fn new<V>(id: Id, known_people: V) -> Self
+    where
+        V: IntoIterator<Item = Id>,
+    {
+        let mut known_people = known_people
+            .into_iter()
+            .filter(|people_id| *people_id != id)
+            .collect::<Vec<_>>();
+        known_people.sort_unstable();
+        known_people.dedup();
+        Self {
+            id,
+            name: None,
+            known_people,
+        }
+    }
+
+    /// The ids this person knows, in increasing order, not counting themselves.
+    #[must_use]
+    pub fn known_people(&self) -> &[Id] {
+        &self.known_people
+    }
+
+    /// Whether this person knows the person with the given id, themselves included.
+    #[must_use]
+    pub fn knows_id(&self, id: &Id) -> bool {
+        self.id == *id || self.known_people.binary_search(id).is_ok()
+    }
+
+    /// Adds `id` to the acquaintances, returning whether it was new.
+    pub fn learn(&mut self, id: Id) -> bool {
+        if id == self.id {
+            return false;
+        }
+        match self.known_people.binary_search(&id) {
+            Ok(_) => false,
+            Err(position) => {
+                self.known_people.insert(position, id);
+                true
+            }
+        }
+    }
+}
+
+impl<Id: Ord + Hash> From<Person<Id>> for SortedPerson<Id> {
+    fn from(person: Person<Id>) -> Self {
+        Self {
+            name: person.name,
+            ..Self::new(person.id, person.known_people)
+        }
+    }
+}
+
+impl<Id: Eq + Hash> From<SortedPerson<Id>> for Person<Id> {
+    fn from(person: SortedPerson<Id>) -> Self {
+        Self {
+            name: person.name,
+            ..Self::new(person.id, person.known_people)
+        }
+    }
+}
+
+impl<Id: PartialEq> PartialEq for SortedPerson<Id> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id.eq(&other.id)
+    }
+}
+impl<Id: Eq> Eq for SortedPerson<Id> {}
+
+impl<Id: Ord> PartialOrd for SortedPerson<Id> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<Id: Ord> Ord for SortedPerson<Id> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<Id: Hash> Hash for SortedPerson<Id> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<Id: Ord + Hash + Clone> Knows for SortedPerson<Id> {
+    type Key = Id;
+
+    fn key(&self) -> &Id {
+        &self.id
+    }
+
+    fn knows(&self, other: &Self) -> bool {
+        self.knows_id(&other.id)
+    }
+
+    fn acquaintances(&self) -> impl Iterator<Item = &Id> {
+        self.known_people.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clique, generator::sample_parties};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_new_deduplicates() {
+        let alice = SortedPerson::new(1, [5, 2, 1, 5, 3, 2]);
+        assert_eq!(alice.known_people(), [2, 3, 5]);
+        assert!(alice.knows_id(&1));
+        assert!(alice.knows_id(&5));
+        assert!(!alice.knows_id(&4));
+    }
+
+    #[test]
+    fn test_learn() {
+        let mut alice = SortedPerson::new(1, [4, 2]);
+        assert!(alice.learn(3));
+        assert!(!alice.learn(3));
+        assert!(!alice.learn(1));
+        assert_eq!(alice.known_people(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let alice = Person::named(1, "Alice", [3, 2]);
+        let sorted = SortedPerson::from(alice.clone());
+        assert_eq!(sorted.name.as_deref(), Some("Alice"));
+        assert_eq!(sorted.known_people(), [2, 3]);
+        let back = Person::from(sorted);
+        assert_eq!(back.name, alice.name);
+        assert_eq!(back.known_people, alice.known_people);
+    }
+
+    #[test]
+    fn test_cclique_agrees_with_hash_sets() {
+        for party in sample_parties(300, 9, 0.6, 53) {
+            let sorted = party
+                .iter()
+                .cloned()
+                .map(SortedPerson::from)
+                .collect::<HashSet<_>>();
+            assert_eq!(
+                sorted
+                    .cclique()
+                    .map(|css| css.into_iter().map(|p| p.id).collect::<HashSet<_>>()),
+                party
+                    .cclique()
+                    .map(|css| css.into_iter().map(|p| p.id).collect()),
+            );
+        }
+    }
+}
@@ -0,0 +1,24 @@
+/// How far a [`cclique_with_progress`](super::Clique::cclique_with_progress) search has got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchProgress {
+    /// Subsets inspected so far.
+    pub examined: usize,
+    /// The size of the subset being inspected; the last one in the final report.
+    pub size: usize,
+    /// Subsets that would be inspected without a celebrity clique: the sum of `C(k, i)` for
+    /// `i` from 1 to the number `k` of candidates, saturating at `usize::MAX`.
+    pub total: usize,
+}
+
+impl SearchProgress {
+    /// The share of `total` examined, between 0 and 1.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn fraction(&self) -> f64 {
+        if self.total == 0 {
+            1.0
+        } else {
+            self.examined as f64 / self.total as f64
+        }
+    }
+}
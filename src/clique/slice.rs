@@ -0,0 +1,148 @@
+use super::{PowerSet, know_each_other, person::Person, violations_among};
+use std::{
+    collections::HashSet,
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// The same person id appears more than once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateId(pub usize);
+
+impl Display for DuplicateId {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "person {} is defined more than once", self.0)
+    }
+}
+
+impl Error for DuplicateId {}
+
+/// Celebrity clique queries over people stored in a slice, answered in terms of indices.
+///
+/// Unlike the set-based impls of [`Clique`](super::Clique), a slice may hold the same id twice,
+/// so every query checks for duplicates first.
+pub trait SliceClique {
+    /// # Errors
+    ///
+    /// Returns the first id that appears twice.
+    fn check_unique_ids(&self) -> Result<(), DuplicateId>;
+
+    /// Whether the people at `indices` know each other.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first id that appears twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    fn is_clique_at(&self, indices: &[usize]) -> Result<bool, DuplicateId>;
+
+    /// Whether the people at `indices` form the celebrity clique of the whole slice.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first id that appears twice.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    fn is_cclique_at(&self, indices: &[usize]) -> Result<bool, DuplicateId>;
+
+    /// The indices of the celebrity clique, in ascending order.
+    ///
+    /// ```
+    /// use celebrities::{Person, clique::SliceClique};
+    ///
+    /// let people = [(3_usize, vec![1]), (1, vec![]), (2, vec![1])].map(Person::from);
+    /// assert_eq!(people.cclique_indices(), Ok(Some(vec![1])));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns the first id that appears twice.
+    fn cclique_indices(&self) -> Result<Option<Vec<usize>>, DuplicateId>;
+}
+
+impl SliceClique for [Person] {
+    fn check_unique_ids(&self) -> Result<(), DuplicateId> {
+        let mut seen = HashSet::with_capacity(self.len());
+        self.iter()
+            .find(|person| !seen.insert(person.id))
+            .map_or(Ok(()), |person| Err(DuplicateId(person.id)))
+    }
+
+    fn is_clique_at(&self, indices: &[usize]) -> Result<bool, DuplicateId> {
+        self.check_unique_ids()?;
+        Ok(know_each_other(indices.iter().map(|&i| &self[i])))
+    }
+
+    fn is_cclique_at(&self, indices: &[usize]) -> Result<bool, DuplicateId> {
+        self.check_unique_ids()?;
+        Ok(is_cclique_at_unchecked(self, indices))
+    }
+
+    fn cclique_indices(&self) -> Result<Option<Vec<usize>>, DuplicateId> {
+        self.check_unique_ids()?;
+        let indices = (0..self.len()).collect::<Vec<_>>();
+        Ok(PowerSet::<_, Vec<_>>::new(&indices)
+            .skip(1)
+            .find(|subset| is_cclique_at_unchecked(self, subset))
+            .map(|mut subset| {
+                subset.sort_unstable();
+                subset
+            }))
+    }
+}
+
+fn is_cclique_at_unchecked(people: &[Person], indices: &[usize]) -> bool {
+    violations_among(indices.iter().map(|&i| &people[i]), people, |someone| {
+        indices.iter().any(|&i| people[i] == *someone)
+    })
+    .next()
+    .is_none()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clique::Clique;
+
+    /// The demo party in decreasing id order.
+    fn shuffled_demo() -> Vec<Person> {
+        let mut people = crate::Party::demo().sorted();
+        people.reverse();
+        people
+    }
+
+    #[test]
+    fn test_cclique_indices() {
+        let people = shuffled_demo();
+        let indices = people.cclique_indices().expect("ids are unique");
+        assert_eq!(indices, Some(vec![4, 5, 6]));
+        assert_eq!(people.is_cclique_at(&[4, 5, 6]), Ok(true));
+        assert_eq!(people.is_clique_at(&[4, 5, 6]), Ok(true));
+        assert_eq!(people.is_cclique_at(&[4, 5]), Ok(false));
+    }
+
+    #[test]
+    fn test_vec_cclique() {
+        let people = shuffled_demo();
+        let mut ids = people
+            .cclique()
+            .unwrap_or_default()
+            .iter()
+            .map(|person| person.id)
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        assert_eq!(ids, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let mut people = shuffled_demo();
+        people.push(Person::from((4_usize, vec![1_usize])));
+        assert_eq!(people.check_unique_ids(), Err(DuplicateId(4)));
+        assert_eq!(people.cclique_indices(), Err(DuplicateId(4)));
+    }
+}
@@ -0,0 +1,10 @@
+/// Which part of [`cclique_fast`](super::Clique::cclique_fast) produced its answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SolveStrategy {
+    /// The people known by everybody were the celebrity clique as they were, or there were none.
+    Candidates,
+    /// Dropping candidates who know somebody else until none was left to drop, or none was left.
+    Elimination,
+    /// The survivors of the elimination failed verification, so every subset was searched.
+    Exhaustive,
+}
@@ -0,0 +1,138 @@
+use super::{DuplicateId, person::Person};
+use std::collections::HashSet;
+
+/// The celebrity clique of the people in `people`, read in a single pass without collecting them.
+///
+/// This is the fold of Bird's derivation: the candidate clique of the people seen so far is kept,
+/// and each newcomer either replaces it (they know none of the candidates), is ignored (none of the
+/// candidates knows them), or joins it. If the whole party has a celebrity clique, the candidate
+/// is it. To check that it is at the end, the ids known by everybody so far are kept too, which
+/// are never more than the first person's acquaintances, and the ids of everybody seen, so that
+/// a celebrity knowing somebody who never showed up is not held against them.
+///
+/// Only the candidates are kept as people, so peak memory is about one id per guest rather than
+/// the whole party.
+///
+/// ```
+/// use celebrities::{Person, clique::cclique_streaming};
+///
+/// let people = [(1_usize, vec![2]), (2, vec![1]), (3, vec![1, 2])].map(Person::from);
+/// let css = cclique_streaming(people.into_iter()).map(|css| css.map(|css| css.len()));
+/// assert_eq!(css, Ok(Some(2)));
+/// ```
+///
+/// # Errors
+///
+/// A person may not appear twice in the stream, even with the same acquaintances, since records
+/// cannot be merged without keeping them all. The first repeated id is returned.
+pub fn cclique_streaming(
+    people: impl Iterator<Item = Person>,
+) -> Result<Option<HashSet<Person>>, DuplicateId> {
+    let mut candidates = Vec::<Person>::new();
+    let mut known_by_everyone = None::<HashSet<usize>>;
+    let mut seen = HashSet::new();
+    for person in people {
+        if !seen.insert(person.id) {
+            return Err(DuplicateId(person.id));
+        }
+        match &mut known_by_everyone {
+            None => {
+                let mut known = person.known_people.clone();
+                known.insert(person.id);
+                known_by_everyone = Some(known);
+            }
+            Some(known) => known.retain(|id| person.knows_id(id)),
+        }
+
+        if !candidates.iter().any(|candidate| person.knows(candidate)) {
+            candidates = vec![person];
+        } else if candidates.iter().any(|candidate| candidate.knows(&person)) {
+            candidates.push(person);
+        }
+    }
+
+    let known_by_everyone = known_by_everyone.unwrap_or_default();
+    let ids = candidates.iter().map(|c| c.id).collect::<HashSet<_>>();
+    let is_cclique = !candidates.is_empty()
+        && ids.is_subset(&known_by_everyone)
+        && candidates.iter().all(|celebrity| {
+            (celebrity.known_people.iter()).all(|id| ids.contains(id) || !seen.contains(id))
+        });
+    Ok(is_cclique.then(|| candidates.into_iter().collect()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Clique,
+        clique::tests::counting,
+        generator::{Rng, sample_parties},
+    };
+
+    fn ids(css: HashSet<Person>) -> HashSet<usize> {
+        css.into_iter().map(|person| person.id).collect()
+    }
+
+    #[test]
+    fn test_demo() {
+        let demo = crate::Party::demo();
+        let stream: Box<dyn Iterator<Item = Person>> = Box::new(demo.sorted().into_iter().rev());
+        assert_eq!(
+            cclique_streaming(stream).map(|css| css.map(ids)),
+            Ok(demo.cclique().map(|css| css.ids()))
+        );
+    }
+
+    #[test]
+    fn test_agrees_with_sets() {
+        let mut rng = Rng::new(55);
+        for party in sample_parties(500, 10, 0.6, 55) {
+            let mut people = party.iter().cloned().collect::<Vec<_>>();
+            for i in (1..people.len()).rev() {
+                people.swap(i, rng.below(i + 1));
+            }
+            assert_eq!(
+                cclique_streaming(people.into_iter()).map(|css| css.map(ids)),
+                Ok(party.cclique().map(ids)),
+                "{party:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_duplicate_ids() {
+        let people = [(1_usize, vec![]), (2, vec![1]), (1, vec![])].map(Person::from);
+        assert_eq!(cclique_streaming(people.into_iter()), Err(DuplicateId(1)));
+        assert_eq!(cclique_streaming(std::iter::empty()), Ok(None));
+    }
+
+    #[test]
+    fn test_memory() {
+        const GUESTS: usize = 100_000;
+        // Guests 0, 1 and 2 know each other; everybody else knows them and three random guests.
+        let stream = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..GUESTS).map(move |id| {
+                let others = if id < 3 {
+                    Vec::new()
+                } else {
+                    (0..3).map(|_| rng.below(GUESTS)).collect()
+                };
+                Person::new(id, (0..3).chain(others))
+            })
+        };
+
+        let (css, streaming) = counting::peak_bytes(|| cclique_streaming(stream(55)));
+        assert_eq!(
+            css.map(|css| css.map(ids)),
+            Ok(Some(HashSet::from([0, 1, 2])))
+        );
+        let (party, collected) = counting::peak_bytes(|| stream(55).collect::<HashSet<_>>());
+        assert_eq!(party.len(), GUESTS);
+        assert!(
+            streaming * 4 < collected,
+            "{streaming} bytes streaming vs {collected} collected"
+        );
+    }
+}
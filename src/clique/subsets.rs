@@ -0,0 +1,202 @@
+use std::{collections::HashSet, hash::Hash, marker::PhantomData};
+
+/// Lazily yields every `k`-element subset of some elements, one collection `C` at a time.
+///
+/// Subsets come out in lexicographic order of the elements' positions, and nothing is allocated
+/// beyond the subset being yielded.
+#[derive(Debug, Clone)]
+pub struct Combinations<'a, T, C = HashSet<T>> {
+    elems: Vec<&'a T>,
+    positions: Option<Vec<usize>>,
+    /// How many subsets are left, or `None` if that does not fit in a `usize`.
+    remaining: Option<usize>,
+    subset: PhantomData<C>,
+}
+
+impl<'a, T, C> Combinations<'a, T, C> {
+    #[must_use]
+    pub fn new(elems: impl IntoIterator<Item = &'a T>, k: usize) -> Self {
+        let elems = elems.into_iter().collect::<Vec<_>>();
+        let positions = (k <= elems.len()).then(|| (0..k).collect());
+        Self {
+            remaining: binomial(elems.len(), k),
+            elems,
+            positions,
+            subset: PhantomData,
+        }
+    }
+}
+
+impl<T: Clone, C: FromIterator<T>> Iterator for Combinations<'_, T, C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        let positions = self.positions.as_mut()?;
+        let subset = positions.iter().map(|&i| self.elems[i].clone()).collect();
+        self.remaining = self.remaining.map(|remaining| remaining - 1);
+
+        // advance to the next combination, or stop after the last one
+        let (n, k) = (self.elems.len(), positions.len());
+        match (0..k).rev().find(|&i| positions[i] != i + n - k) {
+            None => self.positions = None,
+            Some(i) => {
+                positions[i] += 1;
+                for j in i + 1..k {
+                    positions[j] = positions[j - 1] + 1;
+                }
+            }
+        }
+        Some(subset)
+    }
+
+    /// Exact, so that collecting the subsets reserves just enough room for all of them.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.remaining
+            .map_or((usize::MAX, None), |remaining| (remaining, Some(remaining)))
+    }
+}
+
+/// `n` choose `k`, or `None` if it does not fit in a `usize`.
+///
+/// Every intermediate value is itself a binomial coefficient, so nothing is rounded.
+pub(super) fn binomial(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    let k = k.min(n - k);
+    (0..k).try_fold(1_usize, |acc, v| {
+        let acc =
+            u128::try_from(acc).ok()? * u128::try_from(n - v).ok()? / u128::try_from(v + 1).ok()?;
+        usize::try_from(acc).ok()
+    })
+}
+
+/// Lazily yields every subset of some elements, smallest first, one collection `C` at a time.
+///
+/// Subsets of the same size come out as from [`Combinations`], so a search that stops early
+/// never builds the larger subsets.
+///
+/// ```
+/// use celebrities::clique::PowerSet;
+/// use std::collections::HashSet;
+///
+/// let set = HashSet::from([1, 2, 3]);
+/// let subsets = PowerSet::<_>::new(&set).collect::<Vec<HashSet<_>>>();
+/// assert_eq!(subsets.len(), 8);
+/// assert_eq!(subsets[0], HashSet::new());
+/// assert_eq!(subsets[7], set);
+/// assert_eq!(PowerSet::<_, Vec<_>>::new(&[1, 2, 3]).find(|subset| subset.len() == 2), Some(vec![1, 2]));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PowerSet<'a, T, C = HashSet<T>> {
+    elems: Vec<&'a T>,
+    subsets: Option<Combinations<'a, T, C>>,
+    size: usize,
+}
+
+impl<'a, T, C> PowerSet<'a, T, C> {
+    #[must_use]
+    pub fn new(elems: impl IntoIterator<Item = &'a T>) -> Self {
+        let elems = elems.into_iter().collect::<Vec<_>>();
+        Self {
+            subsets: Some(Combinations::new(elems.iter().copied(), 0)),
+            elems,
+            size: 0,
+        }
+    }
+}
+
+impl<T: Clone, C: FromIterator<T>> Iterator for PowerSet<'_, T, C> {
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        loop {
+            if let Some(subset) = self.subsets.as_mut()?.next() {
+                return Some(subset);
+            }
+            self.size += 1;
+            self.subsets = (self.size <= self.elems.len())
+                .then(|| Combinations::new(self.elems.iter().copied(), self.size));
+        }
+    }
+}
+
+/// Every subset of `set` with exactly `k` elements.
+///
+/// ```
+/// use celebrities::clique::k_subsets;
+/// use std::collections::HashSet;
+///
+/// let set = HashSet::from([1, 2, 3]);
+/// assert_eq!(k_subsets(&set, 2).count(), 3);
+/// assert_eq!(k_subsets(&set, 0).collect::<Vec<_>>(), [HashSet::new()]);
+/// assert_eq!(k_subsets(&set, 4).count(), 0);
+/// ```
+#[must_use]
+pub fn k_subsets<T: Clone + Eq + Hash>(set: &HashSet<T>, k: usize) -> Combinations<'_, T> {
+    Combinations::new(set, k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clique::binomial_approx;
+
+    #[test]
+    fn test_binomial() {
+        assert_eq!(binomial(0, 0), Some(1));
+        assert_eq!(binomial(5, 6), Some(0));
+        assert_eq!(binomial(10, 3), Some(120));
+        assert_eq!(binomial(64, 32), Some(1_832_624_140_942_590_534));
+        assert_eq!(binomial(70, 35), None);
+        // Overflows a `usize` on its way up unless the smaller side is used.
+        assert_eq!(binomial(100, 98), Some(4950));
+    }
+
+    #[test]
+    fn test_size_hint_is_exact() {
+        let set = (0..7).collect::<HashSet<_>>();
+        for k in 0..=8 {
+            let mut subsets = k_subsets(&set, k);
+            let mut left = binomial(7, k).expect("small");
+            loop {
+                assert_eq!(subsets.size_hint(), (left, Some(left)), "(7, {k})");
+                if subsets.next().is_none() {
+                    break;
+                }
+                left -= 1;
+            }
+            assert_eq!(left, 0);
+        }
+    }
+
+    #[test]
+    fn test_counts_match_binomial() {
+        for n in 0..=8 {
+            let set = (0..n).collect::<HashSet<_>>();
+            for k in 0..=n + 1 {
+                assert_eq!(
+                    k_subsets(&set, k).count(),
+                    binomial_approx(n, k),
+                    "({n}, {k})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_subsets_are_distinct_and_sized() {
+        let set = (0..6).collect::<HashSet<_>>();
+        let subsets = k_subsets(&set, 3).collect::<Vec<_>>();
+        assert!(subsets.iter().all(|subset| subset.len() == 3));
+        let distinct = subsets
+            .iter()
+            .map(|subset| {
+                let mut subset = subset.iter().copied().collect::<Vec<_>>();
+                subset.sort_unstable();
+                subset
+            })
+            .collect::<HashSet<_>>();
+        assert_eq!(distinct.len(), subsets.len());
+    }
+}
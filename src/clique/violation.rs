@@ -0,0 +1,60 @@
+use std::fmt::{self, Display, Formatter};
+
+/// The first reason found why a set of people is not the celebrity clique of a party.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CcliqueViolation<K = usize> {
+    /// `guest` does not know `celebrity`, but everybody must know every celebrity.
+    NotKnownByEveryone { guest: K, celebrity: K },
+    /// `celebrity` knows `outsider`, but celebrities know only each other.
+    KnowsOutsider { celebrity: K, outsider: K },
+}
+
+/// Which half of the celebrity clique definition a pair breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ViolationKind {
+    NotKnownByEveryone,
+    KnowsOutsider,
+}
+
+impl<K> CcliqueViolation<K> {
+    #[must_use]
+    pub const fn kind(&self) -> ViolationKind {
+        match self {
+            Self::NotKnownByEveryone { .. } => ViolationKind::NotKnownByEveryone,
+            Self::KnowsOutsider { .. } => ViolationKind::KnowsOutsider,
+        }
+    }
+
+    /// Flattens the violation into `(guest, celebrity, kind)`, where the outsider counts as the guest.
+    #[must_use]
+    pub fn into_triple(self) -> (K, K, ViolationKind) {
+        let kind = self.kind();
+        match self {
+            Self::NotKnownByEveryone { guest, celebrity }
+            | Self::KnowsOutsider {
+                celebrity,
+                outsider: guest,
+            } => (guest, celebrity, kind),
+        }
+    }
+}
+
+impl<K: Display> Display for CcliqueViolation<K> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::NotKnownByEveryone { guest, celebrity } => {
+                write!(
+                    f,
+                    "person {guest} does not know proposed celebrity {celebrity}"
+                )
+            }
+            Self::KnowsOutsider {
+                celebrity,
+                outsider,
+            } => write!(
+                f,
+                "proposed celebrity {celebrity} knows {outsider}, who is not in the clique"
+            ),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use super::{Knows, PowerSet};
+use std::collections::HashMap;
+
+const WORD: usize = u64::BITS as usize;
+
+/// The candidates of a party of any size as rows of `u64` words, so that celebrity cliques among
+/// them are checked 64 guests at a time.
+///
+/// Bit `b` of the row of a candidate is set when they know guest `b`, themselves included. Only
+/// candidates known by every guest have rows, so a subset of them is a celebrity clique as soon
+/// as their rows fit in its mask. Bits past the last guest are always clear.
+pub(super) struct WordTable<'a, T> {
+    candidates: Vec<&'a T>,
+    /// The position among the guests of each candidate.
+    positions: Vec<usize>,
+    words: usize,
+    rows: Vec<u64>,
+}
+
+impl<'a, T: Knows> WordTable<'a, T> {
+    /// The table of `candidates`, who must all be known by every one of `guests`; those who are
+    /// not guests are left out.
+    pub fn new(guests: &[&'a T], candidates: &[&'a T]) -> Self {
+        let words = guests.len().div_ceil(WORD);
+        let index = (guests.iter().enumerate())
+            .map(|(position, guest)| (guest.key(), position))
+            .collect::<HashMap<_, _>>();
+        let (candidates, positions): (Vec<_>, Vec<_>) = (candidates.iter())
+            .filter_map(|&candidate| Some((candidate, *index.get(candidate.key())?)))
+            .unzip();
+        let mut rows = vec![0; candidates.len() * words];
+        for (c, candidate) in candidates.iter().enumerate() {
+            for (b, guest) in guests.iter().enumerate() {
+                if candidate.knows(guest) {
+                    rows[c * words + b / WORD] |= 1 << (b % WORD);
+                }
+            }
+        }
+        Self {
+            candidates,
+            positions,
+            words,
+            rows,
+        }
+    }
+
+    fn row(&self, c: usize) -> &[u64] {
+        &self.rows[c * self.words..(c + 1) * self.words]
+    }
+
+    /// Writes the mask of the guests who are the candidates `subset` into `mask`.
+    fn fill(&self, subset: &[usize], mask: &mut Vec<u64>) {
+        mask.clear();
+        mask.resize(self.words, 0);
+        for &c in subset {
+            let position = self.positions[c];
+            mask[position / WORD] |= 1 << (position % WORD);
+        }
+    }
+
+    /// Whether the candidates `subset` know nobody but each other, as their rows are contained in
+    /// `mask`, which must hold the mask of `subset`.
+    fn is_cclique_with(&self, subset: &[usize], mask: &[u64]) -> bool {
+        (subset.iter()).all(|&c| (self.row(c).iter().zip(mask)).all(|(row, mask)| row & !mask == 0))
+    }
+
+    /// Whether the candidates `subset`, by their index in the table, form a celebrity clique.
+    pub fn is_cclique(&self, subset: &[usize]) -> bool {
+        let mut mask = Vec::new();
+        self.fill(subset, &mut mask);
+        !subset.is_empty() && self.is_cclique_with(subset, &mask)
+    }
+
+    /// The non-empty celebrity clique among the subsets of the candidates.
+    ///
+    /// Subsets are visited smallest first, as [`PowerSet`] orders them.
+    /// `step` is called with the size of each one first, and the search stops with its error if it fails.
+    pub fn first_cclique<E>(
+        &self,
+        mut step: impl FnMut(usize) -> Result<(), E>,
+    ) -> Result<Option<Vec<&'a T>>, E> {
+        let indices = (0..self.candidates.len()).collect::<Vec<_>>();
+        let mut mask = Vec::with_capacity(self.words);
+        for celebrities in PowerSet::<_, Vec<_>>::new(&indices).skip(1) {
+            step(celebrities.len())?;
+            self.fill(&celebrities, &mut mask);
+            if self.is_cclique_with(&celebrities, &mask) {
+                return Ok(Some(
+                    celebrities.iter().map(|&c| self.candidates[c]).collect(),
+                ));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Person,
+        clique::{is_cclique_among, known_by_everyone},
+        generator,
+    };
+    use std::convert::Infallible;
+
+    #[test]
+    fn test_tail_words() {
+        for n in [3, 63, 64, 65, 127, 128, 129, 191, 200] {
+            // Everybody knows 0, who knows nobody, and the one after them.
+            let people = (0..n)
+                .map(|id| match id {
+                    0 => Person::new(0, []),
+                    _ => Person::new(id, [0, (id + 1) % n]),
+                })
+                .collect::<Vec<_>>();
+            let guests = people.iter().collect::<Vec<_>>();
+            // The rows alone are checked: `n - 2` knows the last guest, in the last word.
+            let table = WordTable::new(&guests, &[guests[0], guests[n - 2]]);
+            assert!(table.is_cclique(&[0]), "{n} guests");
+            assert!(!table.is_cclique(&[]), "{n} guests");
+            assert!(!table.is_cclique(&[1]), "{n} guests");
+            assert!(!table.is_cclique(&[0, 1]), "{n} guests");
+            let Ok(css) = table.first_cclique(|_| Ok::<_, Infallible>(()));
+            assert_eq!(css, Some(vec![guests[0]]), "{n} guests");
+            // The last guest only knows 0, and is in the mask with them.
+            let table = WordTable::new(&guests, &[guests[n - 1], guests[0]]);
+            assert!(!table.is_cclique(&[0]), "{n} guests");
+            assert!(table.is_cclique(&[0, 1]), "{n} guests");
+        }
+    }
+
+    #[test]
+    fn test_only_guests() {
+        let people = [Person::new(1, []), Person::new(2, [1])];
+        let stranger = Person::new(3, [1]);
+        let guests = people.iter().collect::<Vec<_>>();
+        let table = WordTable::new(&guests, &[&stranger, &people[0]]);
+        let Ok(css) = table.first_cclique(|_| Ok::<_, Infallible>(()));
+        assert_eq!(css, Some(vec![&people[0]]));
+    }
+
+    #[test]
+    fn test_agrees_with_sets() {
+        for n in [5, 63, 64, 65, 130] {
+            for seed in 0..20 {
+                let party = generator::planted_party(n, 1 + seed % 4, 0.9, seed as u64);
+                let guests = party.iter().collect::<Vec<_>>();
+                let candidates = known_by_everyone(guests.iter().copied());
+                let table = WordTable::new(&guests, &candidates);
+                let mut rng = generator::Rng::new(seed as u64);
+                for _ in 0..20 {
+                    let subset = (0..candidates.len())
+                        .filter(|_| rng.chance(0.5))
+                        .collect::<Vec<_>>();
+                    let members = subset.iter().map(|&c| candidates[c]).collect::<Vec<_>>();
+                    assert_eq!(
+                        table.is_cclique(&subset),
+                        !members.is_empty() && is_cclique_among(&members, guests.iter().copied()),
+                        "{n} guests, {members:?}"
+                    );
+                }
+                let Ok(css) = table.first_cclique(|_| Ok::<_, Infallible>(()));
+                assert_eq!(css.map(|css| css.len()), Some(1 + seed % 4));
+            }
+        }
+    }
+}
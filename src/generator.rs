@@ -0,0 +1,256 @@
+//! Reproducible random parties for tests and benchmarks.
+//!
+//! The same seed always gives the same party, on every platform.
+
+use crate::Person;
+use std::collections::HashSet;
+
+/// A small `SplitMix64` generator, so that parties need no dependency to be reproducible.
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    #[must_use]
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub const fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// True with probability `p`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1_u64 << 53) as f64
+    }
+
+    /// A number in `0..n`, or 0 if `n` is 0.
+    pub fn below(&mut self, n: usize) -> usize {
+        let n = u64::try_from(n).unwrap_or(u64::MAX);
+        if n == 0 {
+            return 0;
+        }
+        usize::try_from(self.next_u64() % n).unwrap_or(0)
+    }
+}
+
+/// A party of guests `0..n` where each one knows each other one independently with probability `p`.
+///
+/// ```
+/// use celebrities::{Party, generator::random_party};
+///
+/// let party = Party::from(random_party(10, 0.3, 7));
+/// assert_eq!(party.len(), 10);
+/// assert_eq!(party.to_adjacency(), Party::from(random_party(10, 0.3, 7)).to_adjacency());
+/// ```
+#[must_use]
+pub fn random_party(n: usize, p: f64, seed: u64) -> HashSet<Person> {
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|a| {
+            Person::new(
+                a,
+                (0..n)
+                    .filter(|&b| a != b)
+                    .filter(|_| rng.chance(p))
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect()
+}
+
+/// A party of guests `0..n` where each one knows exactly `degree` others, chosen uniformly.
+///
+/// ```
+/// use celebrities::generator::fixed_degree_party;
+///
+/// let party = fixed_degree_party(10, 3, 7);
+/// assert!(party.iter().all(|person| person.known_people.len() == 3));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `degree` is not less than `n`, since nobody can know themselves.
+#[must_use]
+pub fn fixed_degree_party(n: usize, degree: usize, seed: u64) -> HashSet<Person> {
+    assert!(
+        n == 0 || degree < n,
+        "{n} guests cannot each know {degree} others"
+    );
+    let mut rng = Rng::new(seed);
+    (0..n)
+        .map(|a| {
+            let mut others = (0..n).filter(|&b| a != b).collect::<Vec<_>>();
+            for i in 0..degree {
+                let j = i + rng.below(n - 1 - i);
+                others.swap(i, j);
+            }
+            others.truncate(degree);
+            Person::new(a, others)
+        })
+        .collect()
+}
+
+/// A party of guests `0..n` with a celebrity clique of `clique_size` randomly chosen guests.
+///
+/// Everybody knows the celebrities, who know nobody else, and the other guests know each other
+/// with probability `p`.
+///
+/// # Panics
+///
+/// Panics if `clique_size` is 0 or greater than `n`.
+#[must_use]
+pub fn planted_party(n: usize, clique_size: usize, p: f64, seed: u64) -> HashSet<Person> {
+    assert!(
+        (1..=n).contains(&clique_size),
+        "a celebrity clique of {clique_size} cannot be planted among {n} guests"
+    );
+    let mut rng = Rng::new(seed);
+    let celebrities = pick(n, clique_size, &mut rng);
+    (0..n)
+        .map(|a| {
+            let others = (0..n).filter(|b| !celebrities.contains(&a) && !celebrities.contains(b));
+            let known = others.filter(|_| rng.chance(p)).collect::<Vec<_>>();
+            Person::new(a, celebrities.iter().copied().chain(known))
+        })
+        .collect()
+}
+
+/// `k` of the ids `0..n`, shuffled with `rng`.
+fn pick(n: usize, k: usize, rng: &mut Rng) -> HashSet<usize> {
+    let mut ids = (0..n).collect::<Vec<_>>();
+    for i in (1..n).rev() {
+        ids.swap(i, rng.below(i + 1));
+    }
+    ids.truncate(k);
+    ids.into_iter().collect()
+}
+
+/// `count` parties of up to `max_size` guests, every other one with a planted celebrity clique.
+pub fn sample_parties(
+    count: usize,
+    max_size: usize,
+    p: f64,
+    seed: u64,
+) -> impl Iterator<Item = HashSet<Person>> {
+    let mut rng = Rng::new(seed);
+    (0..count).map(move |i| {
+        let n = i % (max_size + 1);
+        let seed = rng.next_u64();
+        if i % 2 == 1 && n > 0 {
+            planted_party(n, 1 + rng.below(n), p, seed)
+        } else {
+            random_party(n, p, seed)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clique, Party};
+    use std::collections::HashMap;
+
+    fn adjacency(party: HashSet<Person>) -> HashMap<usize, HashSet<usize>> {
+        Party::from(party).to_adjacency()
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn density(party: &HashSet<Person>) -> f64 {
+        let n = party.len();
+        let edges = party.iter().map(|p| p.known_people.len()).sum::<usize>();
+        edges as f64 / (n * (n - 1)) as f64
+    }
+
+    #[test]
+    fn test_random_party_is_deterministic() {
+        let party = |seed| adjacency(random_party(30, 0.4, seed));
+        assert_eq!(party(47), party(47));
+        assert_ne!(party(47), party(48));
+        let party = |seed| adjacency(fixed_degree_party(30, 5, seed));
+        assert_eq!(party(47), party(47));
+        assert_ne!(party(47), party(48));
+    }
+
+    #[test]
+    fn test_random_party_density() {
+        for p in [0.0, 0.1, 0.5, 0.9, 1.0] {
+            let party = random_party(200, p, 47);
+            assert!((density(&party) - p).abs() < 0.01, "p = {p}");
+            assert!(
+                party
+                    .iter()
+                    .all(|person| !person.known_people.contains(&person.id))
+            );
+        }
+    }
+
+    #[test]
+    fn test_fixed_degree_party() {
+        for degree in [0, 1, 7, 49] {
+            let party = fixed_degree_party(50, degree, 47);
+            assert_eq!(party.len(), 50);
+            assert!(party.iter().all(|person| {
+                person.known_people.len() == degree && !person.known_people.contains(&person.id)
+            }));
+        }
+        assert!(fixed_degree_party(0, 0, 47).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot each know")]
+    fn test_fixed_degree_too_large() {
+        let _ = fixed_degree_party(5, 5, 47);
+    }
+
+    #[test]
+    fn test_planted_party() {
+        for n in 1..=12 {
+            for clique_size in 1..=n {
+                for p in [0.0, 0.5, 1.0] {
+                    let seed = u64::try_from(n * 100 + clique_size).unwrap_or_default();
+                    let planted = pick(n, clique_size, &mut Rng::new(seed));
+                    let party = planted_party(n, clique_size, p, seed);
+                    assert_eq!(
+                        party
+                            .cclique()
+                            .map(|css| css.iter().map(|person| person.id).collect()),
+                        Some(planted.clone()),
+                        "{n} guests, {clique_size} planted, p = {p}"
+                    );
+                    for person in &party {
+                        assert!(planted.iter().all(|id| person.knows_id(id)));
+                        if planted.contains(&person.id) {
+                            assert!(person.known_people.is_subset(&planted), "{person:?}");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be planted")]
+    fn test_planted_party_too_large() {
+        let _ = planted_party(3, 4, 0.5, 48);
+    }
+
+    #[test]
+    fn test_sample_parties() {
+        let parties = sample_parties(20, 6, 0.5, 46).collect::<Vec<_>>();
+        assert_eq!(parties.iter().map(HashSet::len).max(), Some(6));
+        assert!(
+            parties
+                .iter()
+                .skip(1)
+                .step_by(2)
+                .filter(|party| !party.is_empty())
+                .all(|party| party.cclique().is_some())
+        );
+    }
+}
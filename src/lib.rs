@@ -0,0 +1,12 @@
+// This implementation is based on the algorithm described in:
+// "Pearls of Functional Algorithm Design" by Richard Bird, Cambridge University Press, ISBN: 9780511763199
+// Reference: Chapter 9, Page 56 -- Finding celebrities
+
+pub mod clique;
+pub mod generator;
+#[doc(hidden)]
+pub mod macros;
+pub mod session;
+
+pub use clique::{AdjacencyClique, Clique, Knows, clique2digraph, party::Party, person::Person};
+pub use session::{Session, solve_and_render};
@@ -0,0 +1,137 @@
+use crate::{Party, Person};
+use std::collections::{HashMap, HashSet};
+
+/// Builds a [`Party`] from `id => [acquaintances]` statements.
+///
+/// `a <=> b` is shorthand for "`a` and `b` know each other"; it can be mixed freely with `=>`
+/// statements. Defining the same id twice with `=>` panics.
+///
+/// ```
+/// use celebrities::{Clique, party};
+/// use std::collections::HashSet;
+///
+/// let party = party! {
+///     1 <=> 2;
+///     2 <=> 3;
+///     3 => [1];
+///     1 => [3];
+///     4 => [1, 2, 3];
+/// };
+/// assert_eq!(party.len(), 4);
+/// assert_eq!(party.cclique().map(|css| css.ids()), Some(HashSet::from([1, 2, 3])));
+/// ```
+#[macro_export]
+macro_rules! party {
+    (@munch $people:ident;) => {};
+    (@munch $people:ident; $id:tt => [$($known:expr),* $(,)?] $(; $($rest:tt)*)?) => {
+        $people.define($id, [$($known),*]);
+        $crate::party!(@munch $people; $($($rest)*)?);
+    };
+    (@munch $people:ident; $a:tt <=> $b:tt $(; $($rest:tt)*)?) => {
+        $people.mutual($a, $b);
+        $crate::party!(@munch $people; $($($rest)*)?);
+    };
+    () => {
+        $crate::Party::new()
+    };
+    ($($body:tt)+) => {{
+        let mut people = $crate::macros::PartyMacro::default();
+        $crate::party!(@munch people; $($body)*);
+        people.finish()
+    }};
+}
+
+#[doc(hidden)]
+#[derive(Debug, Default)]
+pub struct PartyMacro {
+    people: HashMap<usize, Person>,
+    defined: HashSet<usize>,
+}
+
+impl PartyMacro {
+    /// # Panics
+    ///
+    /// Panics if `id` was already defined.
+    pub fn define(&mut self, id: usize, known_people: impl IntoIterator<Item = usize>) {
+        assert!(
+            self.defined.insert(id),
+            "party!: person {id} is defined more than once"
+        );
+        self.person(id).known_people.extend(known_people);
+    }
+
+    pub fn mutual(&mut self, a: usize, b: usize) {
+        self.person(a).known_people.insert(b);
+        self.person(b).known_people.insert(a);
+    }
+
+    fn person(&mut self, id: usize) -> &mut Person {
+        self.people.entry(id).or_insert_with(|| Person::new(id, []))
+    }
+
+    #[must_use]
+    pub fn finish(self) -> Party {
+        self.people
+            .into_values()
+            .map(|person| Person::new(person.id, person.known_people)) // drop self-references.
+            .collect::<HashSet<_>>()
+            .into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Clique;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_empty() {
+        assert!(party! {}.is_empty());
+    }
+
+    #[test]
+    fn test_trailing_semicolon() {
+        let with = party! { 1 => [2]; 2 => []; };
+        let without = party! { 1 => [2]; 2 => [] };
+        assert_eq!(with.sorted(), without.sorted());
+        assert_eq!(
+            with.get(1).map(|person| person.known_people.clone()),
+            Some(HashSet::from([2]))
+        );
+    }
+
+    #[test]
+    fn test_self_reference_is_dropped() {
+        let party = party! { 1 => [1, 2] };
+        assert_eq!(
+            party.get(1).map(|person| person.known_people.clone()),
+            Some(HashSet::from([2]))
+        );
+    }
+
+    #[test]
+    fn test_mutual() {
+        let party = party! { 1 <=> 2; 3 => [1, 2,]; };
+        assert_eq!(party.ids(), HashSet::from([1, 2, 3]));
+        assert!(
+            party
+                .get(1)
+                .is_some_and(|person| person.known_people.contains(&2))
+        );
+        assert!(
+            party
+                .get(2)
+                .is_some_and(|person| person.known_people.contains(&1))
+        );
+        assert_eq!(
+            party.cclique().map(|css| css.ids()),
+            Some(HashSet::from([1, 2]))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "person 1 is defined more than once")]
+    fn test_duplicate_definition() {
+        let _ = party! { 1 => [2]; 1 => [3] };
+    }
+}
@@ -0,0 +1,493 @@
+use crate::{
+    Clique, Party, Person,
+    clique::{CliqueError, DEFAULT_SEARCH_LIMIT, Interner},
+};
+use std::{
+    collections::BTreeSet,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+mod dot;
+
+const DOT_FILE_NAME: &str = "graph.dot";
+
+/// An image format Graphviz renders the graph into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+    Png,
+    Svg,
+    Pdf,
+}
+
+impl Format {
+    /// The file extension, which is also the name Graphviz's `-T` flag expects.
+    #[must_use]
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+            Self::Pdf => "pdf",
+        }
+    }
+}
+
+/// A Graphviz layout engine, each installed as a program of its name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Engine {
+    /// Hierarchical layers, following the edges.
+    #[default]
+    Dot,
+    /// A spring model, for undirected-looking graphs.
+    Neato,
+    /// A force-directed spring model.
+    Fdp,
+    /// A circle.
+    Circo,
+    /// Rings around a center.
+    Twopi,
+    /// Like `fdp`, for large graphs.
+    Sfdp,
+}
+
+impl Engine {
+    /// The program, which is also what Graphviz's `-K` flag expects.
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Dot => "dot",
+            Self::Neato => "neato",
+            Self::Fdp => "fdp",
+            Self::Circo => "circo",
+            Self::Twopi => "twopi",
+            Self::Sfdp => "sfdp",
+        }
+    }
+}
+
+/// Where and how [`Session::render`] writes the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderOptions {
+    output_dir: PathBuf,
+    formats: Vec<Format>,
+    engine: Engine,
+    dot_binary: Option<PathBuf>,
+    /// The ids of the celebrity clique, none when there is no clique, once the party is solved.
+    clique: Option<BTreeSet<usize>>,
+    cluster: bool,
+    merge_mutual: bool,
+    legend: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            output_dir: PathBuf::from("output"),
+            formats: vec![Format::Png],
+            engine: Engine::Dot,
+            dot_binary: None,
+            clique: None,
+            cluster: true,
+            merge_mutual: false,
+            legend: false,
+        }
+    }
+}
+
+impl RenderOptions {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = dir.into();
+        self
+    }
+
+    /// Replaces the image formats; an empty list only writes the DOT file.
+    #[must_use]
+    pub fn formats(mut self, formats: impl IntoIterator<Item = Format>) -> Self {
+        self.formats = formats.into_iter().collect();
+        self
+    }
+
+    /// The layout engine, [`Engine::Dot`] by default.
+    #[must_use]
+    pub const fn engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    /// The Graphviz program that renders the images, rather than the engine's from the `PATH`;
+    /// it is still asked for the engine.
+    #[must_use]
+    pub fn dot_binary(mut self, binary: impl Into<PathBuf>) -> Self {
+        self.dot_binary = Some(binary.into());
+        self
+    }
+
+    /// The answer of solving the party, which the graph shows: the members of its celebrity
+    /// clique are filled in gold, or the graph is captioned when there is none. Parties that were
+    /// not solved are drawn without either.
+    #[must_use]
+    pub fn clique(mut self, css: Option<&Party>) -> Self {
+        self.clique = Some(css.map_or_else(BTreeSet::new, |css| css.ids().into_iter().collect()));
+        self
+    }
+
+    /// Whether the celebrity clique is drawn in a box of its own, as it is by default.
+    #[must_use]
+    pub const fn cluster(mut self, cluster: bool) -> Self {
+        self.cluster = cluster;
+        self
+    }
+
+    /// Whether two guests who know each other are joined by a single double-headed edge rather
+    /// than two arrows. The graph still reads back as the same party.
+    #[must_use]
+    pub const fn merge_mutual(mut self, merge_mutual: bool) -> Self {
+        self.merge_mutual = merge_mutual;
+        self
+    }
+
+    /// Whether the graph has a key to its styles in its bottom right corner. It is the label of
+    /// the graph rather than nodes of it, so it does not move the guests about.
+    #[must_use]
+    pub const fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// The program that renders the images.
+    #[must_use]
+    pub fn program(&self) -> &Path {
+        (self.dot_binary.as_deref()).unwrap_or_else(|| Path::new(self.engine.name()))
+    }
+
+    #[must_use]
+    pub fn dot_path(&self) -> PathBuf {
+        self.output_dir.join(DOT_FILE_NAME)
+    }
+
+    #[must_use]
+    pub fn image_path(&self, format: Format) -> PathBuf {
+        self.dot_path().with_extension(format.extension())
+    }
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    Clique(CliqueError),
+    Io(io::Error),
+    /// The Graphviz program could not be found, after the DOT file was written.
+    GraphvizNotFound {
+        binary: PathBuf,
+    },
+    /// Graphviz is installed, but not the program of the engine asked for.
+    EngineNotFound {
+        engine: Engine,
+    },
+    /// Graphviz ran but reported a failure.
+    Graphviz {
+        format: Format,
+        stderr: String,
+    },
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Clique(err) => write!(f, "{err}"),
+            Self::Io(err) => write!(f, "{err}"),
+            Self::GraphvizNotFound { binary } => {
+                write!(f, "Graphviz `{}` was not found", binary.display())
+            }
+            Self::EngineNotFound { engine } => write!(
+                f,
+                "Graphviz is installed, but its `{}` engine was not found",
+                engine.name()
+            ),
+            Self::Graphviz { format, stderr } => {
+                write!(f, "conversion to {} failed:\n{stderr}", format.extension())
+            }
+        }
+    }
+}
+
+impl Error for SessionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Clique(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::GraphvizNotFound { .. } | Self::EngineNotFound { .. } | Self::Graphviz { .. } => {
+                None
+            }
+        }
+    }
+}
+
+impl From<CliqueError> for SessionError {
+    fn from(err: CliqueError) -> Self {
+        Self::Clique(err)
+    }
+}
+
+impl From<io::Error> for SessionError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Solves a party and renders its "knows" graph.
+///
+/// ```no_run
+/// use celebrities::{party, session::{RenderOptions, Session}};
+///
+/// let session = Session::new(party! { 1 <=> 2; 3 => [1, 2] });
+/// assert_eq!(session.solve()?.map(|css| css.len()), Some(2));
+/// let _written = session.render(&RenderOptions::new().output_dir("scratch"))?;
+/// # Ok::<(), celebrities::session::SessionError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Session {
+    party: Party,
+    names: Option<Interner>,
+}
+
+impl Session {
+    #[must_use]
+    pub const fn new(party: Party) -> Self {
+        Self { party, names: None }
+    }
+
+    /// Shows the guests by the names their ids were interned from, rather than by id.
+    #[must_use]
+    pub fn with_names(mut self, names: Interner) -> Self {
+        self.names = Some(names);
+        self
+    }
+
+    #[must_use]
+    pub const fn party(&self) -> &Party {
+        &self.party
+    }
+
+    /// The names set with [`with_names`](Session::with_names).
+    #[must_use]
+    pub const fn names(&self) -> Option<&Interner> {
+        self.names.as_ref()
+    }
+
+    /// How the guest with id `id` is shown: by name, if there are names, or else by id.
+    #[must_use]
+    pub fn display_id(&self, id: usize) -> String {
+        self.names
+            .as_ref()
+            .and_then(|names| names.resolve_id(id))
+            .map_or_else(|| id.to_string(), str::to_owned)
+    }
+
+    /// The celebrity clique of the party, searching no more than [`DEFAULT_SEARCH_LIMIT`] subsets.
+    /// Unlike [`try_solve`](Session::try_solve), acquaintances who are not at the party are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// [`CliqueError::SearchLimitExceeded`] when there are too many subsets to search.
+    pub fn solve(&self) -> Result<Option<Party>, SessionError> {
+        let css = self.party.cclique_with_limit(DEFAULT_SEARCH_LIMIT);
+        css.map_err(|err| SessionError::Clique(err.into()))
+    }
+
+    /// # Errors
+    ///
+    /// See [`Clique::try_cclique`].
+    pub fn try_solve(&self) -> Result<Option<Party>, SessionError> {
+        Ok(self.party.try_cclique()?)
+    }
+
+    /// The "knows" graph, as the DOT file [`render`](Session::render) writes, with the guests
+    /// labelled by name when there are names.
+    #[must_use]
+    pub fn to_dot(&self, options: &RenderOptions) -> String {
+        let label = |person: &Person| match &self.names {
+            Some(_) => self.display_id(person.id),
+            None => person.label(),
+        };
+        dot::write(&self.party, label, options)
+    }
+
+    /// Writes the DOT file and converts it with Graphviz, returning the paths written.
+    ///
+    /// # Errors
+    ///
+    /// Fails on any IO error, with [`SessionError::GraphvizNotFound`] when the `dot` binary is
+    /// missing, or when Graphviz rejects the input.
+    pub fn render(&self, options: &RenderOptions) -> Result<Vec<PathBuf>, SessionError> {
+        if !options.output_dir.exists() {
+            fs::create_dir_all(&options.output_dir)?;
+        }
+
+        let dot_path = options.dot_path();
+        fs::write(&dot_path, self.to_dot(options))?;
+        let mut written = vec![dot_path];
+
+        for &format in &options.formats {
+            let image_path = options.image_path(format);
+            convert(options, format)?;
+            written.push(image_path);
+        }
+        Ok(written)
+    }
+}
+
+// convert by Graphviz
+fn convert(options: &RenderOptions, format: Format) -> Result<(), SessionError> {
+    let mut command = graphviz(options, format);
+    log::debug!("running {command:?}");
+    let output = command.output().map_err(|err| {
+        if err.kind() != io::ErrorKind::NotFound {
+            return SessionError::Io(err);
+        }
+        if options.dot_binary.is_none()
+            && options.engine != Engine::Dot
+            && Command::new(Engine::Dot.name()).arg("-V").output().is_ok()
+        {
+            SessionError::EngineNotFound {
+                engine: options.engine,
+            }
+        } else {
+            SessionError::GraphvizNotFound {
+                binary: options.program().to_owned(),
+            }
+        }
+    })?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(SessionError::Graphviz {
+            format,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+}
+
+/// The Graphviz command that renders the DOT file into `format`.
+fn graphviz(options: &RenderOptions, format: Format) -> Command {
+    let mut command = Command::new(options.program());
+    command
+        .arg(format!("-T{}", format.extension()))
+        .arg(format!("-K{}", options.engine.name()))
+        .arg(options.dot_path())
+        .arg("-o")
+        .arg(options.image_path(format));
+    command
+}
+
+/// Solves `party` and renders it as PNG into `output_dir`.
+///
+/// # Errors
+///
+/// See [`Session::solve`] and [`Session::render`].
+pub fn solve_and_render(
+    party: Party,
+    output_dir: impl Into<PathBuf>,
+) -> Result<(Option<Party>, Vec<PathBuf>), SessionError> {
+    let session = Session::new(party);
+    let css = session.solve()?;
+    let options = RenderOptions::new()
+        .output_dir(output_dir)
+        .clique(css.as_ref());
+    let written = session.render(&options)?;
+    Ok((css, written))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::party;
+    use std::env;
+
+    #[test]
+    fn test_paths() {
+        let options = RenderOptions::new().output_dir("out");
+        assert_eq!(options.dot_path(), Path::new("out/graph.dot"));
+        assert_eq!(options.image_path(Format::Svg), Path::new("out/graph.svg"));
+    }
+
+    #[test]
+    fn test_graphviz_command() {
+        let args = |command: &Command| {
+            let args = command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned());
+            args.collect::<Vec<_>>()
+        };
+        let options = RenderOptions::new().output_dir("out");
+        let command = graphviz(&options, Format::Png);
+        assert_eq!(command.get_program(), "dot");
+        assert_eq!(
+            args(&command),
+            ["-Tpng", "-Kdot", "out/graph.dot", "-o", "out/graph.png"]
+        );
+
+        let options = options.engine(Engine::Circo);
+        let command = graphviz(&options, Format::Svg);
+        assert_eq!(command.get_program(), "circo");
+        assert_eq!(
+            args(&command),
+            ["-Tsvg", "-Kcirco", "out/graph.dot", "-o", "out/graph.svg"]
+        );
+
+        let command = graphviz(&options.dot_binary("/opt/graphviz/dot"), Format::Pdf);
+        assert_eq!(command.get_program(), "/opt/graphviz/dot");
+        assert_eq!(args(&command)[..2], ["-Tpdf", "-Kcirco"]);
+    }
+
+    #[test]
+    fn test_solve() {
+        let session = Session::new(party! { 1 <=> 2; 3 => [1, 2, 42] });
+        assert_eq!(
+            session.solve().ok().flatten().map(|css| css.ids().len()),
+            Some(2)
+        );
+
+        // Just over the limit: 21 guests who all know each other are all candidates.
+        let everyone = (0..21)
+            .map(|id| Person::new(id, (0..21).filter(|&other| other != id)))
+            .collect::<Party>();
+        let err = Session::new(everyone).solve();
+        assert!(
+            matches!(
+                err,
+                Err(SessionError::Clique(CliqueError::SearchLimitExceeded {
+                    party_size: 21,
+                    limit: DEFAULT_SEARCH_LIMIT
+                }))
+            ),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_render_dot_only() {
+        let dir = env::temp_dir().join(format!("celebrities-session-{}", std::process::id()));
+        let session = Session::new(party! { 1 <=> 2; 3 => [1, 2] });
+        let options = RenderOptions::new().output_dir(&dir).formats([]);
+        let written = session
+            .render(&options)
+            .expect("writing the dot file succeeds");
+
+        assert_eq!(written, [dir.join("graph.dot")]);
+        let dot = fs::read_to_string(&written[0]).expect("dot file was written");
+        assert!(dot.starts_with("digraph {"));
+        assert_eq!(dot, session.to_dot(&options));
+        fs::remove_dir_all(dir).expect("temp dir is removable");
+    }
+}
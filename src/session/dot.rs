@@ -0,0 +1,347 @@
+use super::RenderOptions;
+use crate::{Party, Person};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt::Write as _,
+};
+
+/// The attributes of the nodes of the celebrity clique.
+const CLIQUE_NODE: &str = "style = filled, fillcolor = gold, peripheries = 2";
+/// The attributes of the edges between members of the celebrity clique.
+const CLIQUE_EDGE: &str = "color = goldenrod, penwidth = 2";
+/// The attribute of an edge between two guests who know each other, when it stands for both.
+const MUTUAL_EDGE: &str = "dir = both";
+
+/// `party` as a DOT digraph, with its guests labelled by `label` over how many guests they know
+/// and are known by, and the answer of solving it shown as `options` ask: the members of its
+/// celebrity clique highlighted, and grouped in a cluster, or else a caption saying there is none.
+///
+/// The nodes are named and written in increasing id order, and the edges in increasing order of
+/// their ends, so that the same party always gives the same text. When `options` merge mutual
+/// acquaintances, each pair of guests who know each other gets one edge, from the earlier. The
+/// [`legend`] comes last, so that the cluster does not take its place.
+pub(super) fn write(
+    party: &Party,
+    label: impl Fn(&Person) -> String,
+    options: &RenderOptions,
+) -> String {
+    let clique = options.clique.as_ref();
+    let mut people = party.iter().collect::<Vec<_>>();
+    people.sort_unstable_by_key(|person| person.id);
+    let nodes = (people.iter().enumerate())
+        .map(|(node, person)| (person.id, node))
+        .collect::<HashMap<_, _>>();
+    // people outside the party are neither drawn nor counted
+    let targets = (people.iter())
+        .map(|person| {
+            let mut targets = (person.known_iter())
+                .filter_map(|id| nodes.get(&id).copied())
+                .collect::<Vec<_>>();
+            targets.sort_unstable();
+            targets
+        })
+        .collect::<Vec<_>>();
+    let mut known_by = vec![0; people.len()];
+    for &target in targets.iter().flatten() {
+        known_by[target] += 1;
+    }
+
+    let member = |id| clique.is_some_and(|clique| clique.contains(&id));
+    let node = |dot: &mut String, indent: &str, node: usize, person: &Person| {
+        let label = format!(
+            "label = \"{}\\nknows {} / known by {}\"",
+            escape(&label(person)),
+            targets[node].len(),
+            known_by[node]
+        );
+        let style = if member(person.id) { CLIQUE_NODE } else { "" };
+        let _ = writeln!(dot, "{indent}{node} {}", attributes(&label, style));
+    };
+
+    let mut dot = "digraph {\n".to_owned();
+    if clique.is_some_and(BTreeSet::is_empty) && !options.legend {
+        dot.push_str("    label = \"no celebrity clique\"\n");
+    }
+    let clustered = options.cluster && clique.is_some_and(|clique| !clique.is_empty());
+    if clustered {
+        let members = (people.iter().enumerate())
+            .filter(|(_, person)| member(person.id))
+            .collect::<Vec<_>>();
+        let names = members.iter().map(|(_, person)| label(person));
+        let names = escape(&names.collect::<Vec<_>>().join(", "));
+        dot.push_str("    subgraph cluster_celebrities {\n");
+        let _ = writeln!(dot, "        label = \"celebrity clique: {names}\"");
+        for &(position, person) in &members {
+            node(&mut dot, "        ", position, person);
+        }
+        dot.push_str("    }\n");
+    }
+    for (position, person) in people.iter().enumerate() {
+        if !(clustered && member(person.id)) {
+            node(&mut dot, "    ", position, person);
+        }
+    }
+    for (node, person) in people.iter().enumerate() {
+        for &target in &targets[node] {
+            let mutual = options.merge_mutual && targets[target].binary_search(&node).is_ok();
+            if mutual && target < node {
+                continue;
+            }
+            let style = if member(person.id) && member(people[target].id) {
+                CLIQUE_EDGE
+            } else {
+                ""
+            };
+            let dir = if mutual { MUTUAL_EDGE } else { "" };
+            let _ = writeln!(dot, "    {node} -> {target} {}", attributes(dir, style));
+        }
+    }
+    if options.legend {
+        dot.push_str("    labelloc = b\n    labeljust = r\n");
+        let _ = writeln!(dot, "    label = {}", legend(options));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// The key to the styles of the graph, as an HTML-like label with a row for each of those
+/// `options` draw, saying also when there is no celebrity clique.
+fn legend(options: &RenderOptions) -> String {
+    let mut rows = String::new();
+    match &options.clique {
+        Some(clique) if clique.is_empty() => {
+            rows.push_str(r#"<tr><td colspan="2">no celebrity clique</td></tr>"#);
+        }
+        Some(_) => rows.push_str(r#"<tr><td bgcolor="gold"> </td><td>celebrity clique</td></tr>"#),
+        None => {}
+    }
+    if options.merge_mutual {
+        rows.push_str("<tr><td>&harr;</td><td>know each other</td></tr>");
+    }
+    rows.push_str("<tr><td>&rarr;</td><td>knows</td></tr>");
+    format!(r#"<<table border="0" cellborder="1" cellspacing="0">{rows}</table>>"#)
+}
+
+/// A `[ ... ]` list of the attributes in `first` and then `rest`, either of which may be empty.
+fn attributes(first: &str, rest: &str) -> String {
+    match (first.is_empty(), rest.is_empty()) {
+        (true, true) => "[ ]".to_owned(),
+        (false, true) => format!("[ {first} ]"),
+        (true, false) => format!("[ {rest} ]"),
+        (false, false) => format!("[ {first}, {rest} ]"),
+    }
+}
+
+/// `text` as the inside of a quoted DOT string.
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Clique, clique::party::Identifiers, party};
+
+    /// The first lines of the labels of the nodes of `dot` whose attributes include `style`.
+    fn styled<'a>(dot: &'a str, style: &str) -> BTreeSet<&'a str> {
+        (dot.lines())
+            .filter(|line| line.ends_with(']') && !line.contains("->") && line.contains(style))
+            .filter_map(|line| line.split('"').nth(1)?.split("\\n").next())
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight() {
+        let party = party! { 1 <=> 2; 3 => [1, 2, 42]; 4 => [1, 2] };
+        let options = RenderOptions::new().cluster(false);
+        let solved = options.clone().clique(party.cclique().as_ref());
+        let dot = write(&party, Person::label, &solved);
+        assert_eq!(
+            styled(&dot, CLIQUE_NODE),
+            BTreeSet::from(["1", "2"]),
+            "{dot}"
+        );
+        assert_eq!(dot.matches(CLIQUE_EDGE).count(), 2, "{dot}");
+        assert_eq!(dot.matches(" -> ").count(), 6, "{dot}");
+        assert!(!dot.contains("no celebrity clique"));
+
+        let dot = write(&party, Person::label, &options);
+        assert!(styled(&dot, "fillcolor").is_empty(), "{dot}");
+        assert!(!dot.contains("no celebrity clique"));
+
+        let dot = write(&party, Person::label, &options.clique(None));
+        assert!(styled(&dot, "fillcolor").is_empty(), "{dot}");
+        assert!(dot.starts_with("digraph {\n    label = \"no celebrity clique\"\n"));
+    }
+
+    #[test]
+    fn test_cluster() {
+        let party = party! { 1 <=> 2; 3 => [1, 2, 42]; 4 => [1, 2] };
+        let options = RenderOptions::new().clique(party.cclique().as_ref());
+        let dot = write(&party, Person::label, &options);
+        assert_eq!(dot.matches('{').count(), dot.matches('}').count(), "{dot}");
+        let (_, cluster) =
+            (dot.split_once("    subgraph cluster_celebrities {\n")).expect("a cluster");
+        let (cluster, rest) = cluster
+            .split_once("    }\n")
+            .expect("the end of the cluster");
+        assert!(
+            cluster.starts_with("        label = \"celebrity clique: 1, 2\"\n"),
+            "{dot}"
+        );
+        assert_eq!(
+            styled(cluster, "label = "),
+            BTreeSet::from(["1", "2"]),
+            "{dot}"
+        );
+        assert_eq!(
+            styled(rest, "label = "),
+            BTreeSet::from(["3", "4"]),
+            "{dot}"
+        );
+        assert_eq!(
+            styled(&dot, CLIQUE_NODE),
+            BTreeSet::from(["1", "2"]),
+            "{dot}"
+        );
+        assert!(!cluster.contains("->"), "the edges are outside of it");
+
+        let read = Party::parse_dot(&dot, &mut Identifiers::Numbers).expect("the graph reads back");
+        assert_eq!(read, party! { 1 <=> 2; 3 => [1, 2]; 4 => [1, 2] });
+
+        let dot = write(&party, Person::label, &options.cluster(false));
+        assert!(!dot.contains("subgraph"), "{dot}");
+        let dot = write(&party, Person::label, &RenderOptions::new().clique(None));
+        assert!(!dot.contains("subgraph"), "{dot}");
+    }
+
+    #[test]
+    fn test_merge_mutual() {
+        let edges = |dot: &str| dot.lines().filter(|line| line.contains(" -> ")).count();
+        let options = RenderOptions::new().merge_mutual(true);
+        let triangle = party! { 1 => [2, 3]; 2 => [1, 3]; 3 => [1, 2] };
+        let dot = write(&triangle, Person::label, &options);
+        assert_eq!(edges(&dot), 3, "{dot}");
+        assert_eq!(dot.matches(MUTUAL_EDGE).count(), 3, "{dot}");
+        assert_eq!(
+            edges(&write(&triangle, Person::label, &RenderOptions::new())),
+            6
+        );
+
+        // 1 and 2 know each other, and 3 and 4 know them both.
+        let mixed = party! { 1 <=> 2; 3 => [1, 2]; 4 => [1, 2] };
+        let solved = options.clique(mixed.cclique().as_ref());
+        let dot = write(&mixed, Person::label, &solved);
+        assert_eq!(edges(&dot), 5, "{dot}");
+        assert!(
+            dot.contains(&format!("    0 -> 1 [ {MUTUAL_EDGE}, {CLIQUE_EDGE} ]\n")),
+            "{dot}"
+        );
+        assert_eq!(dot.matches(MUTUAL_EDGE).count(), 1, "{dot}");
+        assert!(
+            dot.ends_with("    2 -> 0 [ ]\n    2 -> 1 [ ]\n    3 -> 0 [ ]\n    3 -> 1 [ ]\n}\n")
+        );
+        // the counts are those of the party
+        assert!(
+            dot.contains(r#"label = "1\nknows 1 / known by 3""#),
+            "{dot}"
+        );
+
+        let read = Party::parse_dot(&dot, &mut Identifiers::Numbers).expect("the graph reads back");
+        assert_eq!(read, mixed);
+        let read = Party::parse_dot(
+            &write(
+                &triangle,
+                Person::label,
+                &RenderOptions::new().merge_mutual(true),
+            ),
+            &mut Identifiers::Numbers,
+        );
+        assert_eq!(read, Ok(triangle));
+    }
+
+    #[test]
+    fn test_legend() {
+        let party = party! { 1 <=> 2; 3 => [1, 2, 42]; 4 => [1, 2] };
+        let statements = |dot: &str| dot.lines().filter(|line| line.ends_with(']')).count();
+        let options = RenderOptions::new().clique(party.cclique().as_ref());
+        let plain = write(&party, Person::label, &options);
+        assert!(!plain.contains("<table"), "{plain}");
+
+        let dot = write(&party, Person::label, &options.clone().legend(true));
+        assert_eq!(
+            statements(&dot),
+            statements(&plain),
+            "no nodes nor edges of its own"
+        );
+        let (graph, legend) = dot.rsplit_once("    labelloc = b\n").expect("a legend");
+        assert_eq!(graph, plain.trim_end_matches("}\n"));
+        assert!(
+            legend.starts_with("    labeljust = r\n    label = <<table"),
+            "{dot}"
+        );
+        assert!(legend.contains(">celebrity clique<"), "{dot}");
+        assert!(legend.contains("&rarr;"), "{dot}");
+        assert!(!legend.contains("&harr;"), "{dot}");
+        let read = Party::parse_dot(&dot, &mut Identifiers::Numbers).expect("the graph reads back");
+        assert_eq!(read, party! { 1 <=> 2; 3 => [1, 2]; 4 => [1, 2] });
+
+        let dot = write(
+            &party,
+            Person::label,
+            &options.merge_mutual(true).legend(true),
+        );
+        assert!(dot.contains("&harr;"), "{dot}");
+        let unsolved = RenderOptions::new().clique(None).legend(true);
+        let dot = write(&party, Person::label, &unsolved);
+        assert!(dot.contains(">no celebrity clique<"), "{dot}");
+        assert!(!dot.contains("label = \"no celebrity clique\""), "{dot}");
+    }
+
+    #[test]
+    fn test_deterministic() {
+        // Each set of people iterates in an order of its own.
+        let parties =
+            (0..8).map(|_| party! { 1 => [4, 2, 3]; 2 => [4, 3]; 3 => [4, 2]; 4 => [2, 3] });
+        let mut dots = parties.map(|party| write(&party, Person::label, &RenderOptions::new()));
+        let first = dots.next().expect("a party");
+        assert!(dots.all(|dot| dot == first), "{first}");
+        assert_eq!(
+            first,
+            "digraph {\n    0 [ label = \"1\\nknows 3 / known by 0\" ]\n    \
+             1 [ label = \"2\\nknows 2 / known by 3\" ]\n    \
+             2 [ label = \"3\\nknows 2 / known by 3\" ]\n    \
+             3 [ label = \"4\\nknows 2 / known by 3\" ]\n    0 -> 1 [ ]\n    0 -> 2 [ ]\n    0 -> 3 [ ]\n    1 -> 2 [ ]\n    \
+             1 -> 3 [ ]\n    2 -> 1 [ ]\n    2 -> 3 [ ]\n    3 -> 1 [ ]\n    3 -> 2 [ ]\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        let party = party! { 1 => [] };
+        let dot = write(
+            &party,
+            |_| r#"say "hi" \o/"#.to_owned(),
+            &RenderOptions::new(),
+        );
+        assert!(
+            dot.contains(r#"[ label = "say \"hi\" \\o/\nknows 0 / known by 0" ]"#),
+            "{dot}"
+        );
+    }
+
+    #[test]
+    fn test_counts() {
+        let party = party! { 1 <=> 2; 3 => [1, 2, 42]; 4 => [] };
+        let dot = write(&party, Person::label, &RenderOptions::new());
+        for label in [
+            r"1\nknows 1 / known by 2",
+            r"2\nknows 1 / known by 2",
+            // 42 is not at the party, so does not count.
+            r"3\nknows 2 / known by 0",
+            r"4\nknows 0 / known by 0",
+        ] {
+            assert!(dot.contains(&format!("label = \"{label}\"")), "{dot}");
+        }
+    }
+}
@@ -0,0 +1,1442 @@
+//! The subcommands of the binary, with their exit codes.
+
+mod common;
+
+use celebrities::{Party, Person, clique::party::Identifiers};
+use common::{fixture, run, run_in, run_with_stdin, scratch, stderr, stdout};
+use std::{
+    env,
+    ffi::{OsStr, OsString},
+    fs,
+    io::{BufRead, BufReader, Read},
+    process::{Command, Stdio},
+    sync::mpsc,
+    thread,
+    time::Duration,
+};
+
+/// What solving the demo party prints: its celebrity clique, 1, 2 and 3.
+const DEMO_CLIQUE: &str = "[
+    \"id: 1 knows {2, 3}\",
+    \"id: 2 knows {1, 3}\",
+    \"id: 3 knows {1, 2}\",
+]
+";
+
+fn has_graphviz() -> bool {
+    Command::new("dot").arg("-V").output().is_ok()
+}
+
+#[test]
+fn test_solve() {
+    let (output, _) = run(
+        "solve",
+        &["solve".as_ref(), fixture("demo.txt").as_os_str()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), DEMO_CLIQUE);
+    assert!(stderr(&output).contains("warning:"));
+
+    let (output, dir) = run(
+        "solve-none",
+        &["solve".as_ref(), fixture("no_celebrities.txt").as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(1), "no clique is not an error");
+    assert_eq!(common::stdout(&output), "no celebrity clique\n");
+    assert!(!dir.join("output").exists(), "solve does not render");
+}
+
+#[test]
+fn test_solve_merged() {
+    let (a, b) = (fixture("merge_a.txt"), fixture("merge_b.json"));
+    // Alone, the first file says 3 knows only 1, so 2 is not known by everyone.
+    let (output, _) = run("solve-merged-alone", &["solve".as_ref(), a.as_os_str()]);
+    assert_eq!(output.status.code(), Some(1));
+
+    let args = [
+        "solve".as_ref(),
+        a.as_os_str(),
+        b.as_os_str(),
+        "--json".as_ref(),
+    ];
+    let (output, _) = run("solve-merged", &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let json = json(&output);
+    assert_eq!(json["party_size"], 4);
+    assert_eq!(json["clique"], serde_json::json!([1, 2]));
+    let counts = stderr(&output);
+    assert!(
+        counts.contains("read 3 people and 3 acquaintances from ")
+            && counts.contains("read 2 people and 3 acquaintances from "),
+        "{counts}"
+    );
+
+    let args = [
+        "solve".as_ref(),
+        a.as_os_str(),
+        b.as_os_str(),
+        "--strict".as_ref(),
+    ];
+    let (output, _) = run("solve-merged-strict", &args);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("merge_b.json: person 3 is described differently in "),
+        "{}",
+        stderr(&output)
+    );
+
+    // Nothing differs when a file is merged with itself.
+    let args = [
+        "solve".as_ref(),
+        a.as_os_str(),
+        a.as_os_str(),
+        "--strict".as_ref(),
+        "-q".as_ref(),
+    ];
+    let (output, _) = run("solve-merged-same", &args);
+    assert_eq!(output.status.code(), Some(1), "{}", stderr(&output));
+    assert!(stderr(&output).is_empty());
+
+    let args = [
+        "solve".as_ref(),
+        a.as_os_str(),
+        b.as_os_str(),
+        "--watch".as_ref(),
+    ];
+    let (output, _) = run("solve-merged-watch", &args);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("single input"));
+}
+
+#[test]
+fn test_solve_candidates() {
+    let demo = fixture("demo.txt");
+    let solve = |test: &str, flags: &[&str]| {
+        let mut args = vec![OsStr::new("solve"), demo.as_os_str()];
+        args.extend(flags.iter().map(OsStr::new));
+        run(test, &args).0
+    };
+    let restricted = solve("candidates", &["--candidates", "1,2,3,6"]);
+    assert!(restricted.status.success(), "{}", stderr(&restricted));
+    let text = stdout(&restricted);
+    assert!(
+        text.starts_with("only 1, 2, 3, 6 may be celebrities:\n["),
+        "{text}"
+    );
+    assert_eq!(text.matches("\"id: ").count(), 3, "{text}");
+
+    let full = json(&solve("candidates-full", &["--json"]));
+    let restricted = json(&solve(
+        "candidates-json",
+        &["--candidates", "3,2,1", "--json"],
+    ));
+    assert_eq!(restricted["clique"], full["clique"]);
+    assert_eq!(restricted["candidates"], serde_json::json!([1, 2, 3]));
+
+    // A subset of the clique is not it.
+    for (test, candidates) in [("candidates-others", "4,5,6,7"), ("candidates-some", "1,2")] {
+        let output = solve(test, &["--candidates", candidates]);
+        assert_eq!(output.status.code(), Some(1), "{candidates}");
+        assert!(
+            stdout(&output).ends_with("\nno celebrity clique\n"),
+            "{candidates}"
+        );
+    }
+
+    for (test, candidates, error) in [
+        (
+            "candidates-stranger",
+            "1,42",
+            "candidate 42 is not at the party",
+        ),
+        ("candidates-invalid", "1,x", "`x` is not an id"),
+    ] {
+        let output = solve(test, &["--candidates", candidates]);
+        assert_eq!(output.status.code(), Some(2), "{candidates}");
+        assert!(stderr(&output).contains(error), "{}", stderr(&output));
+    }
+
+    let names = "alice: bob\nbob: alice\ncarol: alice, bob\n";
+    let args = ["solve", "--candidates", "alice, bob"];
+    let (output, _) = run_with_stdin("candidates-names", &args, names);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("only alice, bob may be celebrities:\n"));
+    let args = ["solve", "--candidates", "alice,zoe"];
+    let (output, _) = run_with_stdin("candidates-names-stranger", &args, names);
+    assert!(stderr(&output).contains("candidate `zoe` is not at the party"));
+}
+
+#[test]
+fn test_solve_many_candidates() {
+    let everyone = (0..40).map(|id| id.to_string()).collect::<Vec<_>>();
+    let party = |known: &dyn Fn(usize) -> Vec<String>| {
+        (0..40)
+            .map(|id| format!("{id}: {}", known(id).join(", ")))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+    let args = ["solve", "-", "--candidates", &everyone.join(",")];
+
+    // Only 0 is known by everybody, so the other 39 candidates are not searched at all.
+    let star = party(&|id| {
+        if id == 0 {
+            Vec::new()
+        } else {
+            vec!["0".to_owned()]
+        }
+    });
+    let (output, _) = run_with_stdin("many-candidates", &args, &star);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).ends_with("\n[\n    \"id: 0 knows {}\",\n]\n"));
+
+    // Everybody knows everybody: too many candidates to search.
+    let complete = party(&|id| {
+        (everyone.iter())
+            .filter(|other| **other != id.to_string())
+            .cloned()
+            .collect()
+    });
+    let (output, _) = run_with_stdin("many-candidates-limit", &args, &complete);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+    assert!(
+        stderr(&output).contains("subsets to search"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_solve_failures() {
+    let (output, _) = run(
+        "solve-invalid",
+        &["solve".as_ref(), fixture("invalid.txt").as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("invalid.txt: line 3:"));
+
+    // Without a file, an empty stdin is an empty party.
+    let (output, _) = run("solve-empty", &["solve"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("empty"), "{}", stderr(&output));
+
+    let (output, _) = run("solve-usage", &["solve", "--no-such-flag"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_exit_codes() {
+    let demo = fixture("demo.txt");
+    let none = fixture("no_celebrities.txt");
+    let invalid = fixture("invalid.txt");
+    for (args, code) in [
+        (vec!["solve".as_ref(), demo.as_os_str()], 0),
+        (vec![demo.as_os_str(), "--no-render".as_ref()], 0),
+        (vec!["solve".as_ref(), none.as_os_str()], 1),
+        (vec![none.as_os_str(), "--no-render".as_ref()], 1),
+        (vec!["solve".as_ref(), invalid.as_os_str()], 2),
+        (vec!["solve".as_ref(), "nobody.txt".as_ref()], 2),
+        (vec!["validate".as_ref(), invalid.as_os_str()], 2),
+        (vec!["solve".as_ref(), "--no-such-flag".as_ref()], 2),
+    ] {
+        let (output, _) = run("exit-codes", &args);
+        assert_eq!(
+            output.status.code(),
+            Some(code),
+            "{args:?}: {}",
+            stderr(&output)
+        );
+    }
+}
+
+#[test]
+fn test_verbosity() {
+    let demo = fixture("demo.txt");
+    let stderr_of = |args: &[&std::ffi::OsStr]| {
+        let (output, _) = run("verbosity", args);
+        assert!(
+            stdout(&output).contains("\"id: 1 knows"),
+            "{}",
+            stderr(&output)
+        );
+        stderr(&output)
+    };
+
+    let default = stderr_of(&["solve".as_ref(), demo.as_os_str()]);
+    assert!(
+        default.contains("warning: people not at the party"),
+        "{default}"
+    );
+    assert!(!default.contains("info:"), "{default}");
+
+    let verbose = stderr_of(&["solve".as_ref(), demo.as_os_str(), "-v".as_ref()]);
+    for line in [
+        "info: reading ",
+        "demo.txt as text, going by its contents",
+        "info: 7 guest(s), with 23 acquaintance(s) between them",
+        "info: solved with exhaustive (auto) in ",
+    ] {
+        assert!(verbose.contains(line), "{verbose}");
+    }
+    assert!(!verbose.contains("debug:"), "{verbose}");
+
+    let missing = env::temp_dir().join("celebrities-no-such-dot");
+    let args = [
+        demo.as_os_str(),
+        "-vv".as_ref(),
+        "--dot-binary".as_ref(),
+        missing.as_os_str(),
+    ];
+    let debug = stderr_of(&args);
+    assert!(debug.contains("debug: running \""), "{debug}");
+    assert!(debug.contains("\"-Tpng\" \"-Kdot\""), "{debug}");
+
+    for quiet in [
+        [demo.as_os_str(), "-q".as_ref()],
+        [demo.as_os_str(), "--quiet".as_ref()],
+    ] {
+        assert_eq!(stderr_of(&quiet), "");
+    }
+    let (output, _) = run("verbosity-conflict", &["solve", "-q", "-v"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+fn json(output: &std::process::Output) -> serde_json::Value {
+    serde_json::from_str(&stdout(output)).expect("stdout is one JSON object")
+}
+
+#[test]
+fn test_json() {
+    let demo = fixture("demo.txt");
+    let (output, _) = run(
+        "json",
+        &["solve".as_ref(), demo.as_os_str(), "--json".as_ref()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let answer = json(&output);
+    assert_eq!(answer["clique"], serde_json::json!([1, 2, 3]));
+    assert_eq!(answer["found"], true);
+    assert_eq!(answer["party_size"], 7);
+    assert_eq!(answer["files"], serde_json::json!([]));
+    assert!(answer.get("names").is_none());
+    assert!(
+        stderr(&output).contains("warning:"),
+        "chatter goes to stderr"
+    );
+
+    let args = [demo.as_os_str(), "--json".as_ref(), "--no-render".as_ref()];
+    let (output, _) = run("json-render", &args);
+    assert_eq!(
+        json(&output)["files"],
+        serde_json::json!(["output/graph.dot"])
+    );
+
+    let names = fixture("names.txt");
+    let (output, _) = run(
+        "json-names",
+        &["solve".as_ref(), names.as_os_str(), "--json".as_ref()],
+    );
+    let answer = json(&output);
+    // in the order they first appear, by which they are numbered
+    assert_eq!(
+        answer["names"],
+        serde_json::json!(["Mary Ann", "bob", "Nat King"])
+    );
+    assert_eq!(answer["clique"], serde_json::json!([0, 1, 2]));
+
+    let none = fixture("no_celebrities.txt");
+    let (output, _) = run(
+        "json-none",
+        &["solve".as_ref(), none.as_os_str(), "--json".as_ref()],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(json(&output)["found"], false);
+    assert_eq!(json(&output)["clique"], serde_json::json!([]));
+
+    let args = [
+        "render".as_ref(),
+        none.as_os_str(),
+        "--json".as_ref(),
+        "--no-render".as_ref(),
+    ];
+    let (output, _) = run("json-render-only", &args);
+    assert!(
+        json(&output).get("found").is_none(),
+        "render does not solve"
+    );
+    assert_eq!(json(&output)["party_size"], 3);
+}
+
+#[test]
+fn test_solve_dir() {
+    let parties = env::temp_dir().join("celebrities-solve-dir-parties");
+    let _ = fs::remove_dir_all(&parties);
+    fs::create_dir_all(parties.join("more")).expect("scratch directory");
+    for (name, copy) in [
+        ("demo.txt", "demo.txt"),
+        ("no_celebrities.txt", "none.txt"),
+        ("invalid.txt", "invalid.txt"),
+        ("demo.json", "more/demo.json"),
+    ] {
+        fs::copy(fixture(name), parties.join(copy)).expect("fixture is copied");
+    }
+    fs::write(parties.join("notes.md"), "not a party").expect("scratch file");
+
+    let (output, dir) = run(
+        "solve-dir",
+        &["solve".as_ref(), "--dir".as_ref(), parties.as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(2), "one file is malformed");
+    let summary = stdout(&output);
+    let lines = summary.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 5, "{summary}");
+    assert!(lines[0].starts_with("file ") && lines[0].ends_with(" result"));
+    assert!(lines[1].starts_with("demo.txt ") && lines[1].ends_with(" {1, 2, 3}"));
+    assert!(lines[2].starts_with("invalid.txt ") && lines[2].contains(" error: "));
+    assert!(lines[2].contains("invalid.txt: line 3:"), "{summary}");
+    assert!(lines[3].starts_with("none.txt ") && lines[3].ends_with(" none"));
+    assert_eq!(lines[4], "3 file(s): 1 clique(s), 1 without, 1 error(s)");
+    assert!(stderr(&output).contains("1 of 3 file(s) failed"));
+
+    let result = fs::read_to_string(dir.join("output/demo.txt.txt")).expect("result is written");
+    assert!(result.contains("\"id: 1 knows"), "{result}");
+    let result = fs::read_to_string(dir.join("output/none.txt.txt")).expect("result is written");
+    assert_eq!(result, "no celebrity clique\n");
+    assert!(!dir.join("output/invalid.txt.txt").exists());
+
+    let (output, dir) = run(
+        "solve-dir-recursive",
+        &[
+            "solve".as_ref(),
+            "--dir".as_ref(),
+            parties.as_os_str(),
+            "--recursive".as_ref(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout(&output).contains("4 file(s): 2 clique(s), 1 without, 1 error(s)"));
+    assert!(dir.join("output/more/demo.json.txt").exists());
+
+    let (output, _) = run(
+        "solve-dir-json",
+        &[
+            "solve".as_ref(),
+            "--dir".as_ref(),
+            parties.as_os_str(),
+            "--json".as_ref(),
+        ],
+    );
+    let files = json(&output)["files"].clone();
+    assert_eq!(files[0]["file"], "demo.txt");
+    assert_eq!(files[0]["clique"], serde_json::json!([1, 2, 3]));
+    assert!(files[1]["error"].is_string(), "{files}");
+    assert_eq!(files[2]["found"], false);
+
+    let (output, _) = run("solve-dir-usage", &["solve", "--recursive"]);
+    assert_eq!(output.status.code(), Some(2), "--recursive needs --dir");
+}
+
+/// The lines read from `pipe`, as they come.
+fn lines(pipe: impl Read + Send + 'static) -> mpsc::Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    receiver
+}
+
+#[test]
+fn test_solve_watch() {
+    let dir = env::temp_dir().join("celebrities-solve-watch");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("scratch directory");
+    let party = dir.join("party.txt");
+    fs::write(&party, "1: 2\n2: 1\n").expect("scratch file");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_celebrities"))
+        .args(["solve".as_ref(), "--watch".as_ref(), party.as_os_str()])
+        .args(["--debounce", "50"])
+        .current_dir(&dir)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("binary runs");
+    let stdout = lines(child.stdout.take().expect("stdout is piped"));
+    let stderr = lines(child.stderr.take().expect("stderr is piped"));
+    let wait_for = |lines: &mpsc::Receiver<String>, text: &str| loop {
+        let line = (lines.recv_timeout(Duration::from_secs(10)))
+            .unwrap_or_else(|_| panic!("no line with {text:?}"));
+        if line.contains(text) {
+            break;
+        }
+    };
+
+    wait_for(&stdout, "\"id: 2 knows");
+    // A burst of saves is solved once it settles, and a bad save does not end the watch.
+    fs::write(&party, "1: 2\n2: two\n").expect("scratch file");
+    wait_for(&stderr, "party.txt: line 2:");
+    for _ in 0..3 {
+        fs::write(&party, "1: 2\n2: 3\n3: 1\n").expect("scratch file");
+    }
+    wait_for(&stdout, "no celebrity clique");
+    fs::write(&party, "1: 2\n2: 1\n3: 1, 2\n").expect("scratch file");
+    wait_for(&stdout, "\"id: 1 knows");
+
+    child.kill().expect("watch is stopped");
+    let _ = child.wait();
+
+    let (output, _) = run("solve-watch-usage", &["solve", "--watch"]);
+    assert_eq!(output.status.code(), Some(2), "--watch needs a file");
+}
+
+#[test]
+fn test_repl() {
+    let commands = "add 8 knows 1 2 3\nremove 4\nforget 5 2\nshow\ndance\nsolve\nquit\nshow\n";
+    let input = fixture("demo.txt");
+    let (output, _) = run_with_stdin("repl", &["repl".as_ref(), input.as_os_str()], commands);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let transcript = stdout(&output);
+    assert!(
+        transcript.starts_with("1: 2, 3\n2: 1, 3\n3: 1, 2\n5: 1, 3\n6: 1, 2, 3, 7\n"),
+        "{transcript}"
+    );
+    assert!(transcript.contains("8: 1, 2, 3\n"), "{transcript}");
+    assert!(transcript.contains("error: unknown command `dance`\ncommands:\n"));
+    assert!(
+        transcript.contains("no celebrity clique"),
+        "5 forgot 2: {transcript}"
+    );
+    assert_eq!(
+        transcript.matches("1: 2, 3").count(),
+        1,
+        "nothing after quit"
+    );
+}
+
+#[test]
+fn test_render() {
+    let input = fixture("no_celebrities.txt");
+    let args = [
+        "render".as_ref(),
+        input.as_os_str(),
+        "--output-dir".as_ref(),
+        "graphs".as_ref(),
+        "-v".as_ref(),
+    ];
+    let (output, dir) = run("render", &args);
+    let dot = fs::read_to_string(dir.join("graphs/graph.dot")).expect("dot file was written");
+    assert_eq!(dot.matches("->").count(), 3, "{dot}");
+    assert!(stdout(&output).is_empty());
+    assert!(output.status.success(), "{}", stderr(&output));
+    if has_graphviz() {
+        assert!(dir.join("graphs/graph.png").exists());
+        assert!(stderr(&output).contains("graph.png"));
+    } else {
+        assert!(stderr(&output).contains("not found"), "{}", stderr(&output));
+    }
+}
+
+#[test]
+fn test_output_dirs_are_independent() {
+    let dir = env::temp_dir().join("celebrities-output-dirs");
+    let _ = fs::remove_dir_all(&dir);
+    for (name, input) in [("a", "demo.txt"), ("b", "no_celebrities.txt")] {
+        let (input, output_dir) = (fixture(input), dir.join(name));
+        let args = [
+            "render".as_ref(),
+            input.as_os_str(),
+            "--output-dir".as_ref(),
+            output_dir.as_os_str(),
+        ];
+        let (output, _) = run(&format!("output-dir-{name}"), &args);
+        if has_graphviz() {
+            let wrote = format!("wrote {}", output_dir.join("graph.png").display());
+            assert!(stderr(&output).contains(&wrote), "{}", stderr(&output));
+        }
+    }
+    let a = fs::read_to_string(dir.join("a/graph.dot")).expect("dot file was written");
+    let b = fs::read_to_string(dir.join("b/graph.dot")).expect("dot file was written");
+    assert_eq!(a.matches("->").count(), 22, "{a}");
+    assert_eq!(b.matches("->").count(), 3, "{b}");
+}
+
+#[test]
+fn test_render_formats() {
+    let input = fixture("demo.txt");
+    let args = [
+        "render".as_ref(),
+        input.as_os_str(),
+        "--format".as_ref(),
+        "svg".as_ref(),
+        "--format".as_ref(),
+        "pdf".as_ref(),
+    ];
+    let (output, dir) = run("render-formats", &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    if !has_graphviz() {
+        assert!(
+            stderr(&output).contains("skipping SVG, PDF"),
+            "{}",
+            stderr(&output)
+        );
+        return;
+    }
+    assert!(
+        !dir.join("output/graph.png").exists(),
+        "only the formats asked for"
+    );
+    assert!(dir.join("output/graph.pdf").exists());
+    let svg = fs::read_to_string(dir.join("output/graph.svg")).expect("svg was written");
+    // past the XML declaration, the doctype and comments, the root element
+    let root = svg
+        .match_indices('<')
+        .map(|(at, _)| &svg[at..])
+        .find(|tag| !tag.starts_with("<?") && !tag.starts_with("<!"));
+    assert!(root.is_some_and(|root| root.starts_with("<svg")), "{svg}");
+}
+
+#[test]
+fn test_no_render() {
+    // Graphviz would fail to start, if it were started at all.
+    let missing = env::temp_dir().join("celebrities-no-such-dot");
+    for flag in ["--no-render", "--dot-only"] {
+        let args = [
+            fixture("demo.txt").into_os_string(),
+            flag.into(),
+            "--dot-binary".into(),
+            missing.clone().into_os_string(),
+        ];
+        let (output, dir) = run("no-render", &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert!(stdout(&output).contains("\"id: 1 knows"));
+        assert!(dir.join("output/graph.dot").exists());
+        assert!(!dir.join("output/graph.png").exists());
+    }
+
+    let args = [
+        "render".into(),
+        fixture("demo.txt").into_os_string(),
+        "--dot-binary".into(),
+        missing.into_os_string(),
+        "--require-render".into(),
+    ];
+    let (output, dir) = run("no-render-missing", &args);
+    assert_eq!(
+        output.status.code(),
+        Some(2),
+        "the binary is run without the flag"
+    );
+    assert!(stderr(&output).contains("celebrities-no-such-dot"));
+    assert!(dir.join("output/graph.dot").exists());
+}
+
+#[test]
+fn test_missing_graphviz() {
+    let path = env::temp_dir().join("celebrities-empty-path");
+    fs::create_dir_all(&path).expect("scratch directory");
+    let dir = env::temp_dir().join("celebrities-missing-graphviz");
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("scratch directory");
+    let binary = |args: &[&str]| {
+        Command::new(env!("CARGO_BIN_EXE_celebrities"))
+            .arg(fixture("demo.txt"))
+            .args(args)
+            .current_dir(&dir)
+            .env("PATH", &path)
+            .stdin(Stdio::null())
+            .output()
+            .expect("binary runs")
+    };
+
+    let output = binary(&[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("\"id: 1 knows"));
+    assert_eq!(
+        stderr(&output).lines().last(),
+        Some("warning: Graphviz 'dot' not found; wrote output/graph.dot, skipping PNG")
+    );
+    assert!(dir.join("output/graph.dot").exists());
+
+    let output = binary(&["--require-render"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("error: Graphviz `dot` was not found"));
+}
+
+/// A directory for `PATH` with fake Graphviz programs of the given names, which write their
+/// arguments into the image.
+#[cfg(unix)]
+fn fake_graphviz(test: &str, programs: &[&str]) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = env::temp_dir().join(format!("celebrities-{test}-path"));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).expect("scratch directory");
+    for program in programs {
+        let script = path.join(program);
+        let script_text = "#!/bin/sh\n\
+            [ \"$1\" = -V ] && exit 0\n\
+            for arg; do image=$arg; done\n\
+            echo \"$@\" > \"$image\"\n";
+        fs::write(&script, script_text).expect("scratch file");
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).expect("script runs");
+    }
+    path
+}
+
+#[cfg(unix)]
+#[test]
+fn test_engine() {
+    let path = fake_graphviz("engine", &["dot", "neato"]);
+    let binary = |args: &[&str]| {
+        let dir = env::temp_dir().join("celebrities-engine");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("scratch directory");
+        let output = Command::new(env!("CARGO_BIN_EXE_celebrities"))
+            .arg("render")
+            .arg(fixture("demo.txt"))
+            .args(args)
+            .current_dir(&dir)
+            .env("PATH", &path)
+            .stdin(Stdio::null())
+            .output()
+            .expect("binary runs");
+        (output, dir)
+    };
+
+    let (output, dir) = binary(&["--engine", "neato"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stderr(&output).contains("wrote output/graph.png with neato"));
+    let image = fs::read_to_string(dir.join("output/graph.png")).expect("image was written");
+    assert_eq!(
+        image,
+        "-Tpng -Kneato output/graph.dot -o output/graph.png\n"
+    );
+
+    let (output, _) = binary(&["--engine", "circo"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("Graphviz is installed, but its `circo` engine was not found"),
+        "{}",
+        stderr(&output)
+    );
+
+    let (output, _) = binary(&["--engine", "spring"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_config() {
+    let dir = scratch("config");
+    let demo = fixture("demo.txt");
+    let render = |flags: &[&str]| {
+        let mut args = vec![OsStr::new("render"), demo.as_os_str()];
+        args.extend(["--dot-binary", "/nonexistent/dot"].map(OsStr::new));
+        args.extend(flags.iter().map(OsStr::new));
+        let _ = fs::remove_dir_all(dir.join("output"));
+        let _ = fs::remove_dir_all(dir.join("from-config"));
+        let _ = fs::remove_dir_all(dir.join("from-flag"));
+        run_in(&dir, &args, b"")
+    };
+
+    // The built-in defaults.
+    let output = render(&[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.join("output/graph.dot").exists());
+    assert!(
+        stderr(&output).contains("skipping PNG\n"),
+        "{}",
+        stderr(&output)
+    );
+
+    fs::write(
+        dir.join("celebrities.toml"),
+        "output-dir = \"from-config\"\nformat = [\"svg\", \"pdf\"]\ncolour = \"red\"\n",
+    )
+    .expect("scratch file");
+    let output = render(&[]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.join("from-config/graph.dot").exists());
+    assert!(!dir.join("output").exists());
+    assert!(
+        stderr(&output).contains("skipping SVG, PDF\n"),
+        "{}",
+        stderr(&output)
+    );
+    assert!(
+        stderr(&output).contains("warning: celebrities.toml: unknown key `colour`, ignored"),
+        "{}",
+        stderr(&output)
+    );
+
+    let output = render(&["--output-dir", "from-flag", "--format", "png"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.join("from-flag/graph.dot").exists());
+    assert!(!dir.join("from-config").exists());
+    assert!(
+        stderr(&output).contains("skipping PNG\n"),
+        "{}",
+        stderr(&output)
+    );
+
+    fs::write(dir.join("other.toml"), "no-render = true\n").expect("scratch file");
+    let output = render(&["--config", "other.toml"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(dir.join("output/graph.dot").exists());
+    assert!(!stderr(&output).contains("skipping"), "{}", stderr(&output));
+    let output = render(&["--config", "other.toml", "--require-render"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("Graphviz `/nonexistent/dot` was not found"));
+
+    fs::write(dir.join("bad.toml"), "engine = \"spring\"\n").expect("scratch file");
+    for (config, error) in [
+        ("bad.toml", "unknown variant `spring`"),
+        ("missing.toml", "missing.toml:"),
+    ] {
+        let output = render(&["--config", config]);
+        assert_eq!(output.status.code(), Some(2), "{config}");
+        assert!(
+            stderr(&output).contains(error),
+            "{config}: {}",
+            stderr(&output)
+        );
+    }
+}
+
+#[test]
+fn test_render_failures() {
+    let (output, dir) = run(
+        "render-invalid",
+        &["render".as_ref(), fixture("invalid.txt").as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(!dir.join("output").exists());
+
+    let (output, _) = run("render-missing", &["render", "nobody.txt"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("nobody.txt"));
+
+    let (output, _) = run("render-gif", &["render", "nobody.txt", "--format", "gif"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_generate() {
+    let args = ["generate", "--n", "6", "--p", "0.4", "--seed", "64"];
+    let (output, _) = run("generate", &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let text = stdout(&output);
+    assert_eq!(text.lines().count(), 6);
+    let party = text
+        .parse::<Party>()
+        .expect("generated parties can be read back");
+    assert_eq!(party.len(), 6);
+
+    let (again, _) = run("generate-again", &args);
+    assert_eq!(stdout(&again), text, "the same seed gives the same party");
+}
+
+#[test]
+fn test_generate_planted() {
+    let args = [
+        "generate",
+        "--n",
+        "16",
+        "--p",
+        "0.2",
+        "--seed",
+        "7",
+        "--plant-clique",
+        "3",
+        "-o",
+        "party.txt",
+    ];
+    let (output, dir) = run("generate-planted", &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).is_empty());
+    assert!(
+        stderr(&output).contains("16 guest(s)") && stderr(&output).contains("clique of 3"),
+        "{}",
+        stderr(&output)
+    );
+    let party = fs::read_to_string(dir.join("party.txt")).expect("the party is written");
+    let (again, dir) = run("generate-planted-again", &args);
+    assert!(again.status.success(), "{}", stderr(&again));
+    assert_eq!(
+        fs::read_to_string(dir.join("party.txt")).expect("the party is written"),
+        party,
+        "the same seed gives the same file"
+    );
+
+    for format in ["text", "json", "toml", "yaml", "dot", "matrix", "edges"] {
+        let file = format!("party.{format}");
+        let mut args = args.to_vec();
+        args.extend(["--output-format", format]);
+        *args
+            .iter_mut()
+            .find(|arg| **arg == "party.txt")
+            .expect("an output") = &file;
+        let (output, dir) = run(&format!("generate-planted-{format}"), &args);
+        assert!(output.status.success(), "{format}: {}", stderr(&output));
+
+        let input = dir.join(&file);
+        let solve = [
+            "--input-format".as_ref(),
+            format.as_ref(),
+            "--no-render".as_ref(),
+            "--json".as_ref(),
+            input.as_os_str(),
+        ];
+        let (solved, _) = run(&format!("generate-planted-{format}-solve"), &solve);
+        assert!(solved.status.success(), "{format}: {}", stderr(&solved));
+        let clique = &json(&solved)["clique"];
+        assert_eq!(
+            clique.as_array().map(Vec::len),
+            Some(3),
+            "{format}: {clique}"
+        );
+    }
+}
+
+#[test]
+fn test_generate_failures() {
+    let (output, _) = run("generate-p", &["generate", "--n", "6", "--p", "1.5"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("not between 0 and 1"));
+
+    let (output, _) = run("generate-n", &["generate"]);
+    assert_eq!(output.status.code(), Some(2));
+
+    let args = ["generate", "--n", "6", "--plant-clique", "7"];
+    let (output, _) = run("generate-plant", &args);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("cannot plant a clique of 7 among 6"));
+}
+
+#[test]
+fn test_validate() {
+    let input = fixture("no_celebrities.txt");
+    let args = ["validate".as_ref(), input.as_os_str(), "-v".as_ref()];
+    let (output, _) = run("validate", &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).is_empty());
+    assert!(stderr(&output).contains("3 people, no problems"));
+}
+
+#[test]
+fn test_validate_problems() {
+    for (file, problems) in [
+        (
+            "demo.txt",
+            &["stranger: 42 is not at the party, but 4 knows them"][..],
+        ),
+        (
+            "isolated.txt",
+            &["isolated: 4 knows nobody at the party, and nobody knows them"],
+        ),
+        (
+            "self_reference.txt",
+            &[
+                "self-reference: line 3: 2 knows themselves",
+                "self-reference: line 4: 3 knows themselves",
+            ],
+        ),
+        (
+            "duplicate.txt",
+            &["duplicate: line 4: 1 was already described on line 2"],
+        ),
+    ] {
+        let input = fixture(file);
+        let (output, _) = run(
+            &format!("validate-{file}"),
+            &["validate".as_ref(), input.as_os_str()],
+        );
+        assert_eq!(output.status.code(), Some(2), "{file}");
+        assert_eq!(
+            stdout(&output).lines().collect::<Vec<_>>(),
+            problems,
+            "{file}"
+        );
+        let found = format!("found {} problem(s)", problems.len());
+        assert!(
+            stderr(&output).contains(&found),
+            "{file}: {}",
+            stderr(&output)
+        );
+
+        let args = [
+            "validate".as_ref(),
+            input.as_os_str(),
+            "--warn-only".as_ref(),
+        ];
+        let (output, _) = run(&format!("validate-{file}-warn-only"), &args);
+        assert!(output.status.success(), "{file}: {}", stderr(&output));
+        assert_eq!(
+            stdout(&output).lines().collect::<Vec<_>>(),
+            problems,
+            "{file}"
+        );
+        assert!(
+            stderr(&output).contains(&format!("warning: {found}")),
+            "{file}"
+        );
+    }
+
+    let (output, _) = run_with_stdin("validate-empty", &["validate"], "# nobody came\n");
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(stdout(&output), "empty: nobody is at the party\n");
+
+    let text = "alice: bob, \"Mary Ann\", zoe\nbob: alice, zoe\n\"Mary Ann\": bob\n";
+    let (output, _) = run_with_stdin("validate-names", &["validate"], text);
+    assert_eq!(
+        stdout(&output),
+        "stranger: zoe is not at the party, but alice, bob know them\n"
+    );
+}
+
+#[test]
+fn test_validate_failures() {
+    let (output, _) = run(
+        "validate-invalid",
+        &["validate".as_ref(), fixture("invalid.txt").as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stderr(&output).contains("line 3"));
+}
+
+#[test]
+fn test_completions() {
+    let (output, _) = run("completions-bash", &["completions", "bash"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let script = stdout(&output);
+    assert!(script.contains("solve"));
+    assert!(script.contains("--output-dir"));
+    assert!(script.contains("neato"), "the engines are completed");
+
+    for shell in ["zsh", "fish", "powershell"] {
+        let (output, _) = run(&format!("completions-{shell}"), &["completions", shell]);
+        assert!(output.status.success(), "{shell}: {}", stderr(&output));
+        assert!(stdout(&output).contains("input-format"), "{shell}");
+    }
+
+    let (output, _) = run("completions-csh", &["completions", "csh"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_without_subcommand() {
+    let (output, dir) = run("demo", &["--output-dir", "out"]);
+    assert!(
+        stdout(&output).starts_with(DEMO_CLIQUE),
+        "{}",
+        stdout(&output)
+    );
+    assert!(dir.join("out/graph.dot").exists());
+    assert!(output.status.success(), "{}", stderr(&output));
+}
+
+#[test]
+fn test_annotate() {
+    for (fixture_name, clique) in [
+        ("demo.txt", Some(["1", "2", "3"])),
+        ("no_celebrities.txt", None),
+    ] {
+        let args = [
+            "render".into(),
+            fixture(fixture_name).into_os_string(),
+            "--no-render".into(),
+        ];
+        let (output, dir) = run(&format!("annotate-{fixture_name}"), &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let dot = fs::read_to_string(dir.join("output/graph.dot")).expect("dot file was written");
+
+        let output = run_in(&dir, &["annotate"], dot.as_bytes());
+        let annotated = stdout(&output);
+        let Some(clique) = clique else {
+            assert_eq!(output.status.code(), Some(1));
+            assert_eq!(annotated, format!("// no celebrity clique\n{dot}"));
+            continue;
+        };
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert!(annotated.starts_with(dot.trim_end().trim_end_matches('}')));
+        // The nodes are named by their index in the graph, and labelled with the guest's id first.
+        let labels = (dot.lines())
+            .filter_map(|line| line.trim().split_once(" [ label = "))
+            .filter_map(|(node, label)| {
+                let label = label.split('"').nth(1)?.split("\\n").next()?;
+                Some((format!("\"{node}\""), label))
+            })
+            .collect::<std::collections::HashMap<_, _>>();
+        let mut highlighted = (annotated.lines())
+            .filter_map(|line| {
+                line.trim()
+                    .strip_suffix(" [ style = filled, fillcolor = gold, penwidth = 3 ]")
+            })
+            .map(|node| labels[node])
+            .collect::<Vec<_>>();
+        highlighted.sort_unstable();
+        assert_eq!(highlighted, clique, "{annotated}");
+    }
+}
+
+#[test]
+fn test_check() {
+    let check = |test: &str, party: &str, args: &[&str]| {
+        let party = fixture(party);
+        let mut all = vec!["check".as_ref(), party.as_os_str()];
+        all.extend(args.iter().map(OsStr::new));
+        run(test, &all).0
+    };
+    let output = check("check-valid", "demo.txt", &["--clique", "3,1,2"]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "1, 2, 3: the celebrity clique\n");
+
+    let output = check("check-invalid", "demo.txt", &["--clique", "1,2,3,4"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        stdout(&output),
+        "1, 2, 3, 4: not the celebrity clique, as person 1 does not know proposed celebrity 4\n"
+    );
+    let output = check("check-outsider", "demo.txt", &["--clique", "1,2"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).ends_with("proposed celebrity 1 knows 3, who is not in the clique\n"));
+
+    // Strangers are flagged before anything is checked.
+    let output = check("check-unknown", "demo.txt", &["--clique", "1,2,42"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout(&output).is_empty());
+    assert_eq!(
+        stderr(&output),
+        "error: proposed celebrity 42 is not at the party\n"
+    );
+
+    let output = check(
+        "check-json",
+        "names.txt",
+        &["--clique", "bob,Mary Ann,dave", "--json"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let json = serde_json::from_str::<serde_json::Value>(&stdout(&output)).expect("JSON");
+    assert_eq!(json["valid"], false);
+    assert_eq!(
+        json["names"],
+        serde_json::json!(["Mary Ann", "bob", "dave"])
+    );
+    assert_eq!(
+        json["violation"],
+        "person Mary Ann does not know proposed celebrity dave"
+    );
+}
+
+#[test]
+fn test_stats() {
+    // The demo party: 4 also knows 42, who did not come.
+    let table = "\
+people             7
+acquaintances     23
+out-degree min     2
+out-degree mean 3.29
+out-degree max     5
+strangers          1
+";
+    let demo = fixture("demo.txt");
+    let (output, _) = run("stats", &["stats".as_ref(), demo.as_os_str()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stderr(&output), table);
+    assert!(stdout(&output).is_empty(), "nothing is solved");
+
+    let args = [
+        "solve".as_ref(),
+        demo.as_os_str(),
+        "--stats".as_ref(),
+        "-q".as_ref(),
+    ];
+    let (output, _) = run("solve-stats", &args);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stderr(&output), table);
+    assert_eq!(stdout(&output), DEMO_CLIQUE);
+}
+
+#[test]
+fn test_algorithms() {
+    let algorithms = ["exhaustive", "linear", "scc", "elimination", "auto"];
+    let clique = |party: &str, algorithm: &str| {
+        let party = fixture(party);
+        let args = [
+            "solve".as_ref(),
+            party.as_os_str(),
+            "--json".as_ref(),
+            "-v".as_ref(),
+            "--algorithm".as_ref(),
+            algorithm.as_ref(),
+        ];
+        let (output, _) = run(&format!("algorithms-{algorithm}"), &args);
+        let code = output.status.code();
+        assert!(
+            code.is_some_and(|code| code < 2),
+            "{algorithm}: {}",
+            stderr(&output)
+        );
+        let chosen = if algorithm == "auto" {
+            "exhaustive (auto)"
+        } else {
+            algorithm
+        };
+        let solved = format!("solved with {chosen} in");
+        assert!(stderr(&output).contains(&solved), "{}", stderr(&output));
+        let json = serde_json::from_str::<serde_json::Value>(&stdout(&output)).expect("JSON");
+        json["clique"].clone()
+    };
+    for party in ["demo.txt", "no_celebrities.txt", "names.txt", "edges.txt"] {
+        let expected = clique(party, "exhaustive");
+        for algorithm in algorithms {
+            assert_eq!(clique(party, algorithm), expected, "{party}: {algorithm}");
+        }
+    }
+
+    // Too large for `auto` to search exhaustively, but with few guests known by everybody.
+    let dir = scratch("algorithms-large");
+    let args = [
+        "generate",
+        "--n",
+        "40",
+        "--p",
+        "0.3",
+        "--seed",
+        "5",
+        "--plant-clique",
+        "3",
+        "-o",
+        "large.txt",
+    ];
+    assert!(run_in(&dir, &args, b"").status.success());
+    for algorithm in algorithms {
+        let args = ["solve", "large.txt", "--json", "--algorithm", algorithm];
+        let output = run_in(&dir, &args, b"");
+        assert!(output.status.success(), "{algorithm}: {}", stderr(&output));
+        let json = serde_json::from_str::<serde_json::Value>(&stdout(&output)).expect("JSON");
+        assert_eq!(
+            json["clique"].as_array().map(Vec::len),
+            Some(3),
+            "{algorithm}"
+        );
+    }
+
+    // Everybody knows everybody: too many candidates to search exhaustively.
+    let everybody = (0..21)
+        .map(|id| {
+            let known = (0..21)
+                .filter(|&other| other != id)
+                .map(|other| other.to_string());
+            format!("{id}: {}", known.collect::<Vec<_>>().join(", "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let args = ["solve", "-", "--algorithm", "exhaustive"];
+    let (output, _) = run_with_stdin("algorithms-limit", &args, &everybody);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+    assert!(
+        stderr(&output).contains("subsets to search"),
+        "{}",
+        stderr(&output)
+    );
+    let args = ["solve", "-", "--algorithm", "linear"];
+    let (output, _) = run_with_stdin("algorithms-limit-linear", &args, &everybody);
+    assert!(output.status.success(), "{}", stderr(&output));
+    // Knowing somebody who is not there is only warned about, and the limit still holds.
+    let dangling = everybody.replacen(": ", ": 99, ", 1);
+    let args = ["solve", "-", "--algorithm", "exhaustive"];
+    let (output, _) = run_with_stdin("algorithms-limit-dangling", &args, &dangling);
+    assert_eq!(output.status.code(), Some(2), "{}", stderr(&output));
+    assert!(stderr(&output).contains("[99]"), "{}", stderr(&output));
+    assert!(
+        stderr(&output).contains("subsets to search"),
+        "{}",
+        stderr(&output)
+    );
+
+    let (output, _) = run("algorithms-unknown", &["solve", "--algorithm", "guess"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("exhaustive, linear, scc, elimination, auto"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_dry_run() {
+    let demo = fs::read_to_string(fixture("demo.txt")).expect("fixture");
+    let expected = demo.parse::<Party>().expect("a valid party");
+    for args in [
+        &["render", "-", "--dry-run"][..],
+        &["render", "--output-dir", "-"],
+        &["-", "--dry-run", "-q"],
+    ] {
+        let (output, dir) = run_with_stdin("dry-run", args, &demo);
+        assert!(output.status.success(), "{args:?}: {}", stderr(&output));
+        let dot = stdout(&output);
+        let dot = dot
+            .get(dot.find("digraph {").expect("a digraph")..)
+            .expect("the DOT graph");
+        assert!(dot.ends_with("}\n"), "{dot}");
+        assert_eq!(dot.matches('{').count(), dot.matches('}').count(), "{dot}");
+        for line in dot.lines().skip(1).map(str::trim) {
+            let statement =
+                line.ends_with(']') && (line.contains(" [ label = ") || line.contains(" -> "));
+            let subgraph = line == "subgraph cluster_celebrities {" || line == "}";
+            let caption = line.starts_with("label = \"") && line.ends_with('"');
+            assert!(statement || subgraph || caption, "{line}");
+        }
+        let party =
+            Party::parse_dot(dot, &mut Identifiers::Numbers).expect("the DOT graph reads back");
+        assert_eq!(party.ids(), expected.ids());
+        assert_eq!(
+            party,
+            expected
+                .iter()
+                .map(|person| {
+                    let known = person.known_iter().filter(|&id| expected.contains_id(id));
+                    Person::new(person.id, known)
+                })
+                .collect(),
+            "{args:?}"
+        );
+        assert_eq!(
+            fs::read_dir(&dir).expect("scratch directory").count(),
+            0,
+            "nothing is written"
+        );
+    }
+
+    let (output, _) = run_with_stdin(
+        "dry-run-json",
+        &["render", "-", "--dry-run", "--json"],
+        &demo,
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(stdout(&output).is_empty());
+    assert!(
+        stderr(&output).contains("`--json` cannot be used with `--dry-run`"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_render_highlights_clique() {
+    for (party, clique) in [
+        ("demo.txt", vec!["1", "2", "3"]),
+        ("no_celebrities.txt", vec![]),
+    ] {
+        let args = [
+            "render".into(),
+            fixture(party).into_os_string(),
+            "--no-render".into(),
+        ];
+        let (output, dir) = run::<OsString>(&format!("highlight-{party}"), &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let dot = fs::read_to_string(dir.join("output/graph.dot")).expect("dot file was written");
+        let mut highlighted = (dot.lines())
+            .filter(|line| line.contains("fillcolor = gold"))
+            .filter_map(|line| line.split('"').nth(1)?.split("\\n").next())
+            .collect::<Vec<_>>();
+        highlighted.sort_unstable();
+        assert_eq!(highlighted, clique, "{dot}");
+        assert_eq!(
+            dot.contains("label = \"no celebrity clique\""),
+            clique.is_empty(),
+            "{dot}"
+        );
+    }
+}
+
+#[test]
+fn test_render_cluster() {
+    let cluster = |test: &str, flags: &[&str]| {
+        let party = fixture("names.txt");
+        let mut args = vec!["render".as_ref(), party.as_os_str(), "--dry-run".as_ref()];
+        args.extend(flags.iter().map(OsStr::new));
+        let (output, _) = run(test, &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        let dot = stdout(&output);
+        let start = dot.find("    subgraph cluster_celebrities {\n")?;
+        let end = start + dot[start..].find("\n    }\n")?;
+        Some(dot[start..end].to_owned())
+    };
+    let cluster_of_names = cluster("cluster", &[]).expect("a cluster");
+    assert!(
+        cluster_of_names.contains("label = \"celebrity clique: Mary Ann, bob, Nat King\""),
+        "{cluster_of_names}"
+    );
+    assert_eq!(cluster_of_names.matches("fillcolor = gold").count(), 3);
+    assert!(
+        !cluster_of_names.contains("\"dave\\n"),
+        "{cluster_of_names}"
+    );
+    assert_eq!(cluster("no-cluster", &["--no-cluster"]), None);
+}
+
+#[test]
+fn test_render_merge_mutual() {
+    let render = |test: &str, flags: &[&str]| {
+        let party = fixture("demo.txt");
+        let mut args = vec!["render".as_ref(), party.as_os_str(), "--dry-run".as_ref()];
+        args.extend(flags.iter().map(OsStr::new));
+        let (output, _) = run(test, &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output)
+    };
+    let edges = |dot: &str| dot.lines().filter(|line| line.contains(" -> ")).count();
+    let arrows = render("mutual-arrows", &[]);
+    let merged = render("mutual-merged", &["--merge-mutual"]);
+    let mutual = merged.matches("dir = both").count();
+    assert!(mutual > 0, "{merged}");
+    assert_eq!(edges(&merged) + mutual, edges(&arrows), "{merged}");
+    let read = |dot: &str| Party::parse_dot(dot, &mut Identifiers::Numbers).expect("valid DOT");
+    assert_eq!(read(&merged), read(&arrows));
+}
+
+#[test]
+fn test_render_legend() {
+    let render = |test: &str, party: &str, flags: &[&str]| {
+        let party = fixture(party);
+        let mut args = vec!["render".as_ref(), party.as_os_str(), "--dry-run".as_ref()];
+        args.extend(flags.iter().map(OsStr::new));
+        let (output, _) = run(test, &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        stdout(&output)
+    };
+    assert!(!render("no-legend", "demo.txt", &[]).contains("<table"));
+    let dot = render("legend", "demo.txt", &["--legend", "--merge-mutual"]);
+    let legend = dot.lines().rev().nth(1).expect("the legend");
+    for key in [">celebrity clique<", "&harr;", "&rarr;"] {
+        assert!(legend.contains(key), "{dot}");
+    }
+    let read = |dot: &str| Party::parse_dot(dot, &mut Identifiers::Numbers).expect("valid DOT");
+    assert_eq!(read(&dot), read(&render("legend-plain", "demo.txt", &[])));
+
+    let dot = render("legend-none", "no_celebrities.txt", &["--legend"]);
+    assert!(dot.contains(">no celebrity clique<"), "{dot}");
+    assert!(!dot.contains("&harr;"), "{dot}");
+}
+
+#[test]
+fn test_render_is_deterministic() {
+    let expected = fs::read_to_string(fixture("demo.dot")).expect("fixture");
+    let args = [
+        "render".into(),
+        fixture("demo.txt").into_os_string(),
+        "--no-render".into(),
+    ];
+    let dots = ["deterministic-a", "deterministic-b"].map(|test| {
+        let (output, dir) = run::<OsString>(test, &args);
+        assert!(output.status.success(), "{}", stderr(&output));
+        fs::read_to_string(dir.join("output/graph.dot")).expect("dot file was written")
+    });
+    assert_eq!(dots[0], dots[1]);
+    assert_eq!(dots[0], expected);
+}
@@ -0,0 +1,65 @@
+//! Helpers shared by the integration tests.
+
+use std::{
+    env, fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+};
+
+pub fn fixture(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name)
+}
+
+/// Runs the binary in an empty scratch directory named after `test`, so that its output stays
+/// out of the tree.
+pub fn run<S: AsRef<std::ffi::OsStr>>(test: &str, args: &[S]) -> (Output, PathBuf) {
+    run_with_stdin(test, args, "")
+}
+
+/// Like [`run`], with `stdin` piped into the binary.
+pub fn run_with_stdin<S: AsRef<std::ffi::OsStr>>(
+    test: &str,
+    args: &[S],
+    stdin: &str,
+) -> (Output, PathBuf) {
+    let dir = scratch(test);
+    (run_in(&dir, args, stdin.as_bytes()), dir)
+}
+
+/// An empty scratch directory named after `test`.
+pub fn scratch(test: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("celebrities-{test}"));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("scratch directory");
+    dir
+}
+
+/// Like [`run_with_stdin`], in `dir` as it is.
+pub fn run_in<S: AsRef<std::ffi::OsStr>>(dir: &Path, args: &[S], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_celebrities"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("binary runs");
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(stdin)
+        .expect("binary reads stdin");
+    child.wait_with_output().expect("binary runs")
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,509 @@
+//! Reading parties from files, both through the library and through the binary.
+
+mod common;
+
+use celebrities::{Clique, Party};
+use common::{fixture, run, run_in, run_with_stdin, scratch, stderr, stdout};
+use std::{collections::HashSet, ffi::OsStr, fs, path::Path};
+
+fn parse(name: &str) -> Party {
+    fs::read_to_string(fixture(name))
+        .expect("fixture exists")
+        .parse()
+        .expect("fixture is valid")
+}
+
+#[test]
+fn test_fixtures_parse() {
+    let demo = parse("demo.txt");
+    assert_eq!(demo.len(), 7);
+    assert!(demo.get(4).is_some_and(|person| person.knows_id(&42)));
+    assert_eq!(
+        demo.cclique().map(|css| css.ids()),
+        Some(HashSet::from([1, 2, 3]))
+    );
+
+    let cycle = parse("no_celebrities.txt");
+    assert_eq!(cycle.len(), 3);
+    assert_eq!(cycle.cclique(), None);
+}
+
+#[test]
+fn test_invalid_fixture() {
+    let text = fs::read_to_string(fixture("invalid.txt")).expect("fixture exists");
+    let err = text.parse::<Party>().expect_err("`two` is not an id");
+    assert_eq!(err.line(), 3);
+}
+
+#[test]
+fn test_json_fixture_matches_text() {
+    let json = fs::read_to_string(fixture("demo.json")).expect("fixture exists");
+    let demo = serde_json::from_str::<Party>(&json).expect("fixture is valid");
+    assert_eq!(demo.to_adjacency(), parse("demo.txt").to_adjacency());
+
+    let round_trip = serde_json::to_string(&demo).expect("parties serialize");
+    let back = serde_json::from_str::<Party>(&round_trip).expect("output reads back");
+    assert_eq!(back.to_adjacency(), demo.to_adjacency());
+}
+
+#[test]
+fn test_binary_reads_the_file() {
+    let (output, dir) = run("input-reads", &[fixture("no_celebrities.txt")]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("no celebrity clique"), "{stdout}");
+    // The graph is of the file's party, not the demo's.
+    let dot = fs::read_to_string(dir.join("output/graph.dot")).expect("dot file was written");
+    assert_eq!(dot.matches("->").count(), 3, "{dot}");
+}
+
+#[test]
+fn test_binary_reports_parse_errors() {
+    let (output, _) = run("input-parse-error", &[fixture("invalid.txt")]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("invalid.txt: line 3:"), "{stderr}");
+    assert!(stderr.contains("`two`"), "{stderr}");
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_binary_reads_json() {
+    let text = run(
+        "input-text",
+        &["solve".as_ref(), fixture("demo.txt").as_os_str()],
+    )
+    .0;
+    let json = run(
+        "input-json",
+        &["solve".as_ref(), fixture("demo.json").as_os_str()],
+    )
+    .0;
+    assert!(json.status.success(), "{}", stderr(&json));
+    // Acquaintances are printed in hash order, so only the ids are compared.
+    let ids = |output| {
+        stdout(output)
+            .lines()
+            .filter_map(|line| {
+                line.split(" knows")
+                    .next()?
+                    .split("id: ")
+                    .nth(1)
+                    .map(str::to_owned)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(&json), ["1", "2", "3"]);
+    assert_eq!(ids(&json), ids(&text));
+
+    let party = r#"{"people": [{"id": 1, "knows": [2]}, {"id": 2, "knows": [1]}, {"id": 3, "knows": [1, 2]}]}"#;
+    let (output, _) = run_with_stdin(
+        "input-json-stdin",
+        &["solve", "--input-format", "json"],
+        party,
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "[\n    \"id: 1 knows {2}\",\n    \"id: 2 knows {1}\",\n]\n"
+    );
+
+    // The flag wins over the extension.
+    let (output, _) = run(
+        "input-json-as-text",
+        &[
+            "validate".as_ref(),
+            fixture("demo.json").as_os_str(),
+            "--input-format".as_ref(),
+            "text".as_ref(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("demo.json: line 1:"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_binary_reports_json_errors() {
+    let (output, _) = run(
+        "input-json-duplicate",
+        &["validate".as_ref(), fixture("duplicate.json").as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = stderr(&output);
+    assert!(
+        stderr.contains("duplicate.json: person 1 is described more than once"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_binary_reads_toml() {
+    let example = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/party.toml");
+    let (output, _) = run("input-toml", &["solve".as_ref(), example.as_os_str()]);
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    for id in 1..=3 {
+        assert!(stdout.contains(&format!("\"id: {id} knows")), "{stdout}");
+    }
+    assert_eq!(stdout.matches("knows").count(), 3, "{stdout}");
+
+    let (output, _) = run(
+        "input-toml-tables",
+        &["solve".as_ref(), fixture("named.toml").as_os_str()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = common::stdout(&output);
+    assert!(stdout.contains("\"Alice (1) knows"), "{stdout}");
+    assert!(stdout.contains("\"Bob (2) knows"), "{stdout}");
+}
+
+#[test]
+fn test_binary_reports_toml_errors() {
+    let report = |test, toml| {
+        let (output, _) = run_with_stdin(test, &["validate", "--input-format", "toml"], toml);
+        assert_eq!(output.status.code(), Some(2));
+        stderr(&output)
+    };
+    let stderr = report("input-toml-key", "[people]\n1 = [2]\ntwo = [1]\n");
+    assert!(stderr.contains("line 3, column 1"), "{stderr}");
+    assert!(stderr.contains("`two` is not an id"), "{stderr}");
+
+    let stderr = report(
+        "input-toml-duplicate",
+        "[people]\n1 = [2]\n2 = [1]\n\"01\" = []\n",
+    );
+    assert!(stderr.contains("line 4"), "{stderr}");
+    assert!(
+        stderr.contains("person 1 is described more than once"),
+        "{stderr}"
+    );
+
+    let stderr = report(
+        "input-toml-tables-duplicate",
+        "[[people]]\nid = 1\n\n[[people]]\nid = 1\nknows = [2]\n",
+    );
+    assert!(
+        stderr.contains("person 1 is described more than once"),
+        "{stderr}"
+    );
+    assert!(stderr.contains("line 5, column 6"), "{stderr}");
+
+    let stderr = report("input-toml-value", "[people]\n1 = [2, \"x\"]\n");
+    assert!(stderr.contains("line 2, column"), "{stderr}");
+}
+
+#[test]
+fn test_binary_reads_yaml() {
+    let (output, _) = run(
+        "input-yaml",
+        &["solve".as_ref(), fixture("anchors.yaml").as_os_str()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(
+        stdout(&output),
+        "[\n    \"id: 1 knows {2}\",\n    \"id: 2 knows {1}\",\n]\n"
+    );
+
+    // The alias gives 5 the same acquaintances as 4.
+    let yaml = fs::read_to_string(fixture("anchors.yaml")).expect("fixture exists");
+    let party = serde_yaml_ng::from_str::<Party>(&yaml).expect("fixture is valid");
+    assert_eq!(party.to_adjacency()[&5], HashSet::from([1, 2]));
+    assert_eq!(party.to_adjacency()[&4], party.to_adjacency()[&5]);
+    assert_eq!(
+        party.get(4).and_then(|dave| dave.name.as_deref()),
+        Some("Dave")
+    );
+
+    let (output, _) = run_with_stdin(
+        "input-yaml-stdin",
+        &["solve", "--input-format", "yaml"],
+        &yaml,
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output).matches("knows").count(), 2);
+}
+
+#[test]
+fn test_binary_reports_yaml_errors() {
+    let (output, _) = run(
+        "input-yaml-duplicate",
+        &["validate".as_ref(), fixture("duplicate.yaml").as_os_str()],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = stderr(&output);
+    assert!(
+        stderr.contains("duplicate.yaml: people: person 1 is described more than once"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_binary_reads_its_own_graph() {
+    // The DOT file is written whether or not Graphviz is there to render it.
+    let (_, dir) = run("input-dot", &[fixture("demo.txt")]);
+    let (output, _) = run(
+        "input-dot-solve",
+        &["solve".as_ref(), dir.join("output/graph.dot").as_os_str()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let stdout = stdout(&output);
+    for id in 1..=3 {
+        assert!(stdout.contains(&format!("\"id: {id} knows")), "{stdout}");
+    }
+    assert_eq!(stdout.matches("knows").count(), 3, "{stdout}");
+
+    let (output, _) = run_with_stdin(
+        "input-dot-undirected",
+        &["solve", "--input-format", "dot"],
+        "graph {\n  1 -- 2\n}\n",
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("<stdin>: line 1: the graph is undirected"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_binary_reads_matrices() {
+    let matrix = fs::read_to_string(fixture("demo_matrix.txt")).expect("fixture exists");
+    let party = Party::from_matrix(&matrix).expect("fixture is valid");
+    let mut demo = parse("demo.txt");
+    demo.forget(4, 42);
+    assert_eq!(party.to_adjacency(), demo.to_adjacency());
+
+    let (output, _) = run(
+        "input-matrix",
+        &[
+            "solve".as_ref(),
+            fixture("demo_matrix.txt").as_os_str(),
+            "--input-format".as_ref(),
+            "matrix".as_ref(),
+        ],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output).matches("knows").count(), 3);
+
+    let (output, _) = run_with_stdin(
+        "input-matrix-error",
+        &["solve", "--input-format", "matrix"],
+        "0 1\n1 2\n",
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("<stdin>: row 2: `2` in column 2 is not 0 or 1"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_binary_reads_edges() {
+    // Detected from the lines, since the extension says nothing.
+    let (output, _) = run(
+        "input-edges",
+        &["solve".as_ref(), fixture("edges.txt").as_os_str()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), "[\n    \"id: 3 knows {}\",\n]\n");
+
+    let (output, _) = run_with_stdin("input-edges-stdin", &["solve"], "1 2\n2 1\n1 2\n");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output).matches("knows").count(), 2);
+
+    let (output, _) = run_with_stdin(
+        "input-edges-forced",
+        &["solve", "--input-format", "edges"],
+        "1 2\n2 1 3\n",
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("<stdin>: line 2: expected two ids"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+/// The party the binary reads from `args`, one sorted `id: known, ...` line per guest.
+fn shown(test: &str, args: &[&OsStr], stdin: &[u8]) -> String {
+    let mut args = args.to_vec();
+    args.insert(0, "repl".as_ref());
+    let output = run_in(&scratch(test), &args, &[stdin, b"show\n"].concat());
+    assert!(output.status.success(), "{test}: {}", stderr(&output));
+    stdout(&output)
+}
+
+#[test]
+fn test_binary_reads_gzip() {
+    for (plain, gzipped) in [
+        ("demo.txt", "demo.txt.gz"),
+        ("demo.json", "demo.json.gz"),
+        ("edges.txt", "edges.csv.gz"),
+    ] {
+        let expected = shown(plain, &[fixture(plain).as_os_str()], b"");
+        assert!(expected.lines().count() >= 3, "{plain}: {expected}");
+        let name = format!("gzip-{gzipped}");
+        assert_eq!(shown(&name, &[fixture(gzipped).as_os_str()], b""), expected);
+    }
+
+    // Piped in, the stream is recognized by its first bytes.
+    let gzipped = fs::read(fixture("demo.txt.gz")).expect("fixture exists");
+    let output = run_in(&scratch("gzip-stdin"), &["solve"], &gzipped);
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(
+        stdout(&output).contains("\"id: 1 knows"),
+        "{}",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn test_binary_reports_gzip_errors() {
+    let corrupt = fixture("corrupt.txt.gz");
+    let (output, _) = run("gzip-corrupt", &["solve".as_ref(), corrupt.as_os_str()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("corrupt.txt.gz: incomplete deflate stream"),
+        "{}",
+        stderr(&output)
+    );
+
+    // `.gz` says the file is compressed, whatever is in it.
+    let dir = scratch("gzip-header");
+    fs::write(dir.join("party.txt.gz"), "1: 2\n2: 1\n").expect("scratch file");
+    let output = run_in(&dir, &["solve", "party.txt.gz"], b"");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("party.txt.gz: invalid gzip header"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+#[test]
+fn test_binary_reports_missing_files() {
+    let (output, _) = run("input-missing", &[Path::new("nobody.txt")]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("nobody.txt"), "{stderr}");
+}
+
+#[test]
+fn test_binary_reads_stdin() {
+    let party = "1: 2\n2: 1\n3: 1, 2\n";
+    for args in [&["solve", "-"][..], &["solve"]] {
+        let (output, _) = run_with_stdin("input-stdin", args, party);
+        assert!(output.status.success(), "{}", stderr(&output));
+        assert_eq!(
+            stdout(&output),
+            "[\n    \"id: 1 knows {2}\",\n    \"id: 2 knows {1}\",\n]\n"
+        );
+    }
+}
+
+#[test]
+fn test_binary_reports_stdin_errors() {
+    let (output, _) = run_with_stdin("input-stdin-error", &["validate"], "1: 2\n2 1\n");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("<stdin>: line 2:"),
+        "{}",
+        stderr(&output)
+    );
+}
+
+/// A terminal on stdin gets the usage rather than a wait for input. `script` provides the
+/// terminal, so the test is skipped where it is not installed.
+#[cfg(target_os = "linux")]
+#[test]
+fn test_binary_does_not_wait_for_a_terminal() {
+    use std::{
+        process::{Command, Stdio},
+        thread,
+        time::{Duration, Instant},
+    };
+
+    let binary = env!("CARGO_BIN_EXE_celebrities");
+    let Ok(mut child) = Command::new("script")
+        .args(["-qec", &format!("{binary} solve"), "/dev/null"])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .spawn()
+    else {
+        return;
+    };
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().expect("script runs") {
+            break status;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            child.kill().expect("script can be killed");
+            panic!("the binary waited for input on a terminal");
+        }
+        thread::sleep(Duration::from_millis(20));
+    };
+    let output = child.wait_with_output().expect("script runs");
+    assert_eq!(status.code(), Some(2));
+    assert!(stdout(&output).contains("Usage:"), "{}", stdout(&output));
+}
+
+#[test]
+fn test_binary_reads_names() {
+    let (output, _) = run(
+        "input-names",
+        &["solve".as_ref(), fixture("names.txt").as_os_str()],
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    let solved = stdout(&output);
+    for line in [
+        "\"Mary Ann knows {Nat King, bob}\"",
+        "\"Nat King knows {Mary Ann, bob}\"",
+        "\"bob knows {Mary Ann, Nat King}\"",
+    ] {
+        assert!(solved.contains(line), "{solved}");
+    }
+    assert_eq!(solved.matches("knows").count(), 3, "{solved}");
+
+    // The graph is labelled with the names, which are kept when it is read back.
+    let (_, dir) = run("input-names-render", &[fixture("names.txt")]);
+    let dot = fs::read_to_string(dir.join("output/graph.dot")).expect("graph is written");
+    assert!(
+        dot.contains("label = \"Mary Ann\\nknows 2 / known by 3\""),
+        "{dot}"
+    );
+    let (output, _) = run(
+        "input-names-dot",
+        &["solve".as_ref(), dir.join("output/graph.dot").as_os_str()],
+    );
+    let solved = stdout(&output);
+    assert!(solved.contains("\"Nat King ("), "{solved}");
+    assert_eq!(solved.matches("knows").count(), 3, "{solved}");
+
+    let (output, _) = run_with_stdin(
+        "input-names-json",
+        &["solve", "--input-format", "json"],
+        r#"{"people": [{"id": "ann", "knows": ["bo"]}, {"id": "bo", "knows": ["ann"]}]}"#,
+    );
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).contains("\"ann knows {bo}\""));
+
+    let (output, _) = run(
+        "input-names-numbers",
+        &[
+            "solve".as_ref(),
+            "--ids".as_ref(),
+            fixture("names.txt").as_os_str(),
+        ],
+    );
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        stderr(&output).contains("names.txt: line 2"),
+        "{}",
+        stderr(&output)
+    );
+}